@@ -0,0 +1,236 @@
+// Horneado de las capas estáticas de las superficies en texturas
+// equirectangulares de albedo, normal y materiales. Evaluar el ruido en cada
+// píxel de cada cuadro es lo que más tiempo se lleva; lo que no cambia con el
+// tiempo se calcula una vez, se guarda en disco y después solo se muestrea.
+// Las nubes, las estrellas y los gigantes (bandas y tormentas que se mueven)
+// se siguen calculando en cada cuadro.
+
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+use nalgebra_glm::Vec3;
+use crate::catalog::BodyInfo;
+use crate::color::Color;
+use crate::planet_shaders::{family_style, SurfaceSample, SurfaceStyle};
+
+const CACHE_DIR: &str = "cache/bake";
+const MAGIC: &[u8; 4] = b"LBAK";
+// Cambiarlo al tocar el código de las capas estáticas invalida la caché
+const BAKE_VERSION: u32 = 3;
+
+pub const MIN_RESOLUTION: usize = 64;
+pub const MAX_RESOLUTION: usize = 8192;
+
+// Textura de un cuerpo: ancho = 2 × alto, longitud -180° a 180° de izquierda
+// a derecha y latitud 90° a -90° de arriba abajo
+pub struct BakedSurface {
+    key: u64,
+    width: usize,
+    height: usize,
+    albedo: Vec<u8>,   // RGB
+    normal: Vec<u8>,   // XYZ del espacio del modelo, de [-1, 1] a [0, 255]
+    material: Vec<u8>, // Máscaras de reflejo y de luces de ciudad
+}
+
+impl BakedSurface {
+    // Evalúa la capa estática en el centro de cada texel. None si la familia
+    // no tiene capa estática.
+    fn bake(style: &SurfaceStyle, width: usize, key: u64) -> Option<Self> {
+        style.surface(Vec3::new(0.0, 1.0, 0.0))?;
+        let height = width / 2;
+        let mut albedo = vec![0u8; width * height * 3];
+        let mut normal = vec![0u8; width * height * 3];
+        let mut material = vec![0u8; width * height * 2];
+
+        // Franjas de filas en paralelo, una por núcleo
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let rows_per_thread = height.div_ceil(threads);
+        let chunk_len = rows_per_thread * width;
+        thread::scope(|scope| {
+            let rows = albedo.chunks_mut(chunk_len * 3).zip(normal.chunks_mut(chunk_len * 3)).zip(material.chunks_mut(chunk_len * 2));
+            for (chunk, ((albedo_rows, normal_rows), material_rows)) in rows.enumerate() {
+                scope.spawn(move || {
+                    let texels = albedo_rows.chunks_exact_mut(3).zip(normal_rows.chunks_exact_mut(3)).zip(material_rows.chunks_exact_mut(2));
+                    for (i, ((albedo_texel, normal_texel), material_texel)) in texels.enumerate() {
+                        let x = i % width;
+                        let y = chunk * rows_per_thread + i / width;
+                        if let Some(sample) = style.surface(texel_direction(x, y, width, height)) {
+                            albedo_texel.copy_from_slice(&[sample.albedo.r(), sample.albedo.g(), sample.albedo.b()]);
+                            for (byte, component) in normal_texel.iter_mut().zip(sample.normal.iter()) {
+                                *byte = ((component * 0.5 + 0.5) * 255.0).round().clamp(0.0, 255.0) as u8;
+                            }
+                            material_texel.copy_from_slice(&[unit_to_byte(sample.specular), unit_to_byte(sample.lights)]);
+                        }
+                    }
+                });
+            }
+        });
+
+        Some(BakedSurface { key, width, height, albedo, normal, material })
+    }
+
+    // Capa estática en una dirección del modelo, con filtrado bilineal
+    pub fn sample(&self, direction: Vec3) -> SurfaceSample {
+        let direction = direction.normalize();
+        let u = (direction.z.atan2(direction.x) + PI) / TAU;
+        let v = (FRAC_PI_2 - direction.y.clamp(-1.0, 1.0).asin()) / PI;
+
+        let fx = u * self.width as f32 - 0.5;
+        let fy = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        // La longitud da la vuelta; la latitud se queda en el borde
+        let column = |x: f32| (x as i32).rem_euclid(self.width as i32) as usize;
+        let (x0, x1) = (column(x0), column(x0 + 1.0));
+        let (y0, y1) = (y0 as usize, (y0 as usize + 1).min(self.height - 1));
+
+        // Capas de 3 canales, o de 2 con z = 0
+        let texel = |data: &[u8], channels: usize, x: usize, y: usize| {
+            let i = (y * self.width + x) * channels;
+            let z = if channels == 3 { data[i + 2] as f32 } else { 0.0 };
+            Vec3::new(data[i] as f32, data[i + 1] as f32, z)
+        };
+        let bilinear = |data: &[u8], channels: usize| {
+            let top = texel(data, channels, x0, y0) * (1.0 - tx) + texel(data, channels, x1, y0) * tx;
+            let bottom = texel(data, channels, x0, y1) * (1.0 - tx) + texel(data, channels, x1, y1) * tx;
+            top * (1.0 - ty) + bottom * ty
+        };
+
+        let albedo = bilinear(&self.albedo, 3);
+        let normal = bilinear(&self.normal, 3) / 255.0 * 2.0 - Vec3::new(1.0, 1.0, 1.0);
+        let material = bilinear(&self.material, 2) / 255.0;
+        SurfaceSample {
+            albedo: Color::new(albedo.x.round() as u8, albedo.y.round() as u8, albedo.z.round() as u8),
+            normal: normal.normalize(),
+            specular: material.x,
+            lights: material.y,
+        }
+    }
+
+    // Formato: "LBAK", versión, clave, ancho, alto, albedo, normal y materiales
+    fn save(&self, path: &PathBuf) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut bytes = Vec::with_capacity(24 + self.albedo.len() * 2 + self.material.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&BAKE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.key.to_le_bytes());
+        bytes.extend_from_slice(&(self.width as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.albedo);
+        bytes.extend_from_slice(&self.normal);
+        bytes.extend_from_slice(&self.material);
+        fs::write(path, bytes)
+    }
+
+    // None si el archivo no existe o no corresponde a la clave
+    fn load(path: &PathBuf, key: u64) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        let header = bytes.get(..24)?;
+        let read_u32 = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+        if &header[..4] != MAGIC || read_u32(4) != BAKE_VERSION {
+            return None;
+        }
+        let stored_key = u64::from_le_bytes(header[8..16].try_into().ok()?);
+        let (width, height) = (read_u32(16) as usize, read_u32(20) as usize);
+        let layer = width * height * 3;
+        if stored_key != key || bytes.len() != 24 + layer * 2 + width * height * 2 {
+            return None;
+        }
+        Some(BakedSurface {
+            key,
+            width,
+            height,
+            albedo: bytes[24..24 + layer].to_vec(),
+            normal: bytes[24 + layer..24 + layer * 2].to_vec(),
+            material: bytes[24 + layer * 2..].to_vec(),
+        })
+    }
+}
+
+fn unit_to_byte(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+// Dirección del modelo en el centro del texel (x, y)
+fn texel_direction(x: usize, y: usize, width: usize, height: usize) -> Vec3 {
+    let longitude = (x as f32 + 0.5) / width as f32 * TAU - PI;
+    let latitude = FRAC_PI_2 - (y as f32 + 0.5) / height as f32 * PI;
+    Vec3::new(latitude.cos() * longitude.cos(), latitude.sin(), latitude.cos() * longitude.sin())
+}
+
+// Clave de la caché: cambia si cambia cualquier parámetro del estilo, la
+// resolución o la versión del horneado (FNV-1a de 64 bits)
+fn cache_key(style: &SurfaceStyle, width: usize) -> u64 {
+    let text = format!("{}|{}|{:?}", BAKE_VERSION, width, style);
+    text.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+fn cache_path(key: u64) -> PathBuf {
+    PathBuf::from(CACHE_DIR).join(format!("{:016x}.bin", key))
+}
+
+// Texturas horneadas de los cuerpos del sistema activo
+pub struct BakedSurfaces {
+    resolution: usize,
+    surfaces: HashMap<String, BakedSurface>,
+}
+
+impl BakedSurfaces {
+    pub fn new(resolution: usize) -> Self {
+        BakedSurfaces { resolution, surfaces: HashMap::new() }
+    }
+
+    // Hornea (o lee de la caché) las superficies que cambiaron desde la
+    // última vez; las que siguen igual no se tocan. Los cuerpos sin parámetros
+    // propios usan los de fábrica de su familia, como al dibujarlos.
+    pub fn refresh(&mut self, bodies: &[Arc<BodyInfo>]) {
+        let mut surfaces = HashMap::new();
+        for body in bodies {
+            let Some(style) = body.surface.clone().or_else(|| family_style(body.shader_id)) else {
+                continue;
+            };
+            let key = cache_key(&style, self.resolution);
+            if let Some(baked) = self.surfaces.remove(body.name.as_ref()).filter(|baked| baked.key == key) {
+                surfaces.insert(body.name.to_string(), baked);
+                continue;
+            }
+
+            let path = cache_path(key);
+            if let Some(baked) = BakedSurface::load(&path, key) {
+                surfaces.insert(body.name.to_string(), baked);
+                continue;
+            }
+
+            let start = Instant::now();
+            let Some(baked) = BakedSurface::bake(&style, self.resolution, key) else {
+                continue;
+            };
+            println!(
+                "Superficie de {} horneada ({}x{}) en {:.1} s",
+                body.name,
+                baked.width,
+                baked.height,
+                start.elapsed().as_secs_f32()
+            );
+            if let Err(e) = baked.save(&path) {
+                println!("No se pudo guardar la superficie en la caché ({}): {}", path.display(), e);
+            }
+            surfaces.insert(body.name.to_string(), baked);
+        }
+        if surfaces.is_empty() {
+            println!("--bake: ningún cuerpo del sistema tiene capa estática que hornear");
+        }
+        self.surfaces = surfaces;
+    }
+
+    pub fn get(&self, info: &BodyInfo) -> Option<&BakedSurface> {
+        self.surfaces.get(info.name.as_ref())
+    }
+}
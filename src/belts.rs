@@ -0,0 +1,282 @@
+// Cinturón de asteroides y cinturón de Kuiper: miles de rocas generadas al
+// arrancar a partir de unas pocas mallas deformadas. Cada roca tiene su propia
+// órbita kepleriana, tamaño y giro, así que no hace falta guardar estado:
+// la posición sale del tiempo de la simulación.
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::sync::Arc;
+use nalgebra_glm::{Vec2, Vec3};
+use crate::catalog::{self, orbit_speed_for_au, SceneScale};
+use crate::kepler::KeplerOrbit;
+use crate::rng::Rng;
+use crate::vertex::Vertex;
+
+const BELT_SEED: u64 = 0x5EED_0B17;
+const ROCK_MESH_COUNT: usize = 6;
+const ASTEROID_COUNT: usize = 2500;
+const KUIPER_COUNT: usize = 600;
+
+// Huecos de Kirkwood: resonancias con Júpiter que vacían esas órbitas (UA)
+const KIRKWOOD_GAPS: [f32; 4] = [2.50, 2.82, 2.95, 3.27];
+const KIRKWOOD_HALF_WIDTH: f32 = 0.03;
+
+// Elementos de la órbita de Plutón que no están en el catálogo
+const PLUTO_ECCENTRICITY: f32 = 0.2488;
+const PLUTO_ASCENDING_NODE_DEG: f32 = 110.3;
+const PLUTO_PERIAPSIS_ARGUMENT_DEG: f32 = 113.8;
+
+pub struct RockMesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+struct Rock {
+    orbit: KeplerOrbit,
+    mesh: usize,
+    scale: f32,
+    spin: Vec3,       // Radianes por unidad de tiempo en cada eje
+    spin_phase: Vec3, // Orientación inicial
+}
+
+// Roca en el instante actual, lista para dibujar
+pub struct RockInstance {
+    pub mesh: usize,
+    pub position: Vec3,
+    pub rotation: Vec3,
+    pub scale: f32,
+}
+
+// Franja de un sistema generado donde poner un cinturón (UA)
+pub struct BeltZone {
+    pub inner_au: f32,
+    pub outer_au: f32,
+    pub count: usize,
+    pub icy: bool, // Más allá de la línea de hielo: rocas tipo Kuiper
+}
+
+pub struct Belt {
+    pub shader_id: u32,
+    pub point_color: u32, // Color de las rocas lejanas que se dibujan como puntos
+    rocks: Vec<Rock>,
+}
+
+impl Belt {
+    pub fn instances(&self, elapsed: f32) -> impl Iterator<Item = RockInstance> + '_ {
+        self.rocks.iter().map(move |rock| RockInstance {
+            mesh: rock.mesh,
+            position: rock.orbit.position(elapsed),
+            rotation: rock.spin_phase + rock.spin * elapsed,
+            scale: rock.scale,
+        })
+    }
+}
+
+// Mallas compartidas y los dos cinturones, siempre iguales para la misma semilla
+pub fn create_belts() -> (Vec<RockMesh>, Vec<Belt>) {
+    let mut rng = Rng::new(BELT_SEED);
+    let meshes = (0..ROCK_MESH_COUNT).map(|_| create_rock_mesh(&mut rng)).collect();
+    let scene_scale = Arc::new(catalog::scene_scale());
+    let belts = vec![asteroid_belt(&mut rng, &scene_scale), kuiper_belt(&mut rng, &scene_scale)];
+    (meshes, belts)
+}
+
+// Cinturones de un sistema generado; las mallas salen de la misma semilla
+pub fn create_belts_in(zones: &[BeltZone], seed: u64) -> (Vec<RockMesh>, Vec<Belt>) {
+    let mut rng = Rng::new(seed ^ BELT_SEED);
+    let meshes = (0..ROCK_MESH_COUNT).map(|_| create_rock_mesh(&mut rng)).collect();
+    let scene_scale = Arc::new(catalog::scene_scale());
+    let belts = zones.iter().map(|zone| zone_belt(&mut rng, zone, &scene_scale)).collect();
+    (meshes, belts)
+}
+
+fn zone_belt(rng: &mut Rng, zone: &BeltZone, scene_scale: &Arc<SceneScale>) -> Belt {
+    let size_range = if zone.icy { (1.5, 4.5) } else { (0.8, 3.5) };
+    let rocks = (0..zone.count)
+        .map(|_| {
+            let au = rng.range(zone.inner_au, zone.outer_au);
+            let eccentricity = 0.15 * rng.next_f32() * rng.next_f32();
+            let inclination = 12.0 * rng.next_f32() * rng.next_f32();
+            create_rock(rng, scene_scale, au, eccentricity, inclination, size_range)
+        })
+        .collect();
+    if zone.icy {
+        Belt { shader_id: 21, point_color: 0x9AB4C8, rocks }
+    } else {
+        Belt { shader_id: 20, point_color: 0x8A8070, rocks }
+    }
+}
+
+// Entre Marte y Júpiter, con los huecos de Kirkwood
+fn asteroid_belt(rng: &mut Rng, scene_scale: &Arc<SceneScale>) -> Belt {
+    let mut rocks = Vec::with_capacity(ASTEROID_COUNT);
+    while rocks.len() < ASTEROID_COUNT {
+        let au = rng.range(2.1, 3.3);
+        if KIRKWOOD_GAPS.iter().any(|gap| (au - gap).abs() < KIRKWOOD_HALF_WIDTH) {
+            continue;
+        }
+        // El producto de dos aleatorios deja la mayoría con valores bajos
+        let eccentricity = 0.2 * rng.next_f32() * rng.next_f32();
+        let inclination = 15.0 * rng.next_f32() * rng.next_f32();
+        rocks.push(create_rock(rng, scene_scale, au, eccentricity, inclination, (0.8, 3.5)));
+    }
+    Belt { shader_id: 20, point_color: 0x8A8070, rocks }
+}
+
+// Más allá de Neptuno: los plutinos comparten la resonancia 3:2 de Plutón y
+// tienen órbitas más alargadas; el resto es el cinturón clásico
+fn kuiper_belt(rng: &mut Rng, scene_scale: &Arc<SceneScale>) -> Belt {
+    let mut rocks = Vec::with_capacity(KUIPER_COUNT);
+    for i in 0..KUIPER_COUNT {
+        let rock = if i % 5 == 0 {
+            let au = rng.range(39.2, 39.7);
+            let eccentricity = rng.range(0.1, 0.3);
+            let inclination = rng.range(0.0, 20.0);
+            create_rock(rng, scene_scale, au, eccentricity, inclination, (1.5, 4.5))
+        } else {
+            let au = rng.range(42.0, 48.0);
+            let eccentricity = 0.1 * rng.next_f32();
+            let inclination = 10.0 * rng.next_f32() * rng.next_f32();
+            create_rock(rng, scene_scale, au, eccentricity, inclination, (1.5, 4.5))
+        };
+        rocks.push(rock);
+    }
+    Belt { shader_id: 21, point_color: 0x9AB4C8, rocks }
+}
+
+fn create_rock(rng: &mut Rng, scene_scale: &Arc<SceneScale>, au: f32, eccentricity: f32, inclination_deg: f32, size_range: (f32, f32)) -> Rock {
+    let orbit = KeplerOrbit {
+        semi_major_axis: au,
+        eccentricity,
+        inclination: inclination_deg.to_radians(),
+        ascending_node: rng.range(0.0, TAU),
+        periapsis_argument: rng.range(0.0, TAU),
+        mean_anomaly_at_epoch: rng.range(0.0, TAU),
+        mean_motion: orbit_speed_for_au(au),
+        scene_scale: Arc::clone(scene_scale),
+    };
+    // Muchas rocas pequeñas y pocas grandes
+    let (min_scale, max_scale) = size_range;
+    let scale = min_scale * (max_scale / min_scale).powf(rng.next_f32().powi(3));
+    Rock {
+        orbit,
+        mesh: rng.index(ROCK_MESH_COUNT),
+        scale,
+        spin: Vec3::new(rng.range(-1.5, 1.5), rng.range(-1.5, 1.5), rng.range(-1.5, 1.5)),
+        spin_phase: Vec3::new(rng.range(0.0, TAU), rng.range(0.0, TAU), rng.range(0.0, TAU)),
+    }
+}
+
+// Solo existe en el Sistema Solar
+pub fn pluto_orbit() -> Option<KeplerOrbit> {
+    let pluto = catalog::find("Plutón")?;
+    Some(KeplerOrbit {
+        semi_major_axis: pluto.semi_major_axis_au(),
+        eccentricity: PLUTO_ECCENTRICITY,
+        inclination: pluto.orbit_inclination_deg.to_radians(),
+        ascending_node: PLUTO_ASCENDING_NODE_DEG.to_radians(),
+        periapsis_argument: PLUTO_PERIAPSIS_ARGUMENT_DEG.to_radians(),
+        mean_anomaly_at_epoch: 0.0,
+        mean_motion: pluto.orbit_speed(),
+        scene_scale: Arc::new(catalog::scene_scale()),
+    })
+}
+
+// === MALLAS ===
+
+// Icosfera deformada: alargada en dos ejes, con bultos suaves y un par de
+// cráteres hundidos
+fn create_rock_mesh(rng: &mut Rng) -> RockMesh {
+    let (points, faces) = create_icosphere(1);
+
+    let axes = Vec3::new(1.0, rng.range(0.55, 0.9), rng.range(0.5, 0.85));
+    let lumps: Vec<(Vec3, f32, f32)> = (0..4)
+        .map(|_| (rng.unit_vector(), rng.range(2.0, 4.0), rng.range(0.06, 0.14)))
+        .collect();
+    let craters: Vec<Vec3> = (0..2).map(|_| rng.unit_vector()).collect();
+
+    let positions: Vec<Vec3> = points
+        .iter()
+        .map(|p| {
+            let mut radius = 1.0;
+            for (direction, frequency, amplitude) in &lumps {
+                radius += (p.dot(direction) * frequency).sin() * amplitude;
+            }
+            for crater in &craters {
+                let angle = p.dot(crater).clamp(-1.0, 1.0).acos();
+                if angle < 0.4 {
+                    radius -= 0.12 * (1.0 - (angle / 0.4).powi(2));
+                }
+            }
+            p.component_mul(&axes) * radius
+        })
+        .collect();
+
+    // Normales suaves: promedio de las caras que comparten cada vértice.
+    // Las caras se ordenan igual que en create_sphere, que es el sentido que
+    // espera el descarte de caras traseras del rasterizador.
+    let mut normals = vec![Vec3::zeros(); positions.len()];
+    let mut indices = Vec::with_capacity(faces.len() * 3);
+    for face in &faces {
+        let [a, b, c] = face.map(|i| i as usize);
+        let mut normal = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+        if normal.dot(&(positions[a] + positions[b] + positions[c])) > 0.0 {
+            indices.extend([face[0], face[2], face[1]]);
+        } else {
+            indices.extend(*face);
+            normal = -normal;
+        }
+        for i in [a, b, c] {
+            normals[i] += normal;
+        }
+    }
+
+    let vertices = positions
+        .iter()
+        .zip(&normals)
+        .map(|(position, normal)| Vertex::new(*position, normal.normalize(), Vec2::new(0.0, 0.0)))
+        .collect();
+    RockMesh { vertices, indices }
+}
+
+// Icosaedro subdividido y proyectado sobre la esfera unitaria
+fn create_icosphere(subdivisions: u32) -> (Vec<Vec3>, Vec<[u32; 3]>) {
+    let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let mut points: Vec<Vec3> = [
+        (-1.0, t, 0.0), (1.0, t, 0.0), (-1.0, -t, 0.0), (1.0, -t, 0.0),
+        (0.0, -1.0, t), (0.0, 1.0, t), (0.0, -1.0, -t), (0.0, 1.0, -t),
+        (t, 0.0, -1.0), (t, 0.0, 1.0), (-t, 0.0, -1.0), (-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| Vec3::new(x, y, z).normalize())
+    .collect();
+
+    let mut faces: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut midpoint = |a: u32, b: u32, points: &mut Vec<Vec3>| -> u32 {
+            let key = (a.min(b), a.max(b));
+            *midpoints.entry(key).or_insert_with(|| {
+                points.push(((points[a as usize] + points[b as usize]) * 0.5).normalize());
+                (points.len() - 1) as u32
+            })
+        };
+
+        let mut subdivided = Vec::with_capacity(faces.len() * 4);
+        for [a, b, c] in faces {
+            let ab = midpoint(a, b, &mut points);
+            let bc = midpoint(b, c, &mut points);
+            let ca = midpoint(c, a, &mut points);
+            subdivided.extend([[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+        }
+        faces = subdivided;
+    }
+
+    (points, faces)
+}
@@ -0,0 +1,91 @@
+// Relieve en la normal (bump mapping) para los shaders de superficie. Un
+// shader describe la altura del terreno y `height_normal` da la normal con
+// relieve sobre la esfera unidad, en el espacio del modelo, que es lo que se
+// hornea con la capa estática. Al dibujar, `perturb` pasa esa inclinación a
+// la base tangente y la aplica a la normal interpolada de la malla: los
+// cuerpos que no son esferas conservan su forma y solo ganan los detalles.
+
+use nalgebra_glm::{Mat3, Vec3};
+
+// Paso de las diferencias finitas, en radios del cuerpo. Más pequeño que los
+// detalles más finos (los cráteres menores miden unos 0.01)
+const EPSILON: f32 = 0.0015;
+
+// Direcciones este y norte sobre la esfera en un punto. En los polos, donde
+// no están definidas, se toma cualquier pareja perpendicular.
+pub fn tangent_basis(sphere_pos: Vec3) -> (Vec3, Vec3) {
+    let up = if sphere_pos.y.abs() > 0.999 { Vec3::new(1.0, 0.0, 0.0) } else { Vec3::new(0.0, 1.0, 0.0) };
+    let east = up.cross(&sphere_pos).normalize();
+    let north = sphere_pos.cross(&east);
+    (east, north)
+}
+
+// Normal de una función de altura sobre la esfera, por diferencias finitas
+// hacia el este y el norte. Sirve con ruido o con perfiles como los de los
+// cráteres; evalúa `height` tres veces. Subir hacia el este inclina la normal
+// hacia el oeste; `strength` exagera o suaviza el relieve.
+pub fn height_normal(sphere_pos: Vec3, strength: f32, height: impl Fn(Vec3) -> f32) -> Vec3 {
+    let (east, north) = tangent_basis(sphere_pos);
+    let center = height(sphere_pos);
+    let d_east = height((sphere_pos + east * EPSILON).normalize()) - center;
+    let d_north = height((sphere_pos + north * EPSILON).normalize()) - center;
+    // El gradiente ya es tangente a la esfera
+    let gradient = (east * d_east + north * d_north) / EPSILON;
+    (sphere_pos - gradient * strength).normalize()
+}
+
+// Normal en el mundo con el relieve de `bumped` (una normal de
+// `height_normal` en `sphere_pos`) aplicado sobre `base`, la normal
+// interpolada de la malla ya en el mundo. `to_world` orienta el este de la
+// esfera en el mundo; sin relieve devuelve `base` tal cual.
+pub fn perturb(base: Vec3, sphere_pos: Vec3, bumped: Vec3, to_world: &Mat3) -> Vec3 {
+    if bumped == sphere_pos {
+        return base;
+    }
+    let (east, north) = tangent_basis(sphere_pos);
+    // Base tangente alrededor de `base`, con el este de la esfera aplanado sobre ella
+    let east_world = to_world * east;
+    let east_world = east_world - base * east_world.dot(&base);
+    if east_world.magnitude_squared() < 1e-12 {
+        return base;
+    }
+    let east_world = east_world.normalize();
+    let north_world = base.cross(&east_world);
+    (east_world * bumped.dot(&east) + north_world * bumped.dot(&north) + base * bumped.dot(&sphere_pos)).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ripples(p: Vec3) -> f32 {
+        (p.x * 40.0).sin() * 0.01 + (p.z * 25.0).cos() * 0.01
+    }
+
+    #[test]
+    fn perturb_on_a_sphere_matches_the_bumped_normal() {
+        let sphere_pos = Vec3::new(0.3, 0.5, -0.8).normalize();
+        let bumped = height_normal(sphere_pos, 1.0, ripples);
+        let normal = perturb(sphere_pos, sphere_pos, bumped, &Mat3::identity());
+        assert!((normal - bumped).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn perturb_keeps_the_mesh_normal_without_relief() {
+        let sphere_pos = Vec3::new(0.0, 0.6, 0.8);
+        let base = Vec3::new(0.2, 0.9, 0.1).normalize();
+        assert_eq!(perturb(base, sphere_pos, sphere_pos, &Mat3::identity()), base);
+    }
+
+    #[test]
+    fn perturb_tilts_the_mesh_normal_by_the_same_angle() {
+        let sphere_pos = Vec3::new(0.6, 0.0, 0.8);
+        let bumped = height_normal(sphere_pos, 1.0, ripples);
+        // Normal de una malla aplastada: no coincide con la de la esfera
+        let base = Vec3::new(0.3, 0.6, 0.74).normalize();
+        let normal = perturb(base, sphere_pos, bumped, &Mat3::identity());
+        let tilt = bumped.dot(&sphere_pos).acos();
+        assert!(tilt > 0.01);
+        assert!((normal.dot(&base).acos() - tilt).abs() < 1e-3);
+    }
+}
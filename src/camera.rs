@@ -1,5 +1,4 @@
-use nalgebra_glm::{Vec3, Mat4, perspective, look_at, ortho};
-use std::f32::consts::PI;
+use nalgebra_glm::{Vec3, Mat4, perspective, look_at};
 
 pub struct Camera {
     pub position: Vec3,
@@ -25,7 +24,7 @@ impl Camera {
             fov: 35.0_f32.to_radians(), // Reducir FOV para menos distorsión
             aspect_ratio: width / height,
             near: 0.1,
-            far: 5000.0,
+            far: 10000.0,
             distance_from_target: 1200.0, // Alejarse más para ver mejor
             horizontal_angle: 0.0,
             vertical_angle: 0.3, // Ángulo más suave
@@ -37,16 +36,7 @@ impl Camera {
     }
 
     pub fn get_projection_matrix(&self) -> Mat4 {
-        // CAMBIO TEMPORAL: Usar proyección ortográfica para eliminar distorsión
-        let left = -600.0;
-        let right = 600.0; 
-        let bottom = -400.0;
-        let top = 400.0;
-        
-        nalgebra_glm::ortho(left, right, bottom, top, self.near, self.far)
-        
-        // Comentamos la proyección perspectiva por ahora:
-        // perspective(self.fov, self.aspect_ratio, self.near, self.far)
+        perspective(self.fov, self.aspect_ratio, self.near, self.far)
     }
 
     pub fn get_view_projection_matrix(&self) -> Mat4 {
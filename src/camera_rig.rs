@@ -0,0 +1,278 @@
+use nalgebra_glm::{Vec3, Mat4, look_at, perspective, ortho};
+use crate::camera::Camera;
+use crate::{BodyState, SpaceshipCamera};
+
+// Duración de la transición entre modos (segundos)
+const TRANSITION_TIME: f32 = 0.8;
+// Altura de la cámara del mapa y mitad del alto visible en proyección ortográfica
+const MAP_HEIGHT: f32 = 4200.0;
+const MAP_HALF_HEIGHT: f32 = 2400.0;
+// Planos de recorte de las cámaras en perspectiva
+const NEAR: f32 = 1.0;
+const FAR: f32 = 10000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraMode {
+    Chase,
+    Cockpit,
+    Orbit,
+    TopDown,
+    Cinematic,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Chase => CameraMode::Cockpit,
+            CameraMode::Cockpit => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::TopDown,
+            CameraMode::TopDown => CameraMode::Cinematic,
+            CameraMode::Cinematic => CameraMode::Chase,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CameraMode::Chase => "Persecución",
+            CameraMode::Cockpit => "Cabina",
+            CameraMode::Orbit => "Observador",
+            CameraMode::TopDown => "Mapa del sistema",
+            CameraMode::Cinematic => "Cinemática",
+        }
+    }
+}
+
+// Lo que ve la cámara en un frame: posición, objetivo y proyección
+#[derive(Debug, Clone, Copy)]
+pub struct CameraView {
+    pub eye: Vec3,
+    pub target: Vec3,
+    pub up: Vec3,
+    pub fov: f32,
+    pub near: f32,
+    pub far: f32,
+    // Some(mitad del alto visible) para proyección ortográfica
+    pub ortho_half_height: Option<f32>,
+}
+
+impl CameraView {
+    pub fn view_matrix(&self) -> Mat4 {
+        look_at(&self.eye, &self.target, &self.up)
+    }
+
+    pub fn projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        match self.ortho_half_height {
+            Some(half_height) => {
+                let half_width = half_height * aspect_ratio;
+                ortho(-half_width, half_width, -half_height, half_height, self.near, self.far)
+            }
+            None => perspective(self.fov, aspect_ratio, self.near, self.far),
+        }
+    }
+
+    pub fn direction(&self) -> Vec3 {
+        (self.target - self.eye).normalize()
+    }
+
+    fn lerp(a: &CameraView, b: &CameraView, t: f32) -> CameraView {
+        CameraView {
+            eye: a.eye + (b.eye - a.eye) * t,
+            target: a.target + (b.target - a.target) * t,
+            up: (a.up + (b.up - a.up) * t).normalize(),
+            fov: a.fov + (b.fov - a.fov) * t,
+            near: a.near.min(b.near),
+            far: a.far.max(b.far),
+            // La proyección ortográfica solo se activa al terminar la transición
+            ortho_half_height: if t >= 1.0 { b.ortho_half_height } else { None },
+        }
+    }
+}
+
+pub struct CameraRig {
+    pub mode: CameraMode,
+    pub orbit: Camera,
+    // Índice en la lista de cuerpos sobre el que se fija el observador
+    pub locked_body: usize,
+    aspect_ratio: f32,
+    from: CameraView,
+    current: CameraView,
+    transition: f32,
+    cinematic_time: f32,
+}
+
+impl CameraRig {
+    pub fn new(width: f32, height: f32, ship: &SpaceshipCamera) -> Self {
+        let mut orbit = Camera::new(width, height);
+        orbit.update_aspect_ratio(width, height);
+        let view = chase_view(ship);
+
+        CameraRig {
+            mode: CameraMode::Chase,
+            orbit,
+            locked_body: 0,
+            aspect_ratio: width / height,
+            from: view,
+            current: view,
+            transition: 1.0,
+            cinematic_time: 0.0,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.from = self.current;
+            self.transition = 0.0;
+        }
+    }
+
+    pub fn cycle_mode(&mut self) {
+        self.set_mode(self.mode.next());
+    }
+
+    // Fija el observador sobre otro cuerpo, ajustando la distancia a su tamaño
+    pub fn lock_on(&mut self, index: usize, bodies: &[BodyState]) {
+        if let Some(body) = bodies.get(index) {
+            self.locked_body = index;
+            self.orbit.distance_from_target = body.radius * 4.0;
+            self.orbit.zoom(0.0);
+            self.orbit.set_target(body.position);
+        }
+    }
+
+    // Si la nave se dibuja desde este modo (en cabina taparía la vista)
+    pub fn shows_ship(&self) -> bool {
+        self.mode != CameraMode::Cockpit || self.transition < 1.0
+    }
+
+    pub fn update(&mut self, dt: f32, ship: &SpaceshipCamera, bodies: &[BodyState]) {
+        if let Some(body) = bodies.get(self.locked_body) {
+            self.orbit.set_target(body.position);
+        }
+
+        let desired = match self.mode {
+            CameraMode::Chase => chase_view(ship),
+            CameraMode::Cockpit => cockpit_view(ship),
+            CameraMode::Orbit => CameraView {
+                eye: self.orbit.position,
+                target: self.orbit.target,
+                up: self.orbit.up,
+                fov: self.orbit.fov,
+                near: self.orbit.near,
+                far: self.orbit.far,
+                ortho_half_height: None,
+            },
+            CameraMode::TopDown => CameraView {
+                eye: Vec3::new(0.0, MAP_HEIGHT, 0.0),
+                target: Vec3::new(0.0, 0.0, 0.0),
+                up: Vec3::new(0.0, 0.0, -1.0),
+                fov: ship.base_fov,
+                near: NEAR,
+                far: FAR,
+                ortho_half_height: Some(MAP_HALF_HEIGHT),
+            },
+            CameraMode::Cinematic => {
+                self.cinematic_time += dt;
+                cinematic_view(ship, bodies, self.cinematic_time)
+            }
+        };
+
+        if self.transition < 1.0 {
+            self.transition = (self.transition + dt / TRANSITION_TIME).min(1.0);
+            let t = self.transition;
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.current = CameraView::lerp(&self.from, &desired, if t >= 1.0 { 1.0 } else { eased });
+        } else if self.mode == CameraMode::Cinematic {
+            // La cinemática cambia de planeta cercano: suavizar siempre
+            let mut smoothed = CameraView::lerp(&self.current, &desired, (dt * 2.0).min(1.0));
+            smoothed.ortho_half_height = None;
+            self.current = smoothed;
+        } else {
+            self.current = desired;
+        }
+    }
+
+    pub fn view(&self) -> &CameraView {
+        &self.current
+    }
+
+    pub fn view_projection_matrix(&self) -> Mat4 {
+        if self.mode == CameraMode::Orbit && self.transition >= 1.0 {
+            return self.orbit.get_view_projection_matrix();
+        }
+        self.current.projection_matrix(self.aspect_ratio) * self.current.view_matrix()
+    }
+}
+
+// Vista de tercera persona: cámara con resorte detrás de la nave
+fn chase_view(ship: &SpaceshipCamera) -> CameraView {
+    CameraView {
+        eye: ship.get_camera_position(),
+        target: ship.get_camera_target(),
+        up: ship.get_camera_up(),
+        fov: ship.fov,
+        near: NEAR,
+        far: FAR,
+        ortho_half_height: None,
+    }
+}
+
+// Vista de cabina: en la nariz de la nave, siguiendo su roll
+fn cockpit_view(ship: &SpaceshipCamera) -> CameraView {
+    let forward = ship.get_forward_vector();
+    let right = ship.get_right_vector();
+    let up = forward.cross(&right).normalize();
+    let (sin_roll, cos_roll) = ship.rotation.z.sin_cos();
+    let rolled_up = up * cos_roll + right * sin_roll;
+    let eye = ship.position + forward * 12.0;
+
+    CameraView {
+        eye,
+        target: eye + forward,
+        up: rolled_up,
+        fov: ship.fov,
+        near: NEAR,
+        far: FAR,
+        ortho_half_height: None,
+    }
+}
+
+// Vista cinemática: encuadra la nave y el planeta más cercano girando lentamente
+fn cinematic_view(ship: &SpaceshipCamera, bodies: &[BodyState], time: f32) -> CameraView {
+    let nearest = bodies.iter().min_by(|a, b| {
+        let da = (a.position - ship.position).magnitude() - a.radius;
+        let db = (b.position - ship.position).magnitude() - b.radius;
+        da.total_cmp(&db)
+    });
+
+    let Some(body) = nearest else {
+        return chase_view(ship);
+    };
+
+    let world_up = Vec3::new(0.0, 1.0, 0.0);
+    let span_vector = body.position - ship.position;
+    let midpoint = ship.position + span_vector * 0.5;
+    let span = span_vector.magnitude() + body.radius * 2.0;
+
+    // Dirección lateral a la línea nave-planeta, rotando con el tiempo
+    let mut side = span_vector.cross(&world_up);
+    if side.magnitude() < 0.001 {
+        side = Vec3::new(1.0, 0.0, 0.0);
+    }
+    let side = side.normalize();
+    let along = span_vector.normalize();
+    let angle = (time * 0.15).sin() * 0.8;
+    let offset_dir = (side * angle.cos() + along * angle.sin() * 0.5 + world_up * 0.35).normalize();
+
+    let distance = span * 0.5 / (ship.fov * 0.5).tan() * 1.3;
+
+    CameraView {
+        eye: midpoint + offset_dir * distance,
+        target: midpoint,
+        up: world_up,
+        fov: ship.fov,
+        near: NEAR,
+        far: FAR,
+        ortho_half_height: None,
+    }
+}
@@ -0,0 +1,705 @@
+// Catálogo de cuerpos del sistema: datos físicos reales y la forma en que se
+// traducen a la escena. Los tamaños y velocidades se comprimen para que el
+// sistema quepa en pantalla, pero conservan el orden relativo entre cuerpos.
+// El catálogo activo es el Sistema Solar salvo que se instale uno generado.
+
+use std::borrow::Cow;
+use std::f32::consts::PI;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+use crate::planet_shaders::SurfaceStyle;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyKind {
+    Star,
+    Planet,
+    DwarfPlanet,
+    Comet,
+    Moon,
+}
+
+#[derive(Clone)]
+pub struct BodyInfo {
+    // Los textos son prestados en el catálogo de fábrica y propios en los
+    // sistemas generados o leídos de los archivos de escena
+    pub name: Cow<'static, str>,
+    pub kind: BodyKind,
+    // Planeta alrededor del que orbita (solo lunas)
+    pub parent: Option<Cow<'static, str>>,
+    pub radius_km: f32,
+    pub mass_kg: f64,
+    pub orbital_period_days: f32, // Alrededor del Sol, o del planeta para las lunas
+    pub day_length_hours: f32,    // Día sideral; negativo si la rotación es retrógrada
+    pub axial_tilt_deg: f32,      // Oblicuidad: ángulo entre el eje y la normal de la órbita
+    // Inclinación de la órbita respecto a la eclíptica (en las lunas, respecto
+    // al ecuador del planeta). Con más de 90° la órbita es retrógrada.
+    pub orbit_inclination_deg: f32,
+    pub tidally_locked: bool,     // Siempre muestra la misma cara a su planeta
+    pub moon_count: u32,
+    pub description: Cow<'static, str>,
+
+    // === ESCENA ===
+    pub shader_id: u32,
+    // Radio de la órbita en la escena (en las lunas, en radios del planeta)
+    pub orbit_radius: f32,
+    // Semiejes del cuerpo relativos al mayor; las lunas pequeñas no son esféricas
+    pub shape: [f32; 3],
+    // Parámetros del shader de su familia (cuerpos generados); si no hay, se
+    // usa el shader propio de `shader_id`
+    pub surface: Option<SurfaceStyle>,
+}
+
+// Referencias para escalar: la Tierra mide 85 unidades y recorre un radián
+// de su órbita por unidad de tiempo (una vuelta cada 2π unidades)
+const EARTH_RADIUS_KM: f32 = 6371.0;
+const EARTH_SCALE: f32 = 85.0;
+const EARTH_YEAR_DAYS: f32 = 365.26;
+const EARTH_DAY_HOURS: f32 = 23.93;
+const EARTH_ROTATION_SPEED: f32 = 0.05;
+// Las estrellas no siguen la escala de los planetas: a tamaño proporcional el
+// Sol taparía a Mercurio. Mide 200 unidades y las demás escalan con la raíz
+// cuarta del radio
+const SUN_RADIUS_KM: f32 = 696_340.0;
+const SUN_SCALE: f32 = 200.0;
+
+// La luna más rápida de cada planeta da una vuelta en unas 1.5 unidades de tiempo
+const MOON_MAX_ORBIT_SPEED: f32 = 4.0;
+// Tamaño mínimo en la escena para que Fobos y Deimos sigan siendo visibles
+const MIN_MOON_SCALE: f32 = 2.0;
+
+const SPHERE: [f32; 3] = [1.0, 1.0, 1.0];
+
+pub const CATALOG: [BodyInfo; 22] = [
+    BodyInfo {
+        name: Cow::Borrowed("Sol"),
+        kind: BodyKind::Star,
+        parent: None,
+        radius_km: 696_340.0,
+        mass_kg: 1.989e30,
+        orbital_period_days: 0.0,
+        day_length_hours: 609.1,
+        axial_tilt_deg: 7.25,
+        orbit_inclination_deg: 0.0,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("Estrella de tipo G. Contiene el 99.8% de la masa del sistema."),
+        shader_id: 1,
+        orbit_radius: 0.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Mercurio"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 2439.7,
+        mass_kg: 3.301e23,
+        orbital_period_days: 87.97,
+        day_length_hours: 1407.6,
+        axial_tilt_deg: 0.03,
+        orbit_inclination_deg: 7.0,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("El planeta más cercano al Sol. Superficie llena de cráteres y sin atmósfera."),
+        shader_id: 4,
+        orbit_radius: 300.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Venus"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 6051.8,
+        mass_kg: 4.867e24,
+        orbital_period_days: 224.70,
+        day_length_hours: -5832.5,
+        axial_tilt_deg: 177.4,
+        orbit_inclination_deg: 3.39,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("Atmósfera densa de CO2 y nubes de ácido sulfúrico. Gira al revés."),
+        shader_id: 7,
+        orbit_radius: 500.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Tierra"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 6371.0,
+        mass_kg: 5.972e24,
+        orbital_period_days: 365.26,
+        day_length_hours: 23.93,
+        axial_tilt_deg: 23.44,
+        orbit_inclination_deg: 0.0,
+        tidally_locked: false,
+        moon_count: 1,
+        description: Cow::Borrowed("Nuestro planeta. Océanos de agua líquida y una atmósfera rica en oxígeno."),
+        shader_id: 0,
+        orbit_radius: 700.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Marte"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 3389.5,
+        mass_kg: 6.417e23,
+        orbital_period_days: 686.98,
+        day_length_hours: 24.62,
+        axial_tilt_deg: 25.19,
+        orbit_inclination_deg: 1.85,
+        tidally_locked: false,
+        moon_count: 2,
+        description: Cow::Borrowed("El planeta rojo. Desiertos de óxido de hierro y casquetes polares."),
+        shader_id: 3,
+        orbit_radius: 900.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Júpiter"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 69_911.0,
+        mass_kg: 1.898e27,
+        orbital_period_days: 4332.59,
+        day_length_hours: 9.93,
+        axial_tilt_deg: 3.13,
+        orbit_inclination_deg: 1.3,
+        tidally_locked: false,
+        moon_count: 95,
+        description: Cow::Borrowed("Gigante gaseoso con bandas de nubes y la Gran Mancha Roja."),
+        shader_id: 2,
+        orbit_radius: 1200.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Saturno"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 58_232.0,
+        mass_kg: 5.683e26,
+        orbital_period_days: 10_759.22,
+        day_length_hours: 10.66,
+        axial_tilt_deg: 26.73,
+        orbit_inclination_deg: 2.49,
+        tidally_locked: false,
+        moon_count: 146,
+        description: Cow::Borrowed("Gigante gaseoso de tonos dorados, famoso por su sistema de anillos."),
+        shader_id: 8,
+        orbit_radius: 1550.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Urano"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 25_362.0,
+        mass_kg: 8.681e25,
+        orbital_period_days: 30_688.5,
+        day_length_hours: -17.24,
+        axial_tilt_deg: 97.77,
+        orbit_inclination_deg: 0.77,
+        tidally_locked: false,
+        moon_count: 28,
+        description: Cow::Borrowed("Gigante de hielo verde azulado. Gira prácticamente acostado."),
+        shader_id: 9,
+        orbit_radius: 1850.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Neptuno"),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km: 24_622.0,
+        mass_kg: 1.024e26,
+        orbital_period_days: 60_195.0,
+        day_length_hours: 16.11,
+        axial_tilt_deg: 28.32,
+        orbit_inclination_deg: 1.77,
+        tidally_locked: false,
+        moon_count: 16,
+        description: Cow::Borrowed("Gigante de hielo azul intenso con los vientos más rápidos del sistema."),
+        shader_id: 5,
+        orbit_radius: 2150.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Plutón"),
+        kind: BodyKind::DwarfPlanet,
+        parent: None,
+        radius_km: 1188.3,
+        mass_kg: 1.303e22,
+        orbital_period_days: 90_560.0,
+        day_length_hours: -153.29,
+        axial_tilt_deg: 122.53,
+        orbit_inclination_deg: 17.16,
+        tidally_locked: false,
+        moon_count: 5,
+        description: Cow::Borrowed("Planeta enano del cinturón de Kuiper. Llanuras de nitrógeno helado con forma de corazón."),
+        shader_id: 19,
+        orbit_radius: 2330.0, // Semieje mayor; la órbita real sale de belts::pluto_orbit
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Halley"),
+        kind: BodyKind::Comet,
+        parent: None,
+        radius_km: 5.5,
+        mass_kg: 2.2e14,
+        orbital_period_days: 27_509.0,
+        day_length_hours: 52.8,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 162.26,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("El cometa periódico más famoso: vuelve cada 75 años en una órbita retrógrada."),
+        shader_id: 22,
+        orbit_radius: 1820.0, // Semieje mayor; la órbita real sale de comets::comet_orbit
+        shape: [1.0, 0.53, 0.53],
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Encke"),
+        kind: BodyKind::Comet,
+        parent: None,
+        radius_km: 2.4,
+        mass_kg: 9.2e13,
+        orbital_period_days: 1204.0,
+        day_length_hours: 11.0,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 11.78,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("El cometa de periodo más corto conocido: pasa por el perihelio cada 3.3 años."),
+        shader_id: 22,
+        orbit_radius: 990.0,
+        shape: [1.0, 0.85, 0.8],
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Churyumov-Gerasimenko"),
+        kind: BodyKind::Comet,
+        parent: None,
+        radius_km: 2.0,
+        mass_kg: 1.0e13,
+        orbital_period_days: 2352.0,
+        day_length_hours: 12.4,
+        axial_tilt_deg: 52.0,
+        orbit_inclination_deg: 7.04,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed("Núcleo de dos lóbulos visitado por la sonda Rosetta y su módulo Philae."),
+        shader_id: 22,
+        orbit_radius: 1100.0,
+        shape: [1.0, 0.7, 0.6],
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Luna"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Tierra")),
+        radius_km: 1737.4,
+        mass_kg: 7.342e22,
+        orbital_period_days: 27.32,
+        day_length_hours: 655.7,
+        axial_tilt_deg: 6.68,
+        orbit_inclination_deg: 5.14,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Único satélite natural de la Tierra. Siempre muestra la misma cara."),
+        shader_id: 10,
+        orbit_radius: 2.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Fobos"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Marte")),
+        radius_km: 11.27,
+        mass_kg: 1.066e16,
+        orbital_period_days: 0.319,
+        day_length_hours: 7.66,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 1.09,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Luna irregular surcada de estrías. Cae lentamente en espiral hacia Marte."),
+        shader_id: 17,
+        orbit_radius: 1.4,
+        shape: [1.0, 0.67, 0.81],
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Deimos"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Marte")),
+        radius_km: 6.2,
+        mass_kg: 1.476e15,
+        orbital_period_days: 1.263,
+        day_length_hours: 30.3,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 0.93,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("La menor de las lunas de Marte. Superficie lisa cubierta de regolito."),
+        shader_id: 18,
+        orbit_radius: 2.2,
+        shape: [1.0, 0.73, 0.80],
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Ío"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Júpiter")),
+        radius_km: 1821.6,
+        mass_kg: 8.932e22,
+        orbital_period_days: 1.769,
+        day_length_hours: 42.46,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 0.05,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("El cuerpo con más actividad volcánica del sistema, cubierto de azufre."),
+        shader_id: 11,
+        orbit_radius: 1.35,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Europa"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Júpiter")),
+        radius_km: 1560.8,
+        mass_kg: 4.800e22,
+        orbital_period_days: 3.551,
+        day_length_hours: 85.23,
+        axial_tilt_deg: 0.1,
+        orbit_inclination_deg: 0.47,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Corteza de hielo agrietada sobre un océano de agua líquida."),
+        shader_id: 12,
+        orbit_radius: 1.55,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Ganímedes"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Júpiter")),
+        radius_km: 2634.1,
+        mass_kg: 1.482e23,
+        orbital_period_days: 7.155,
+        day_length_hours: 171.7,
+        axial_tilt_deg: 0.33,
+        orbit_inclination_deg: 0.20,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("La luna más grande del sistema. Terreno oscuro antiguo y surcos claros."),
+        shader_id: 13,
+        orbit_radius: 1.8,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Calisto"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Júpiter")),
+        radius_km: 2410.3,
+        mass_kg: 1.076e23,
+        orbital_period_days: 16.689,
+        day_length_hours: 400.5,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 0.19,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Superficie oscura y antigua, la más craterizada del sistema."),
+        shader_id: 14,
+        orbit_radius: 2.1,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Titán"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Saturno")),
+        radius_km: 2574.7,
+        mass_kg: 1.345e23,
+        orbital_period_days: 15.945,
+        day_length_hours: 382.7,
+        axial_tilt_deg: 0.3,
+        orbit_inclination_deg: 0.35,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Atmósfera densa de nitrógeno con neblina naranja y lagos de metano."),
+        shader_id: 15,
+        orbit_radius: 2.0,
+        shape: SPHERE,
+        surface: None,
+    },
+    BodyInfo {
+        name: Cow::Borrowed("Tritón"),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Borrowed("Neptuno")),
+        radius_km: 1353.4,
+        mass_kg: 2.139e22,
+        orbital_period_days: 5.877,
+        day_length_hours: -141.0,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: 156.9,
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed("Órbita retrógrada: probablemente un objeto capturado. Géiseres de nitrógeno."),
+        shader_id: 16,
+        orbit_radius: 1.8,
+        shape: SPHERE,
+        surface: None,
+    },
+];
+
+// Sistema que se está simulando; por defecto, el Sistema Solar. Cada cuerpo
+// va en su propio Arc, así la escena lo guarda sin copiarlo y un sistema
+// sustituido se libera cuando nadie lo usa.
+static ACTIVE: LazyLock<RwLock<Arc<[Arc<BodyInfo>]>>> =
+    LazyLock::new(|| RwLock::new(CATALOG.iter().cloned().map(Arc::new).collect()));
+
+pub fn bodies() -> Arc<[Arc<BodyInfo>]> {
+    ACTIVE.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+// Sustituye el sistema activo
+pub fn install(bodies: Vec<BodyInfo>) {
+    *ACTIVE.write().unwrap_or_else(PoisonError::into_inner) = bodies.into_iter().map(Arc::new).collect();
+}
+
+// Cuerpos del sistema activo en orden
+fn active() -> impl Iterator<Item = Arc<BodyInfo>> {
+    let bodies = bodies();
+    (0..bodies.len()).map(move |i| Arc::clone(&bodies[i]))
+}
+
+pub fn find(name: &str) -> Option<Arc<BodyInfo>> {
+    active().find(|info| info.name == name)
+}
+
+pub fn star() -> Arc<BodyInfo> {
+    active().find(|info| info.kind == BodyKind::Star).expect("el sistema debe tener una estrella")
+}
+
+pub fn planets() -> impl Iterator<Item = Arc<BodyInfo>> {
+    active().filter(|info| info.kind == BodyKind::Planet)
+}
+
+pub fn comets() -> impl Iterator<Item = Arc<BodyInfo>> {
+    active().filter(|info| info.kind == BodyKind::Comet)
+}
+
+// Órbitas más juntas que esto (en ln UA) cuentan como la misma
+const MIN_SEGMENT_WIDTH: f32 = 1e-4;
+
+// Radio en la escena para una distancia real al Sol en unidades astronómicas.
+// Interpola en escala logarítmica entre las órbitas de los planetas y
+// prolonga el último tramo hacia fuera. Dentro de la órbita de Mercurio es
+// proporcional a la distancia, para llegar al centro sin pasar de largo.
+// Se guarda en las órbitas al crearlas: recorrer el catálogo en cada punto
+// es demasiado caro para miles de rocas.
+#[derive(Clone)]
+pub struct SceneScale {
+    inner_au: f32,
+    // (ln UA, radio en la escena) de cada planeta, de dentro hacia fuera
+    points: Vec<(f32, f32)>,
+}
+
+impl SceneScale {
+    // Con un solo planeta la escala es proporcional a su órbita; sin
+    // ninguno, la del Sistema Solar. Los planetas se ordenan por distancia y
+    // de los que comparten periodo queda el primero.
+    pub fn new<'a, I>(bodies: I) -> Self
+    where
+        I: IntoIterator<Item = &'a BodyInfo>,
+    {
+        let mut points: Vec<(f32, f32)> = bodies
+            .into_iter()
+            .filter(|info| info.kind == BodyKind::Planet && info.orbital_period_days > 0.0)
+            .map(|info| (info.semi_major_axis_au().ln(), info.orbit_radius))
+            .filter(|(x, _)| x.is_finite())
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|outer, inner| outer.0 - inner.0 < MIN_SEGMENT_WIDTH);
+        let Some(&(inner_x, _)) = points.first() else {
+            return SceneScale::new(&CATALOG);
+        };
+        SceneScale { inner_au: inner_x.exp(), points }
+    }
+
+    pub fn radius_for_au(&self, au: f32) -> f32 {
+        let (_, inner_radius) = self.points[0];
+        if self.points.len() < 2 || au < self.inner_au {
+            return inner_radius * au.max(0.0) / self.inner_au;
+        }
+        let x = au.ln();
+        let mut segment = (self.points[0], self.points[1]);
+        for pair in self.points.windows(2) {
+            segment = (pair[0], pair[1]);
+            if x <= segment.1.0 {
+                break;
+            }
+        }
+        let ((x0, r0), (x1, r1)) = segment;
+        if x1 - x0 < MIN_SEGMENT_WIDTH {
+            return r0;
+        }
+        r0 + (r1 - r0) * (x - x0) / (x1 - x0)
+    }
+}
+
+// Escala del sistema activo
+pub fn scene_scale() -> SceneScale {
+    SceneScale::new(bodies().iter().map(Arc::as_ref))
+}
+
+// Igual, con las órbitas de otro conjunto de cuerpos
+pub fn scene_radius_in<'a, I>(bodies: I, au: f32) -> f32
+where
+    I: IntoIterator<Item = &'a BodyInfo>,
+{
+    SceneScale::new(bodies).radius_for_au(au)
+}
+
+// Velocidad orbital para un semieje mayor real, con la misma ley que los
+// planetas: el periodo en años es a^1.5, y la escena usa su raíz cuadrada
+pub fn orbit_speed_for_au(au: f32) -> f32 {
+    au.powf(-0.75)
+}
+
+pub fn moons_of(planet: &str) -> impl Iterator<Item = Arc<BodyInfo>> + '_ {
+    active().filter(move |info| info.parent.as_deref() == Some(planet))
+}
+
+impl BodyInfo {
+    // Tamaño en la escena de un planeta: raíz cuarta del radio real, así
+    // Júpiter sigue siendo el mayor sin tapar las órbitas vecinas
+    pub fn planet_scale(&self) -> f32 {
+        EARTH_SCALE * (self.radius_km / EARTH_RADIUS_KM).powf(0.25)
+    }
+
+    pub fn star_scale(&self) -> f32 {
+        SUN_SCALE * (self.radius_km / SUN_RADIUS_KM).powf(0.25)
+    }
+
+    // Las lunas guardan la proporción real con su planeta
+    pub fn moon_scale(&self, parent: &BodyInfo, parent_scale: f32) -> f32 {
+        (parent_scale * self.radius_km / parent.radius_km).max(MIN_MOON_SCALE)
+    }
+
+    // Radianes de órbita por unidad de tiempo, negativo si la órbita es
+    // retrógrada. Los planetas usan la raíz cuadrada de la proporción real;
+    // las lunas de un mismo planeta conservan la proporción exacta entre sus
+    // periodos (se ven las resonancias de Ío, Europa y Ganímedes).
+    pub fn orbit_speed(&self) -> f32 {
+        if self.orbital_period_days <= 0.0 {
+            return 0.0;
+        }
+        let speed = match &self.parent {
+            Some(parent) => {
+                let fastest = moons_of(parent)
+                    .map(|moon| moon.orbital_period_days)
+                    .fold(f32::INFINITY, f32::min);
+                MOON_MAX_ORBIT_SPEED * fastest / self.orbital_period_days
+            }
+            None => (EARTH_YEAR_DAYS / self.orbital_period_days).sqrt(),
+        };
+        if self.is_retrograde_orbit() { -speed } else { speed }
+    }
+
+    // Semieje mayor real a partir del periodo (tercera ley de Kepler); solo
+    // tiene sentido para los cuerpos que orbitan el Sol
+    pub fn semi_major_axis_au(&self) -> f32 {
+        (self.orbital_period_days / EARTH_YEAR_DAYS).powf(2.0 / 3.0)
+    }
+
+    pub fn is_retrograde_orbit(&self) -> bool {
+        self.orbit_inclination_deg > 90.0
+    }
+
+    // Radianes de giro sobre su propio eje por unidad de tiempo. Positivo es el
+    // mismo sentido que las órbitas. Con una inclinación mayor de 90° el eje ya
+    // apunta hacia abajo, así que un día retrógrado (Venus, Urano) se obtiene
+    // girando en sentido positivo sobre ese eje invertido.
+    pub fn rotation_speed(&self) -> f32 {
+        let speed = EARTH_ROTATION_SPEED * (EARTH_DAY_HOURS / self.day_length_hours.abs()).sqrt();
+        let retrograde = self.day_length_hours < 0.0;
+        let upside_down = self.axial_tilt_deg > 90.0;
+        if retrograde != upside_down { -speed } else { speed }
+    }
+
+    pub fn axial_tilt(&self) -> f32 {
+        self.axial_tilt_deg.to_radians()
+    }
+
+    // Ángulo de giro sobre el eje. `orbit_angle` es el ángulo del cuerpo en su
+    // órbita: un cuerpo con acoplamiento de marea gira lo mismo que avanza en
+    // ella, con la cara +X del modelo mirando a su planeta.
+    pub fn spin_angle(&self, elapsed: f32, orbit_angle: f32) -> f32 {
+        if self.tidally_locked {
+            orbit_angle + PI
+        } else {
+            elapsed * self.rotation_speed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(name: &'static str, orbital_period_days: f32, orbit_radius: f32) -> BodyInfo {
+        BodyInfo {
+            name: Cow::Borrowed(name),
+            orbital_period_days,
+            orbit_radius,
+            ..CATALOG[3].clone()
+        }
+    }
+
+    #[test]
+    fn scene_scale_matches_planet_orbits() {
+        let scale = SceneScale::new(&CATALOG);
+        for info in CATALOG.iter().filter(|info| info.kind == BodyKind::Planet) {
+            let radius = scale.radius_for_au(info.semi_major_axis_au());
+            assert!((radius - info.orbit_radius).abs() < 0.5, "{}: {} en vez de {}", info.name, radius, info.orbit_radius);
+        }
+        assert_eq!(scale.radius_for_au(0.0), 0.0);
+    }
+
+    #[test]
+    fn scene_scale_sorts_planets() {
+        let sorted = [planet("A", 100.0, 300.0), planet("B", 400.0, 600.0), planet("C", 2000.0, 1000.0)];
+        let shuffled = [sorted[2].clone(), sorted[0].clone(), sorted[1].clone()];
+        let (sorted, shuffled) = (SceneScale::new(&sorted), SceneScale::new(&shuffled));
+        for au in [0.1, 0.5, 1.0, 2.0, 5.0, 40.0] {
+            assert_eq!(sorted.radius_for_au(au), shuffled.radius_for_au(au));
+        }
+    }
+
+    #[test]
+    fn scene_scale_ignores_repeated_periods() {
+        let bodies = [planet("A", EARTH_YEAR_DAYS, 500.0), planet("B", EARTH_YEAR_DAYS, 700.0), planet("C", 0.0, 50.0)];
+        let scale = SceneScale::new(&bodies);
+        for au in [0.5, 1.0, 3.0] {
+            let radius = scale.radius_for_au(au);
+            assert!(radius.is_finite());
+            assert!((radius - 500.0 * au).abs() < 1e-3 * radius, "{} UA: {}", au, radius);
+        }
+    }
+}
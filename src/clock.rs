@@ -0,0 +1,160 @@
+// Escalas de tiempo disponibles (veces el tiempo real)
+pub const RATE_PRESETS: [f32; 4] = [1.0, 10.0, 100.0, 1000.0];
+
+// Reloj de la simulación: acumula el delta de cada tick escalado por la
+// velocidad actual, así cambiar la escala no teletransporta los planetas.
+pub struct SimClock {
+    time: f64, // f64 para no perder precisión a 1000x
+    preset: usize,
+    reversed: bool,
+    paused: bool,
+}
+
+impl SimClock {
+    pub fn new() -> Self {
+        SimClock {
+            time: 0.0,
+            preset: 0,
+            reversed: false,
+            paused: false,
+        }
+    }
+
+    // Restaurar un estado guardado (repeticiones)
+    pub fn from_parts(time: f64, preset: usize, reversed: bool, paused: bool) -> Self {
+        SimClock {
+            time,
+            preset: preset.min(RATE_PRESETS.len() - 1),
+            reversed,
+            paused,
+        }
+    }
+
+    pub fn advance(&mut self, dt: f32) {
+        self.time += (dt * self.rate()) as f64;
+    }
+
+    // Tiempo de simulación usado por órbitas y shaders
+    pub fn time(&self) -> f32 {
+        self.time as f32
+    }
+
+    pub fn time_f64(&self) -> f64 {
+        self.time
+    }
+
+    // Velocidad efectiva: 0 en pausa, negativa al rebobinar
+    pub fn rate(&self) -> f32 {
+        if self.paused {
+            0.0
+        } else if self.reversed {
+            -RATE_PRESETS[self.preset]
+        } else {
+            RATE_PRESETS[self.preset]
+        }
+    }
+
+    pub fn preset(&self) -> usize {
+        self.preset
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn toggle_reverse(&mut self) {
+        self.reversed = !self.reversed;
+    }
+
+    pub fn faster(&mut self) {
+        self.preset = (self.preset + 1).min(RATE_PRESETS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.preset = self.preset.saturating_sub(1);
+    }
+
+    // Descripción corta de la velocidad, p. ej. "-100x" o "10x (pausa)"
+    pub fn describe(&self) -> String {
+        let sign = if self.reversed { "-" } else { "" };
+        let rate = format!("{}{}x", sign, RATE_PRESETS[self.preset]);
+        if self.paused { format!("{} (pausa)", rate) } else { rate }
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_freezes_time_exactly() {
+        let mut clock = SimClock::new();
+        clock.advance(0.5);
+        let before = clock.time_f64();
+        clock.toggle_pause();
+        assert_eq!(clock.rate(), 0.0);
+        for _ in 0..1000 {
+            clock.advance(0.016);
+        }
+        assert_eq!(clock.time_f64(), before);
+        clock.toggle_pause();
+        clock.advance(0.5);
+        assert_eq!(clock.time_f64(), 1.0);
+    }
+
+    #[test]
+    fn reverse_runs_time_backwards_at_the_same_rate() {
+        let mut clock = SimClock::new();
+        clock.faster();
+        clock.advance(1.0);
+        assert_eq!(clock.time(), 10.0);
+        clock.toggle_reverse();
+        assert_eq!(clock.rate(), -10.0);
+        clock.advance(1.0);
+        assert_eq!(clock.time(), 0.0);
+        // Rebobinar puede llevar el tiempo a negativo
+        clock.advance(0.5);
+        assert_eq!(clock.time(), -5.0);
+        assert_eq!(clock.describe(), "-10x");
+    }
+
+    #[test]
+    fn pause_keeps_direction_and_preset() {
+        let mut clock = SimClock::new();
+        clock.faster();
+        clock.faster();
+        clock.toggle_reverse();
+        clock.toggle_pause();
+        assert_eq!(clock.describe(), "-100x (pausa)");
+        clock.toggle_pause();
+        assert_eq!(clock.rate(), -100.0);
+    }
+
+    #[test]
+    fn presets_clamp_at_both_ends() {
+        let mut clock = SimClock::new();
+        clock.slower();
+        assert_eq!(clock.preset(), 0);
+        for _ in 0..10 {
+            clock.faster();
+        }
+        assert_eq!(clock.preset(), RATE_PRESETS.len() - 1);
+        let restored = SimClock::from_parts(3.0, 99, true, true);
+        assert_eq!(restored.preset(), RATE_PRESETS.len() - 1);
+        assert!(restored.is_paused() && restored.is_reversed());
+    }
+}
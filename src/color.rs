@@ -8,7 +8,7 @@ pub struct Color {
 }
 
 impl Color {
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Color { r, g, b }
     }
 
@@ -16,6 +16,10 @@ impl Color {
         Color { r: 0, g: 0, b: 0 }
     }
 
+    pub fn from_hex(hex: u32) -> Self {
+        Color::new((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
     pub fn to_hex(&self) -> u32 {
         ((self.r as u32) << 16) | ((self.g as u32) << 8) | (self.b as u32)
     }
@@ -42,6 +46,15 @@ impl Color {
             (self.b() as f32 * factor).min(255.0) as u8,
         )
     }
+
+    /// 🔹 Suma de luz (brillos, luces emisivas), saturando en 255
+    pub fn add(&self, other: &Color) -> Color {
+        Color::new(
+            self.r.saturating_add(other.r),
+            self.g.saturating_add(other.g),
+            self.b.saturating_add(other.b),
+        )
+    }
 }
 
 impl fmt::Display for Color {
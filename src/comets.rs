@@ -0,0 +1,146 @@
+// Cometas en órbitas muy excéntricas. Cerca del Sol el hielo del núcleo se
+// sublima y aparecen una coma y dos colas: la de iones, recta y azulada,
+// apunta justo en dirección contraria al Sol; la de polvo, más ancha y
+// amarillenta, se curva quedándose atrás en la órbita.
+
+use std::sync::Arc;
+use nalgebra_glm::Vec3;
+use crate::catalog::{self, orbit_speed_for_au, BodyInfo};
+use crate::framebuffer::Framebuffer;
+use crate::kepler::KeplerOrbit;
+use crate::shaders::ScreenProjector;
+
+// El núcleo real mide pocos kilómetros; en la escena no se vería
+pub const NUCLEUS_SCALE: f32 = 4.0;
+
+// Más lejos del Sol que esto (UA) el cometa está inactivo
+const ACTIVITY_DISTANCE_AU: f32 = 5.0;
+
+// Tamaños con actividad máxima (unidades de la escena)
+const COMA_RADIUS: f32 = 24.0;
+const ION_TAIL_LENGTH: f32 = 450.0;
+const DUST_TAIL_LENGTH: f32 = 300.0;
+// Cuánto se curva la cola de polvo hacia atrás en la órbita
+const DUST_TAIL_CURVE: f32 = 0.6;
+const TAIL_SAMPLES: usize = 40;
+
+const COMA_COLOR: u32 = 0xCFE8FF;
+const ION_TAIL_COLOR: u32 = 0x5A9BFF;
+const DUST_TAIL_COLOR: u32 = 0xFFE2A8;
+
+// Elementos de la órbita que no están en el catálogo
+struct CometElements {
+    name: &'static str,
+    eccentricity: f32,
+    ascending_node_deg: f32,
+    periapsis_argument_deg: f32,
+    mean_anomaly_at_epoch: f32, // Elegida para que al arrancar haya alguno cerca del Sol
+}
+
+const COMET_ELEMENTS: [CometElements; 3] = [
+    CometElements {
+        name: "Halley",
+        eccentricity: 0.967,
+        ascending_node_deg: 58.42,
+        periapsis_argument_deg: 111.33,
+        mean_anomaly_at_epoch: -0.3,
+    },
+    CometElements {
+        name: "Encke",
+        eccentricity: 0.848,
+        ascending_node_deg: 334.57,
+        periapsis_argument_deg: 186.54,
+        mean_anomaly_at_epoch: 5.9,
+    },
+    CometElements {
+        name: "Churyumov-Gerasimenko",
+        eccentricity: 0.641,
+        ascending_node_deg: 50.2,
+        periapsis_argument_deg: 12.8,
+        mean_anomaly_at_epoch: 4.5,
+    },
+];
+
+// Órbita de un cometa del catálogo. La inclinación de más de 90° ya hace la
+// órbita retrógrada, así que el movimiento medio siempre es positivo.
+pub fn comet_orbit(info: &BodyInfo) -> Option<KeplerOrbit> {
+    let elements = COMET_ELEMENTS.iter().find(|elements| elements.name == info.name)?;
+    let semi_major_axis = info.semi_major_axis_au();
+    Some(KeplerOrbit {
+        semi_major_axis,
+        eccentricity: elements.eccentricity,
+        inclination: info.orbit_inclination_deg.to_radians(),
+        ascending_node: elements.ascending_node_deg.to_radians(),
+        periapsis_argument: elements.periapsis_argument_deg.to_radians(),
+        mean_anomaly_at_epoch: elements.mean_anomaly_at_epoch,
+        mean_motion: orbit_speed_for_au(semi_major_axis),
+        scene_scale: Arc::new(catalog::scene_scale()),
+    })
+}
+
+// Actividad entre 0 y 1. La luz que recibe el núcleo cae con el cuadrado de
+// la distancia; cerca del límite se apaga suavemente.
+pub fn activity(distance_au: f32) -> f32 {
+    if distance_au >= ACTIVITY_DISTANCE_AU {
+        return 0.0;
+    }
+    let sunlight = (1.0 / (distance_au * distance_au)).min(1.0);
+    let fade = 1.0 - distance_au / ACTIVITY_DISTANCE_AU;
+    sunlight * fade.sqrt()
+}
+
+// Coma y colas con brillo aditivo; se dibuja después de toda la geometría
+pub fn draw_comet(framebuffer: &mut Framebuffer, projector: &ScreenProjector, orbit: &KeplerOrbit, time: f32) {
+    let activity = activity(orbit.distance_au(time));
+    if activity <= 0.0 {
+        return;
+    }
+
+    let position = orbit.position(time);
+    let away_from_sun = position.normalize();
+    let heading = (orbit.position(time + 0.01) - orbit.position(time - 0.01)).normalize();
+    let size = activity.sqrt();
+
+    // === COLA DE POLVO ===
+    // Las partículas se quedan atrás en la órbita: la cola se curva
+    draw_tail(framebuffer, projector, TAIL_SAMPLES, |t| {
+        let offset = away_from_sun * t - heading * (t * t * DUST_TAIL_CURVE);
+        let point = position + offset * DUST_TAIL_LENGTH * size;
+        let width = COMA_RADIUS * size * (0.5 + t * 1.5);
+        let intensity = activity * 0.12 * (1.0 - t).powf(1.2);
+        (point, width, DUST_TAIL_COLOR, intensity)
+    });
+
+    // === COLA DE IONES ===
+    // El viento solar la empuja en línea recta lejos del Sol
+    draw_tail(framebuffer, projector, TAIL_SAMPLES, |t| {
+        let point = position + away_from_sun * (ION_TAIL_LENGTH * size * t);
+        let width = COMA_RADIUS * size * (0.25 + t * 0.4);
+        let intensity = activity * 0.15 * (1.0 - t).powf(1.5);
+        (point, width, ION_TAIL_COLOR, intensity)
+    });
+
+    // === COMA ===
+    if let Some(center) = projector.project(position) {
+        let radius = projector.radius_pixels(position, COMA_RADIUS * size);
+        framebuffer.add_sprite(center.x, center.y, center.z, radius, COMA_COLOR, 0.6 * activity);
+        framebuffer.add_sprite(center.x, center.y, center.z, radius * 0.25, 0xFFFFFF, 0.8 * activity);
+    }
+}
+
+// Recorre la cola de la cabeza (t = 0) a la punta (t = 1) dibujando sprites
+fn draw_tail(
+    framebuffer: &mut Framebuffer,
+    projector: &ScreenProjector,
+    samples: usize,
+    sample: impl Fn(f32) -> (Vec3, f32, u32, f32),
+) {
+    for i in 1..=samples {
+        let t = i as f32 / samples as f32;
+        let (point, width, color, intensity) = sample(t);
+        if let Some(screen) = projector.project(point) {
+            let radius = projector.radius_pixels(point, width);
+            framebuffer.add_sprite(screen.x, screen.y, screen.z, radius, color, intensity);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// Exportación de las superficies a PNG sin abrir la ventana: un mapa
+// equirectangular, las seis caras de un cubemap o una esfera iluminada.
+// Evalúa los mismos shaders que el simulador sobre la esfera unidad.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use nalgebra_glm::{Mat3, Vec3};
+use crate::catalog;
+use crate::color::Color;
+use crate::generator;
+use crate::bump::tangent_basis;
+use crate::planet_shaders::{family_style, fixed_shader, SurfaceStyle};
+use crate::png;
+use crate::scene_config;
+
+pub const USAGE: &str = "Lab05 export <map|cube|sphere> (--body <nombre> | --shader <id> | --preset <nombre>) \
+[--size <píxeles>] [--time <t>] [--light <x,y,z>] [--longitude <grados>] [--seed <número>] [--output <archivo.png>]\n\
+En map y cube, --light da la superficie iluminada desde esa dirección (con nubes y terminador) \
+en vez del albedo; --time sin --light solo vale para superficies animadas.";
+
+const DEFAULT_SIZE: usize = 1024;
+const MAX_SIZE: usize = 8192;
+// Luz por defecto de la esfera: desde arriba a la izquierda y por delante
+const DEFAULT_LIGHT: [f32; 3] = [-1.0, 0.5, 1.0];
+// La cámara está en +z mirando al origen
+const VIEW_DIR: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Map,
+    Cube,
+    Sphere,
+}
+
+enum Source {
+    Body(String),
+    Shader(u32),
+    Preset(String),
+}
+
+struct ExportOptions {
+    mode: Mode,
+    source: Source,
+    size: usize,
+    time: Option<f32>,
+    // Sin ella, los mapas y cubemaps son el albedo y la esfera usa DEFAULT_LIGHT
+    light: Option<Vec3>,
+    longitude_deg: f32,
+    seed: Option<u64>,
+    output: Option<String>,
+}
+
+// Shader a evaluar: el de un cuerpo del catálogo, un id o un preset
+struct Target {
+    name: String,
+    shader_id: u32,
+    surface: Option<SurfaceStyle>,
+}
+
+impl Target {
+    // `to_world` lleva las normales del modelo al espacio de la imagen
+    fn shade(&self, position: Vec3, normal: Vec3, to_world: &Mat3, light_dir: Vec3, time: f32) -> Color {
+        match &self.surface {
+            Some(style) => style.shade(position, normal, to_world, light_dir, VIEW_DIR, time),
+            None => fixed_shader(self.shader_id, position, normal, light_dir, VIEW_DIR, time),
+        }
+    }
+
+    fn has_static_layer(&self) -> bool {
+        self.surface.as_ref().is_some_and(|style| style.surface(Vec3::new(0.0, 1.0, 0.0)).is_some())
+    }
+}
+
+// `args` son los argumentos que siguen a "export"
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_args(args)?;
+    let target = resolve_target(&options)?;
+    let base = options.output.clone().unwrap_or_else(|| default_output(&target.name, options.mode));
+    let time = options.time.unwrap_or(0.0);
+    // El albedo de una capa estática no depende del tiempo
+    if options.mode != Mode::Sphere && options.time.is_some() && options.light.is_none() && target.has_static_layer() {
+        return Err(format!(
+            "{} tiene una capa estática y su albedo no cambia con --time; añade --light para exportarla iluminada",
+            target.name
+        ));
+    }
+
+    match options.mode {
+        Mode::Map => {
+            let (width, height) = (options.size, options.size / 2);
+            let pixels = render(width, height, |x, y| {
+                Some(face_on(&target, map_direction(x, y, width, height), options.light, time))
+            });
+            write(&base, width, height, &pixels)?;
+        }
+        Mode::Cube => {
+            let stem = base.strip_suffix(".png").unwrap_or(&base);
+            for (face, suffix) in CUBE_FACES.iter().enumerate() {
+                let pixels = render(options.size, options.size, |x, y| {
+                    Some(face_on(&target, cube_direction(face, x, y, options.size), options.light, time))
+                });
+                write(&format!("{}_{}.png", stem, suffix), options.size, options.size, &pixels)?;
+            }
+        }
+        Mode::Sphere => {
+            let size = options.size;
+            let light = options.light.unwrap_or_else(|| Vec3::new(DEFAULT_LIGHT[0], DEFAULT_LIGHT[1], DEFAULT_LIGHT[2]));
+            let (sin, cos) = options.longitude_deg.to_radians().sin_cos();
+            // Giro alrededor del eje para que `--longitude` quede de frente
+            let to_image = Mat3::new(
+                sin, 0.0, -cos,
+                0.0, 1.0, 0.0,
+                cos, 0.0, sin,
+            );
+            let pixels = render(size, size, |x, y| {
+                // Proyección ortográfica: la esfera llena la imagen
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = 1.0 - (y as f32 + 0.5) / size as f32 * 2.0;
+                let r2 = u * u + v * v;
+                if r2 > 1.0 {
+                    return None;
+                }
+                let normal = Vec3::new(u, v, (1.0 - r2).sqrt());
+                let position = to_image.transpose() * normal;
+                Some(target.shade(position, normal, &to_image, light, time))
+            });
+            write(&base, size, size, &pixels)?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<ExportOptions, String> {
+    let mut args = args.iter();
+    let mode = match args.next().map(String::as_str) {
+        Some("map") => Mode::Map,
+        Some("cube") => Mode::Cube,
+        Some("sphere") => Mode::Sphere,
+        Some(other) => return Err(format!("modo de exportación desconocido '{}' (map, cube o sphere)", other)),
+        None => return Err("falta el modo de exportación (map, cube o sphere)".to_string()),
+    };
+
+    let mut source = None;
+    let mut options = ExportOptions {
+        mode,
+        source: Source::Shader(0),
+        size: DEFAULT_SIZE,
+        time: None,
+        light: None,
+        longitude_deg: 0.0,
+        seed: None,
+        output: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} necesita un valor", arg));
+        match arg.as_str() {
+            "--body" => source = Some(Source::Body(value()?.clone())),
+            "--shader" => {
+                let id = value()?;
+                source = Some(Source::Shader(id.parse().map_err(|_| format!("id de shader no válido '{}'", id))?));
+            }
+            "--preset" => source = Some(Source::Preset(value()?.clone())),
+            "--size" => {
+                let size = value()?;
+                options.size = size
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|size| (2..=MAX_SIZE).contains(size))
+                    .ok_or_else(|| format!("tamaño no válido '{}' (de 2 a {})", size, MAX_SIZE))?;
+            }
+            "--time" => {
+                let time = value()?;
+                options.time = Some(time.parse().map_err(|_| format!("tiempo no válido '{}'", time))?);
+            }
+            "--light" => options.light = Some(parse_vector(value()?)?),
+            "--longitude" => {
+                let longitude = value()?;
+                options.longitude_deg = longitude.parse().map_err(|_| format!("longitud no válida '{}'", longitude))?;
+            }
+            "--seed" => {
+                let seed = value()?;
+                options.seed = Some(seed.parse().map_err(|_| format!("semilla no válida '{}'", seed))?);
+            }
+            "--output" => options.output = Some(value()?.clone()),
+            other => return Err(format!("argumento desconocido '{}'", other)),
+        }
+    }
+
+    options.source = source.ok_or("indica qué exportar con --body, --shader o --preset")?;
+    if options.mode == Mode::Map && !options.size.is_multiple_of(2) {
+        return Err(format!("el mapa necesita un ancho par (se pidió {})", options.size));
+    }
+    Ok(options)
+}
+
+// "x,y,z" a un vector unitario
+fn parse_vector(text: &str) -> Result<Vec3, String> {
+    let components: Vec<f32> = text
+        .split(',')
+        .map(|part| part.trim().parse::<f32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("vector no válido '{}' (usa x,y,z)", text))?;
+    let [x, y, z] = components[..] else {
+        return Err(format!("vector no válido '{}' (usa x,y,z)", text));
+    };
+    let vector = Vec3::new(x, y, z);
+    if vector.norm() < 1e-6 {
+        return Err("la dirección de la luz no puede ser nula".to_string());
+    }
+    Ok(vector.normalize())
+}
+
+fn resolve_target(options: &ExportOptions) -> Result<Target, String> {
+    match &options.source {
+        Source::Body(name) => {
+            // Los mismos cuerpos que vería el simulador con estos argumentos
+            match options.seed {
+                Some(seed) => catalog::install(generator::generate(seed).bodies),
+                None => catalog::install(scene_config::load()?),
+            }
+            let info = catalog::find(name).ok_or_else(|| {
+                let names: Vec<_> = catalog::bodies().iter().map(|body| body.name.to_string()).collect();
+                format!("no hay ningún cuerpo llamado '{}' (hay: {})", name, names.join(", "))
+            })?;
+            let surface = info.surface.clone().or_else(|| family_style(info.shader_id));
+            Ok(Target { name: info.name.to_string(), shader_id: info.shader_id, surface })
+        }
+        Source::Shader(id) => Ok(Target { name: format!("shader{}", id), shader_id: *id, surface: family_style(*id) }),
+        Source::Preset(name) => {
+            let surface = scene_config::preset(name).ok_or_else(|| format!("preset desconocido '{}'", name))?;
+            Ok(Target { name: name.clone(), shader_id: 0, surface: Some(surface) })
+        }
+    }
+}
+
+fn default_output(name: &str, mode: Mode) -> String {
+    let name = name.to_lowercase().replace(' ', "_");
+    match mode {
+        Mode::Map => format!("{}_map.png", name),
+        Mode::Cube => format!("{}_cube.png", name),
+        Mode::Sphere => format!("{}_sphere.png", name),
+    }
+}
+
+// Color de la superficie en una dirección, con el punto visto de frente. Con
+// `light` (en el espacio del modelo), iluminado desde ahí; sin ella, el albedo
+// de la capa estática si la tiene (sin nubes ni luz) y si no, la luz detrás
+// del observador, sin terminador ni halo
+fn face_on(target: &Target, direction: Vec3, light: Option<Vec3>, time: f32) -> Color {
+    // El punto queda de frente: su este, norte y vertical pasan a x, y, z
+    let (east, north) = tangent_basis(direction);
+    let to_image = Mat3::from_rows(&[east.transpose(), north.transpose(), direction.transpose()]);
+    if let Some(light) = light {
+        return target.shade(direction, VIEW_DIR, &to_image, to_image * light, time);
+    }
+    match target.surface.as_ref().and_then(|surface| surface.surface(direction)) {
+        Some(sample) => sample.albedo,
+        None => target.shade(direction, VIEW_DIR, &to_image, VIEW_DIR, time),
+    }
+}
+
+// Recorre la imagen; None deja el píxel transparente
+fn render(width: usize, height: usize, pixel: impl Fn(usize, usize) -> Option<Color>) -> Vec<u8> {
+    let mut pixels = vec![0u8; width * height * 4];
+    for (i, rgba) in pixels.chunks_exact_mut(4).enumerate() {
+        if let Some(color) = pixel(i % width, i / width) {
+            rgba.copy_from_slice(&[color.r(), color.g(), color.b(), 255]);
+        }
+    }
+    pixels
+}
+
+fn write(filename: &str, width: usize, height: usize, pixels: &[u8]) -> Result<(), String> {
+    png::write_rgba(filename, width, height, pixels).map_err(|e| format!("no se pudo escribir {}: {}", filename, e))?;
+    println!("Exportado {} ({}x{})", filename, width, height);
+    Ok(())
+}
+
+// Misma proyección que las texturas horneadas: longitud -180° a 180° de
+// izquierda a derecha y latitud 90° a -90° de arriba abajo
+fn map_direction(x: usize, y: usize, width: usize, height: usize) -> Vec3 {
+    let longitude = (x as f32 + 0.5) / width as f32 * TAU - PI;
+    let latitude = FRAC_PI_2 - (y as f32 + 0.5) / height as f32 * PI;
+    Vec3::new(latitude.cos() * longitude.cos(), latitude.sin(), latitude.cos() * longitude.sin())
+}
+
+// Caras en el orden y con la orientación habituales de los cubemaps
+const CUBE_FACES: [&str; 6] = ["px", "nx", "py", "ny", "pz", "nz"];
+
+fn cube_direction(face: usize, x: usize, y: usize, size: usize) -> Vec3 {
+    let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+    let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+    let direction = match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    };
+    direction.normalize()
+}
@@ -5,6 +5,7 @@ pub struct Fragment {
     pub position: Vec2,
     pub color: Color,
     pub depth: f32,
+    pub alpha: f32, // Menos de 1 para capas translúcidas como las nubes
 }
 
 impl Fragment {
@@ -13,6 +14,16 @@ impl Fragment {
             position: Vec2::new(x, y),
             color,
             depth,
+            alpha: 1.0,
         }
     }
-}
\ No newline at end of file
+
+    pub fn translucent(x: f32, y: f32, color: Color, depth: f32, alpha: f32) -> Self {
+        Fragment {
+            position: Vec2::new(x, y),
+            color,
+            depth,
+            alpha,
+        }
+    }
+}
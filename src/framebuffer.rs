@@ -1,5 +1,10 @@
 // framebuffer.rs
 
+use crate::font::{glyph, GLYPH_HEIGHT, GLYPH_SPACING, GLYPH_WIDTH};
+
+// Límite para los sprites aditivos muy cercanos a la cámara
+const MAX_SPRITE_RADIUS: f32 = 120.0;
+
 pub struct Framebuffer {
     pub width: usize,
     pub height: usize,
@@ -7,6 +12,8 @@ pub struct Framebuffer {
     pub zbuffer: Vec<f32>,
     background_color: u32,
     current_color: u32,
+    // Rectángulo (x, y, ancho, alto) que limita la capa 2D
+    clip_rect: Option<(i32, i32, i32, i32)>,
 }
 
 impl Framebuffer {
@@ -18,6 +25,7 @@ impl Framebuffer {
             zbuffer: vec![f32::INFINITY; width * height],
             background_color: 0x000000,
             current_color: 0xFFFFFF,
+            clip_rect: None,
         }
     }
 
@@ -40,6 +48,24 @@ impl Framebuffer {
         }
     }
 
+    // Capa translúcida (nubes): se mezcla con lo que hay debajo si pasa la
+    // prueba de profundidad, y no escribe en el Z-buffer
+    pub fn blend_point(&mut self, x: usize, y: usize, depth: f32, color: u32, alpha: f32) {
+        if x < self.width && y < self.height {
+            let index = y * self.width + x;
+            if self.zbuffer[index] > depth {
+                let alpha = alpha.clamp(0.0, 1.0);
+                let dst = self.buffer[index];
+                let mix = |shift: u32| {
+                    let s = ((color >> shift) & 0xFF) as f32;
+                    let d = ((dst >> shift) & 0xFF) as f32;
+                    ((s * alpha + d * (1.0 - alpha)) as u32) << shift
+                };
+                self.buffer[index] = mix(16) | mix(8) | mix(0);
+            }
+        }
+    }
+
     pub fn set_background_color(&mut self, color: u32) {
         self.background_color = color;
     }
@@ -47,4 +73,170 @@ impl Framebuffer {
     pub fn set_current_color(&mut self, color: u32) {
         self.current_color = color;
     }
-}
\ No newline at end of file
+
+    // === EFECTOS ADITIVOS ===
+    // Suman luz sobre la imagen con prueba de profundidad, pero sin escribir
+    // en el Z-buffer: los brillos se acumulan entre sí y no tapan lo de detrás
+
+    pub fn add_pixel(&mut self, x: i32, y: i32, depth: f32, color: u32, intensity: f32) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height || intensity <= 0.0 {
+            return;
+        }
+        let index = y as usize * self.width + x as usize;
+        if self.zbuffer[index] <= depth {
+            return;
+        }
+        let dst = self.buffer[index];
+        let add = |shift: u32| {
+            let s = ((color >> shift) & 0xFF) as f32 * intensity;
+            let d = ((dst >> shift) & 0xFF) as f32;
+            ((s + d).min(255.0) as u32) << shift
+        };
+        self.buffer[index] = add(16) | add(8) | add(0);
+    }
+
+    // Punto difuso centrado en (x, y) que se desvanece hacia el borde
+    pub fn add_sprite(&mut self, x: f32, y: f32, depth: f32, radius: f32, color: u32, intensity: f32) {
+        if radius < 1.0 {
+            // Más pequeño que un pixel: la luz se reparte en su área
+            self.add_pixel(x as i32, y as i32, depth, color, intensity * radius.max(0.25));
+            return;
+        }
+        let radius = radius.min(MAX_SPRITE_RADIUS);
+        let r = radius.ceil() as i32;
+        let (cx, cy) = (x.floor() as i32, y.floor() as i32);
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let px = (cx + dx) as f32 + 0.5 - x;
+                let py = (cy + dy) as f32 + 0.5 - y;
+                let d2 = (px * px + py * py) / (radius * radius);
+                if d2 < 1.0 {
+                    let falloff = (1.0 - d2) * (1.0 - d2);
+                    self.add_pixel(cx + dx, cy + dy, depth, color, intensity * falloff);
+                }
+            }
+        }
+    }
+
+    // === CAPA 2D (HUD) ===
+    // Se dibuja encima de todo, sin prueba de profundidad
+
+    // Limitar el dibujo 2D a un rectángulo (paneles como el minimapa)
+    pub fn set_clip_rect(&mut self, x: i32, y: i32, width: usize, height: usize) {
+        self.clip_rect = Some((x, y, width as i32, height as i32));
+    }
+
+    pub fn clear_clip_rect(&mut self) {
+        self.clip_rect = None;
+    }
+
+    // Mezcla un color sobre el pixel con la opacidad dada (0.0 - 1.0)
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: u32, alpha: f32) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        if let Some((cx, cy, cw, ch)) = self.clip_rect
+            && (x < cx || y < cy || x >= cx + cw || y >= cy + ch)
+        {
+            return;
+        }
+        let index = y as usize * self.width + x as usize;
+        let alpha = alpha.clamp(0.0, 1.0);
+        let dst = self.buffer[index];
+        let mix = |shift: u32| {
+            let s = ((color >> shift) & 0xFF) as f32;
+            let d = ((dst >> shift) & 0xFF) as f32;
+            ((s * alpha + d * (1.0 - alpha)) as u32) << shift
+        };
+        self.buffer[index] = mix(16) | mix(8) | mix(0);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: usize, height: usize, color: u32, alpha: f32) {
+        for py in y..y + height as i32 {
+            for px in x..x + width as i32 {
+                self.blend_pixel(px, py, color, alpha);
+            }
+        }
+    }
+
+    // Escribe texto con la fuente bitmap. (x, y) es la esquina superior izquierda.
+    // Devuelve el ancho dibujado en pixeles.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: u32, scale: usize, alpha: f32) -> usize {
+        let scale = scale.max(1);
+        let advance = ((GLYPH_WIDTH + GLYPH_SPACING) * scale) as i32;
+        let mut cursor = x;
+
+        for c in text.chars() {
+            let columns = glyph(c);
+            for (col, bits) in columns.iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT {
+                    if bits & (1 << row) == 0 {
+                        continue;
+                    }
+                    let px = cursor + (col * scale) as i32;
+                    let py = y + (row * scale) as i32;
+                    for sy in 0..scale as i32 {
+                        for sx in 0..scale as i32 {
+                            self.blend_pixel(px + sx, py + sy, color, alpha);
+                        }
+                    }
+                }
+            }
+            cursor += advance;
+        }
+
+        (cursor - x).max(0) as usize
+    }
+
+    // Línea 2D con la misma opacidad en todo el trazo
+    pub fn draw_line_2d(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u32, alpha: f32) {
+        let (mut x, mut y) = (x0, y0);
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            self.blend_pixel(x, y, color, alpha);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    // Triángulo 2D relleno (flechas del HUD)
+    pub fn fill_triangle_2d(&mut self, a: (f32, f32), b: (f32, f32), c: (f32, f32), color: u32, alpha: f32) {
+        let min_x = a.0.min(b.0).min(c.0).floor() as i32;
+        let max_x = a.0.max(b.0).max(c.0).ceil() as i32;
+        let min_y = a.1.min(b.1).min(c.1).floor() as i32;
+        let max_y = a.1.max(b.1).max(c.1).ceil() as i32;
+
+        let edge = |p: (f32, f32), q: (f32, f32), x: f32, y: f32| (q.0 - p.0) * (y - p.1) - (q.1 - p.1) * (x - p.0);
+        let area = edge(a, b, c.0, c.1);
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(b, c, px, py) / area;
+                let w1 = edge(c, a, px, py) / area;
+                let w2 = edge(a, b, px, py) / area;
+                if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                    self.blend_pixel(x, y, color, alpha);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,547 @@
+// Generador de sistemas estelares a partir de una semilla: la estrella, los
+// planetas con sus lunas y los cinturones. Sigue reglas sencillas de los
+// sistemas reales: rocosos dentro de la línea de hielo, gigantes fuera,
+// océanos solo en la zona habitable y órbitas cada vez más separadas. El
+// resultado es un catálogo como el del Sistema Solar, así que la escena, el
+// HUD y el minimapa lo usan sin cambios.
+
+use std::borrow::Cow;
+use crate::belts::BeltZone;
+use crate::catalog::{self, BodyInfo, BodyKind, CATALOG};
+use crate::color::Color;
+use crate::planet_shaders::{GiantStyle, MoonStyle, StarStyle, Storm, SurfaceStyle, TerrestrialStyle};
+use crate::rng::Rng;
+
+const SUN_MASS_KG: f64 = 1.989e30;
+const SUN_RADIUS_KM: f32 = 696_340.0;
+const EARTH_MASS_KG: f64 = 5.972e24;
+const EARTH_RADIUS_KM: f32 = 6371.0;
+const EARTH_YEAR_DAYS: f32 = 365.26;
+
+const MIN_PLANETS: usize = 3;
+const MAX_PLANETS: usize = 9;
+// Más allá de esto (radio en la escena) los planetas se saldrían del minimapa
+const MAX_ORBIT_RADIUS: f32 = 2300.0;
+// Hueco mínimo en la escena entre los sistemas de lunas de dos planetas vecinos
+const ORBIT_GAP: f32 = 40.0;
+// Las órbitas de las lunas van de 1.4 a unos 2.4 radios del planeta
+const FIRST_MOON_ORBIT: f32 = 1.4;
+const MOON_ORBIT_STEP: (f32, f32) = (0.2, 0.35);
+
+// Separación extra entre el último rocoso y el primer gigante
+const SNOW_LINE_GAP: f32 = 1.6;
+
+const ASTEROID_BELT_COUNT: usize = 1500;
+const KUIPER_BELT_COUNT: usize = 500;
+
+const LETTERS: [&str; 9] = ["b", "c", "d", "e", "f", "g", "h", "i", "j"];
+const NUMERALS: [&str; 6] = ["I", "II", "III", "IV", "V", "VI"];
+const SYLLABLES: [&str; 16] = [
+    "ka", "lo", "ri", "ve", "ta", "no", "mi", "sa", "ze", "du", "ran", "tor", "bel", "qui", "ar", "on",
+];
+
+pub struct GeneratedSystem {
+    pub bodies: Vec<BodyInfo>,
+    pub belts: Vec<BeltZone>,
+}
+
+// Tipos espectrales de estrella con sus rangos (en unidades solares)
+struct StarClass {
+    description: &'static str,
+    weight: f32,
+    radius: (f32, f32),
+    mass: (f32, f32),
+    luminosity: (f32, f32),
+    core: u32,
+    hot: u32,
+    flare: u32,
+    activity: f32, // Cuánto late el brillo: las enanas rojas tienen fulguraciones
+}
+
+// Las enanas rojas son las más comunes; las de tipo A, raras
+const STAR_CLASSES: [StarClass; 5] = [
+    StarClass {
+        description: "Enana roja de tipo M: fría, pequeña y de vida muy larga.",
+        weight: 0.45,
+        radius: (0.3, 0.6),
+        mass: (0.2, 0.6),
+        luminosity: (0.01, 0.08),
+        core: 0xFFB070,
+        hot: 0xFF7A3A,
+        flare: 0xD83A10,
+        activity: 0.2,
+    },
+    StarClass {
+        description: "Enana naranja de tipo K: estable y algo más fría que el Sol.",
+        weight: 0.25,
+        radius: (0.7, 0.9),
+        mass: (0.6, 0.9),
+        luminosity: (0.15, 0.6),
+        core: 0xFFD890,
+        hot: 0xFFA050,
+        flare: 0xF05A18,
+        activity: 0.12,
+    },
+    StarClass {
+        description: "Estrella de tipo G, parecida al Sol.",
+        weight: 0.15,
+        radius: (0.9, 1.2),
+        mass: (0.9, 1.1),
+        luminosity: (0.7, 1.5),
+        core: 0xFFFF64,
+        hot: 0xFF9632,
+        flare: 0xFF3200,
+        activity: 0.1,
+    },
+    StarClass {
+        description: "Estrella de tipo F: blanco amarillenta y más caliente que el Sol.",
+        weight: 0.10,
+        radius: (1.2, 1.6),
+        mass: (1.1, 1.5),
+        luminosity: (2.0, 5.0),
+        core: 0xFFFFF0,
+        hot: 0xFFF0B0,
+        flare: 0xFFB060,
+        activity: 0.06,
+    },
+    StarClass {
+        description: "Estrella blanca de tipo A: caliente, luminosa y de vida corta.",
+        weight: 0.05,
+        radius: (1.7, 2.5),
+        mass: (1.6, 2.5),
+        luminosity: (10.0, 25.0),
+        core: 0xF4F8FF,
+        hot: 0xCAD8FF,
+        flare: 0x8FA8FF,
+        activity: 0.03,
+    },
+];
+
+// Qué tipo de planeta se forma a cada distancia
+#[derive(Clone, Copy, PartialEq)]
+enum PlanetClass {
+    Temperate, // Zona habitable: océanos y nubes
+    Hot,       // Más cerca: roca desnuda o nubes densas
+    Cold,      // Entre la zona habitable y la línea de hielo: desierto frío
+    GasGiant,
+    IceGiant,
+}
+
+pub fn generate(seed: u64) -> GeneratedSystem {
+    let mut rng = Rng::new(seed);
+    let mut bodies = Vec::new();
+
+    // === ESTRELLA ===
+    let class = pick_star_class(&mut rng);
+    let star_name = star_name(&mut rng);
+    let star_mass = rng.range(class.mass.0, class.mass.1);
+    let luminosity = rng.range(class.luminosity.0, class.luminosity.1);
+    let star = BodyInfo {
+        name: Cow::Owned(star_name.clone()),
+        kind: BodyKind::Star,
+        parent: None,
+        radius_km: rng.range(class.radius.0, class.radius.1) * SUN_RADIUS_KM,
+        mass_kg: star_mass as f64 * SUN_MASS_KG,
+        orbital_period_days: 0.0,
+        day_length_hours: rng.range(300.0, 900.0),
+        axial_tilt_deg: rng.range(0.0, 10.0),
+        orbit_inclination_deg: 0.0,
+        tidally_locked: false,
+        moon_count: 0,
+        description: Cow::Borrowed(class.description),
+        shader_id: 1,
+        orbit_radius: 0.0,
+        shape: [1.0, 1.0, 1.0],
+        surface: Some(SurfaceStyle::Star(StarStyle {
+            core: Color::from_hex(class.core),
+            hot: Color::from_hex(class.hot),
+            flare: Color::from_hex(class.flare),
+            spot: Color::from_hex(class.flare).scale(0.5),
+            corona: Color::from_hex(class.hot),
+            granulation_scale: 1.0,
+            spot_scale: 1.5,
+            spot_coverage: rng.range(0.1, 0.3),
+            corona_strength: 0.6,
+            pulse: class.activity,
+        })),
+    };
+    let star_scale = star.star_scale();
+    bodies.push(star);
+
+    // === PLANETAS ===
+    // La luz cae con el cuadrado de la distancia: las zonas escalan con √L
+    let snow_line = 2.7 * luminosity.sqrt();
+    let habitable_zone = (0.95 * luminosity.sqrt(), 1.4 * luminosity.sqrt());
+    let planet_count = MIN_PLANETS + rng.index(MAX_PLANETS - MIN_PLANETS + 1);
+    let mut au = rng.range(0.2, 0.5) * luminosity.sqrt().max(0.1);
+    let mut previous_edge = star_scale;
+    let mut planet_range = Vec::new();
+
+    for letter in LETTERS.iter().take(planet_count) {
+        let class = planet_class(&mut rng, au, snow_line, habitable_zone);
+        let name = format!("{} {}", star_name, letter);
+        let (mut planet, moons) = create_planet(&mut rng, name, class, au, star_mass);
+
+        // Órbita en la escena: la del Sistema Solar para esa distancia, o más
+        // lejos si no cabe junto al planeta anterior y sus lunas
+        let scale = planet.planet_scale();
+        let reach = moons
+            .iter()
+            .map(|moon| scale * moon.orbit_radius + moon.moon_scale(&planet, scale))
+            .fold(scale, f32::max);
+        let orbit_radius = catalog::scene_radius_in(&CATALOG, planet.semi_major_axis_au())
+            .max(previous_edge + reach + ORBIT_GAP);
+        if orbit_radius > MAX_ORBIT_RADIUS {
+            break;
+        }
+        planet.orbit_radius = orbit_radius;
+        planet.moon_count = moons.len() as u32;
+        previous_edge = orbit_radius + reach;
+        planet_range.push((class, planet.semi_major_axis_au()));
+
+        bodies.push(planet);
+        bodies.extend(moons);
+        // Cada órbita bastante más lejos que la anterior. Al cruzar la línea
+        // de hielo queda un hueco más ancho, donde puede ir un cinturón
+        let next_au = au * rng.range(1.4, 2.0);
+        au = if au < snow_line && next_au >= snow_line { next_au * SNOW_LINE_GAP } else { next_au };
+    }
+
+    GeneratedSystem { belts: create_belt_zones(&mut rng, &planet_range), bodies }
+}
+
+fn pick_star_class(rng: &mut Rng) -> &'static StarClass {
+    let total: f32 = STAR_CLASSES.iter().map(|class| class.weight).sum();
+    let mut roll = rng.range(0.0, total);
+    for class in &STAR_CLASSES {
+        if roll < class.weight {
+            return class;
+        }
+        roll -= class.weight;
+    }
+    &STAR_CLASSES[STAR_CLASSES.len() - 1]
+}
+
+fn planet_class(rng: &mut Rng, au: f32, snow_line: f32, habitable_zone: (f32, f32)) -> PlanetClass {
+    if au < habitable_zone.0 {
+        PlanetClass::Hot
+    } else if au <= habitable_zone.1 {
+        PlanetClass::Temperate
+    } else if au < snow_line {
+        PlanetClass::Cold
+    } else if rng.next_f32() < (snow_line / au).powi(2) * 0.8 + 0.2 {
+        // Justo pasada la línea de hielo hay más material: gigantes de gas
+        PlanetClass::GasGiant
+    } else {
+        PlanetClass::IceGiant
+    }
+}
+
+// Planeta y sus lunas. Las masas y radios siguen relaciones aproximadas de
+// los planetas conocidos; el periodo sale de la tercera ley de Kepler.
+fn create_planet(rng: &mut Rng, name: String, class: PlanetClass, au: f32, star_mass: f32) -> (BodyInfo, Vec<BodyInfo>) {
+    let (earth_masses, radius_km) = match class {
+        PlanetClass::GasGiant => (rng.range(50.0, 600.0), rng.range(55_000.0, 75_000.0)),
+        PlanetClass::IceGiant => (rng.range(10.0, 25.0), rng.range(22_000.0, 27_000.0)),
+        _ => {
+            let mass = 0.05 * 160f32.powf(rng.next_f32());
+            (mass, EARTH_RADIUS_KM * mass.powf(0.28))
+        }
+    };
+    let giant = matches!(class, PlanetClass::GasGiant | PlanetClass::IceGiant);
+
+    let (description, shader_id, surface) = match class {
+        PlanetClass::Temperate => (
+            "Mundo rocoso templado con océanos, casquetes y nubes.",
+            0,
+            terrestrial_style(rng, class),
+        ),
+        PlanetClass::Hot if rng.next_f32() < 0.5 => (
+            "Mundo rocoso caliente sin atmósfera, cubierto de cráteres.",
+            4,
+            SurfaceStyle::Airless(airless_style(rng, false)),
+        ),
+        PlanetClass::Hot => (
+            "Mundo rocoso caliente envuelto en nubes densas.",
+            7,
+            terrestrial_style(rng, class),
+        ),
+        PlanetClass::Cold => (
+            "Mundo rocoso frío y desértico con casquetes de hielo.",
+            3,
+            terrestrial_style(rng, class),
+        ),
+        PlanetClass::GasGiant => ("Gigante gaseoso de hidrógeno y helio.", 2, giant_style(rng, class)),
+        PlanetClass::IceGiant => ("Gigante de hielo con una atmósfera de metano.", 5, giant_style(rng, class)),
+    };
+
+    let period_days = EARTH_YEAR_DAYS * (au.powi(3) / star_mass).sqrt();
+    let planet = BodyInfo {
+        name: Cow::Owned(name),
+        kind: BodyKind::Planet,
+        parent: None,
+        radius_km,
+        mass_kg: earth_masses as f64 * EARTH_MASS_KG,
+        orbital_period_days: period_days,
+        day_length_hours: if giant { rng.range(9.0, 18.0) } else { rng.range(10.0, 60.0) },
+        axial_tilt_deg: if rng.next_f32() < 0.05 { rng.range(90.0, 180.0) } else { rng.range(0.0, 30.0) },
+        orbit_inclination_deg: rng.range(0.0, 4.0),
+        // Muy cerca de la estrella la marea frena el giro
+        tidally_locked: au < 0.1,
+        moon_count: 0,
+        description: Cow::Borrowed(description),
+        shader_id,
+        orbit_radius: 0.0,
+        shape: [1.0, 1.0, 1.0],
+        surface: Some(surface),
+    };
+
+    let moon_count = match class {
+        PlanetClass::GasGiant => 2 + rng.index(4),
+        PlanetClass::IceGiant => 1 + rng.index(4),
+        PlanetClass::Hot => 0,
+        _ => rng.index(3),
+    };
+    let mut orbit = FIRST_MOON_ORBIT;
+    let moons = (0..moon_count)
+        .map(|index| {
+            let moon = create_moon(rng, &planet.name, NUMERALS[index], orbit, class);
+            orbit += rng.range(MOON_ORBIT_STEP.0, MOON_ORBIT_STEP.1);
+            moon
+        })
+        .collect();
+    (planet, moons)
+}
+
+fn create_moon(rng: &mut Rng, planet: &str, numeral: &str, orbit_radius: f32, class: PlanetClass) -> BodyInfo {
+    let icy = matches!(class, PlanetClass::GasGiant | PlanetClass::IceGiant | PlanetClass::Cold);
+    let radius_km = 150.0 * 18f32.powf(rng.next_f32());
+    let retrograde = rng.next_f32() < 0.1;
+    // Solo importa la proporción entre lunas de un mismo planeta
+    let orbital_period_days = 1.5 * orbit_radius.powf(1.5);
+    BodyInfo {
+        name: Cow::Owned(format!("{} {}", planet, numeral)),
+        kind: BodyKind::Moon,
+        parent: Some(Cow::Owned(planet.to_string())),
+        radius_km,
+        mass_kg: 8.4e18 * (radius_km as f64 / 100.0).powi(3),
+        orbital_period_days,
+        // Acoplada por marea: un día dura una vuelta a su planeta
+        day_length_hours: orbital_period_days * 24.0,
+        axial_tilt_deg: 0.0,
+        orbit_inclination_deg: if retrograde { rng.range(150.0, 175.0) } else { rng.range(0.0, 5.0) },
+        tidally_locked: true,
+        moon_count: 0,
+        description: Cow::Borrowed(if icy { "Luna helada cubierta de cráteres." } else { "Luna rocosa cubierta de cráteres." }),
+        shader_id: 13,
+        orbit_radius,
+        shape: [1.0, 1.0, 1.0],
+        surface: Some(SurfaceStyle::Airless(airless_style(rng, icy))),
+    }
+}
+
+// Un cinturón rocoso donde termina la zona de los rocosos, si hay hueco
+// antes del primer gigante, y otro helado más allá del último planeta
+fn create_belt_zones(rng: &mut Rng, planets: &[(PlanetClass, f32)]) -> Vec<BeltZone> {
+    let mut zones = Vec::new();
+    let is_giant = |class: &PlanetClass| matches!(class, PlanetClass::GasGiant | PlanetClass::IceGiant);
+    if let Some(first_giant) = planets.iter().position(|(class, _)| is_giant(class))
+        && first_giant > 0
+        && rng.next_f32() < 0.7
+    {
+        let inner_au = planets[first_giant - 1].1 * 1.3;
+        let outer_au = planets[first_giant].1 * 0.7;
+        if outer_au > inner_au * 1.15 {
+            zones.push(BeltZone { inner_au, outer_au, count: ASTEROID_BELT_COUNT, icy: false });
+        }
+    }
+    if let Some(&(_, last_au)) = planets.last()
+        && rng.next_f32() < 0.7
+    {
+        zones.push(BeltZone { inner_au: last_au * 1.35, outer_au: last_au * 1.7, count: KUIPER_BELT_COUNT, icy: true });
+    }
+    zones
+}
+
+// === ESTILOS DE SUPERFICIE ===
+
+fn terrestrial_style(rng: &mut Rng, class: PlanetClass) -> SurfaceStyle {
+    let seed = rng.next_u64() as u32;
+    // Los detalles salen de la semilla del planeta y no del generador, así
+    // las órbitas de una semilla no cambian al añadir parámetros
+    let mut detail = Rng::new(seed as u64);
+    let style = match class {
+        PlanetClass::Temperate => TerrestrialStyle {
+            deep_ocean: hsv(rng.range(205.0, 230.0), 0.9, 0.45),
+            shallow_ocean: hsv(rng.range(185.0, 205.0), 0.7, 0.7),
+            lowland: hsv(rng.range(70.0, 130.0), 0.55, 0.5),
+            highland: hsv(rng.range(25.0, 45.0), 0.35, 0.6),
+            dark_terrain: hsv(detail.range(70.0, 130.0), 0.5, 0.3),
+            ice: Color::new(240, 245, 250),
+            cloud: Color::new(255, 255, 255),
+            city: Color::black(),
+            ocean_level: rng.range(0.4, 0.65),
+            dark_coverage: 0.0,
+            canyons: 0.0,
+            relief: 0.0, // Sin cráteres ni cañones que marcar
+            crater_density: 0.0,
+            ice_latitude: rng.range(0.7, 0.88),
+            cloud_cover: rng.range(0.25, 0.55),
+            city_lights: 0.0, // Mundos sin habitar
+            glint: 0.6,
+            ambient: 0.5,
+            feature_scale: rng.range(1.2, 2.5),
+            seed,
+        },
+        PlanetClass::Hot => TerrestrialStyle {
+            deep_ocean: Color::black(),
+            shallow_ocean: Color::black(),
+            lowland: hsv(rng.range(20.0, 45.0), 0.6, 0.6),
+            highland: hsv(rng.range(30.0, 50.0), 0.4, 0.8),
+            dark_terrain: hsv(detail.range(15.0, 35.0), 0.5, 0.3),
+            ice: Color::new(240, 230, 200),
+            cloud: hsv(rng.range(40.0, 55.0), 0.35, 0.95),
+            city: Color::black(),
+            ocean_level: 0.0,
+            dark_coverage: detail.range(0.1, 0.35),
+            canyons: 0.0,
+            relief: 0.4,
+            crater_density: detail.range(0.1, 0.4),
+            ice_latitude: 2.0, // Sin casquetes
+            cloud_cover: rng.range(0.7, 0.95),
+            city_lights: 0.0,
+            glint: 0.0,
+            ambient: 0.55, // Atmósfera densa que difunde la luz
+            feature_scale: rng.range(1.5, 3.0),
+            seed,
+        },
+        _ => TerrestrialStyle {
+            deep_ocean: hsv(rng.range(200.0, 220.0), 0.6, 0.3),
+            shallow_ocean: hsv(rng.range(190.0, 210.0), 0.5, 0.45),
+            lowland: hsv(rng.range(5.0, 25.0), 0.65, 0.55),
+            highland: hsv(rng.range(15.0, 35.0), 0.45, 0.7),
+            dark_terrain: hsv(detail.range(5.0, 25.0), 0.55, 0.3),
+            ice: Color::new(235, 240, 245),
+            cloud: Color::new(235, 225, 215),
+            city: Color::black(),
+            ocean_level: if rng.next_f32() < 0.3 { rng.range(0.1, 0.25) } else { 0.0 },
+            dark_coverage: detail.range(0.1, 0.3),
+            canyons: if detail.next_f32() < 0.4 { detail.range(0.2, 0.6) } else { 0.0 },
+            relief: 0.7,
+            crater_density: detail.range(0.1, 0.35),
+            ice_latitude: rng.range(0.55, 0.8),
+            cloud_cover: rng.range(0.0, 0.2),
+            city_lights: 0.0,
+            glint: 0.4,
+            ambient: 0.35,
+            feature_scale: rng.range(1.5, 3.0),
+            seed,
+        },
+    };
+    SurfaceStyle::Terrestrial(style)
+}
+
+fn giant_style(rng: &mut Rng, class: PlanetClass) -> SurfaceStyle {
+    // Gigantes de gas en tonos cálidos; los de hielo, azules o cian
+    let (hue, saturation, turbulence, band_count) = if class == PlanetClass::GasGiant {
+        (rng.range(15.0, 45.0), rng.range(0.3, 0.55), rng.range(0.2, 0.45), rng.range(5.0, 9.0))
+    } else {
+        (rng.range(175.0, 225.0), rng.range(0.35, 0.6), rng.range(0.05, 0.3), rng.range(2.0, 4.0))
+    };
+    let storm_latitude = if rng.next_f32() < 0.6 { Some(rng.range(-0.5, 0.5)) } else { None };
+    let seed = rng.next_u64() as u32;
+    let mut detail = Rng::new(seed as u64);
+
+    // Una tormenta grande y, a veces, óvalos pequeños que derivan
+    let storm_color = hsv(hue - 10.0, (saturation * 1.5).min(1.0), 0.6);
+    let mut storms = Vec::new();
+    if let Some(latitude) = storm_latitude {
+        storms.push(Storm {
+            latitude_deg: latitude.asin().to_degrees(),
+            longitude_deg: detail.range(-180.0, 180.0),
+            width_deg: detail.range(10.0, 22.0),
+            height_deg: detail.range(5.0, 10.0),
+            color: storm_color,
+            drift: detail.range(-0.2, 0.2),
+        });
+    }
+    for _ in 0..detail.index(3) {
+        storms.push(Storm {
+            latitude_deg: detail.range(-50.0, 50.0),
+            longitude_deg: detail.range(-180.0, 180.0),
+            width_deg: detail.range(3.0, 7.0),
+            height_deg: detail.range(2.0, 4.0),
+            color: hsv(hue, saturation * 0.3, 0.95),
+            drift: detail.range(-0.5, 0.5),
+        });
+    }
+
+    SurfaceStyle::Giant(GiantStyle {
+        bands: [
+            hsv(hue - 15.0, saturation * 0.4, 0.95),
+            hsv(hue, saturation, 0.8),
+            hsv(hue + 10.0, saturation * 1.2, 0.55),
+        ],
+        eddy: hsv(hue, saturation * 0.3, 0.9),
+        haze: hsv(hue, saturation * 0.5, 0.9),
+        band_count,
+        band_contrast: detail.range(0.5, 1.0),
+        turbulence,
+        eddy_coverage: detail.range(0.0, 0.3),
+        polar_haze: if class == PlanetClass::IceGiant { detail.range(0.0, 0.5) } else { 0.0 },
+        haze_strength: 0.3,
+        storms,
+        polar_vortex: None,
+        ambient: 0.3,
+        seed,
+    })
+}
+
+// Superficie sin aire: gris parduzco, o hielo sucio más allá de la línea de hielo
+fn airless_style(rng: &mut Rng, icy: bool) -> MoonStyle {
+    let (hue, saturation, value) = if icy {
+        (rng.range(20.0, 220.0), rng.range(0.05, 0.2), rng.range(0.6, 0.85))
+    } else {
+        (rng.range(20.0, 40.0), rng.range(0.1, 0.3), rng.range(0.4, 0.6))
+    };
+    MoonStyle {
+        base: hsv(hue, saturation, value),
+        dark: hsv(hue, saturation, value * 0.65),
+        bright: hsv(hue, saturation * 0.7, (value * 1.25).min(1.0)),
+        crater: hsv(hue, saturation * 0.5, if icy { 0.9 } else { value * 0.6 }),
+        dark_coverage: rng.range(0.2, 0.6),
+        crater_density: rng.range(0.15, 0.55),
+        grooves: if rng.next_f32() < 0.2 { rng.range(0.2, 0.6) } else { 0.0 },
+        lumpiness: 0.0,
+        relief: 0.6,
+        feature_scale: 1.5,
+        maria: Vec::new(),
+        ambient: 0.2,
+    }
+}
+
+// Color a partir de tono (grados), saturación y valor en [0, 1]
+fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let saturation = saturation.clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    Color::new(((r + m) * 255.0) as u8, ((g + m) * 255.0) as u8, ((b + m) * 255.0) as u8)
+}
+
+// Nombre de dos o tres sílabas con la primera letra en mayúscula
+fn star_name(rng: &mut Rng) -> String {
+    let syllables = 2 + rng.index(2);
+    let name: String = (0..syllables).map(|_| SYLLABLES[rng.index(SYLLABLES.len())]).collect();
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
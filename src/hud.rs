@@ -0,0 +1,280 @@
+use std::time::Instant;
+use crate::framebuffer::Framebuffer;
+use crate::font::{text_width, GLYPH_HEIGHT};
+use crate::indicators::{draw_indicators, format_distance};
+use crate::minimap::Minimap;
+use crate::catalog::{BodyInfo, BodyKind};
+use crate::simulation::Simulation;
+
+// Cuánto dura un aviso en pantalla y cuánto tarda en desvanecerse (segundos)
+const NOTIFICATION_TIME: f32 = 3.0;
+const NOTIFICATION_FADE: f32 = 0.8;
+const MAX_NOTIFICATIONS: usize = 5;
+
+const TEXT_SCALE: usize = 2;
+const LINE_HEIGHT: i32 = ((GLYPH_HEIGHT + 2) * TEXT_SCALE) as i32;
+const MARGIN: i32 = 12;
+
+const TEXT_COLOR: u32 = 0xDDE6FF;
+const ACCENT_COLOR: u32 = 0xFFC857;
+const WARNING_COLOR: u32 = 0xFF6B6B;
+const PANEL_COLOR: u32 = 0x05070F;
+
+struct Notification {
+    text: String,
+    age: f32,
+}
+
+// Contador de cuadros por segundo medido con el reloj real
+struct FpsCounter {
+    frames: u32,
+    window_start: Instant,
+    fps: f32,
+}
+
+impl FpsCounter {
+    fn tick(&mut self) {
+        self.frames += 1;
+        let seconds = self.window_start.elapsed().as_secs_f32();
+        if seconds >= 0.5 {
+            self.fps = self.frames as f32 / seconds;
+            self.frames = 0;
+            self.window_start = Instant::now();
+        }
+    }
+}
+
+// Capa de información sobre la imagen: estado de la nave, del reloj y avisos
+pub struct Hud {
+    notifications: Vec<Notification>,
+    fps: FpsCounter,
+    pub visible: bool,
+    pub minimap: Minimap,
+    // Líneas del panel de controles (F1); vacío si está cerrado
+    help_lines: Vec<String>,
+    // Error de los archivos de escena; se muestra hasta que se corrija
+    pub scene_error: Option<String>,
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Hud {
+            notifications: Vec::new(),
+            fps: FpsCounter { frames: 0, window_start: Instant::now(), fps: 0.0 },
+            visible: true,
+            minimap: Minimap::new(),
+            help_lines: Vec::new(),
+            scene_error: None,
+        }
+    }
+
+    pub fn notify(&mut self, text: impl Into<String>) {
+        self.notifications.push(Notification { text: text.into(), age: 0.0 });
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    // Abre o cierra el panel con las teclas asignadas
+    pub fn toggle_help(&mut self, lines: Vec<String>) {
+        if self.help_lines.is_empty() {
+            self.help_lines = lines;
+        } else {
+            self.help_lines.clear();
+        }
+    }
+
+    // Avanza los avisos y el contador de FPS; se llama una vez por cuadro
+    pub fn update(&mut self, dt: f32) {
+        self.fps.tick();
+        for notification in self.notifications.iter_mut() {
+            notification.age += dt;
+        }
+        self.notifications.retain(|n| n.age < NOTIFICATION_TIME);
+    }
+
+    pub fn draw(&self, framebuffer: &mut Framebuffer, sim: &Simulation) {
+        if !self.visible {
+            return;
+        }
+
+        draw_indicators(framebuffer, sim);
+        let status_bottom = self.draw_status(framebuffer, sim);
+        if let Some(target) = sim.selected_body.and_then(|i| sim.bodies.get(i)) {
+            self.draw_body_info(framebuffer, &target.info, status_bottom + MARGIN);
+        }
+        self.minimap.draw(framebuffer, sim);
+        self.draw_notifications(framebuffer);
+        if !self.help_lines.is_empty() {
+            self.draw_help(framebuffer);
+        }
+    }
+
+    // Panel superior izquierdo: FPS, velocidad, reloj y cuerpo más cercano.
+    // Devuelve la coordenada y donde termina el panel.
+    fn draw_status(&self, framebuffer: &mut Framebuffer, sim: &Simulation) -> i32 {
+        let mut lines: Vec<(String, u32)> = vec![
+            (format!("FPS {:.0}", self.fps.fps), TEXT_COLOR),
+            (format!("Velocidad {:.0} u/s", sim.ship.velocity.magnitude()), TEXT_COLOR),
+        ];
+
+        let clock_color = if sim.clock.is_paused() {
+            WARNING_COLOR
+        } else if sim.clock.is_reversed() {
+            ACCENT_COLOR
+        } else {
+            TEXT_COLOR
+        };
+        lines.push((format!("Tiempo {}", sim.clock.describe()), clock_color));
+        if sim.clock.is_paused() {
+            lines.push(("PAUSADO".to_string(), WARNING_COLOR));
+        }
+
+        let nearest = sim
+            .bodies
+            .iter()
+            .map(|body| (body, (body.position - sim.ship.position).magnitude() - body.radius))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((body, distance)) = nearest {
+            lines.push((format!("Cerca: {} a {}", body.info.name, format_distance(distance.max(0.0))), TEXT_COLOR));
+        }
+        if let Some(target) = sim.selected_body.and_then(|i| sim.bodies.get(i)) {
+            let distance = ((target.position - sim.ship.position).magnitude() - target.radius).max(0.0);
+            lines.push((format!("Objetivo: {} a {}", target.info.name, format_distance(distance)), ACCENT_COLOR));
+        }
+
+        if sim.warp_drive.is_engaged() {
+            lines.push(("WARP".to_string(), ACCENT_COLOR));
+        }
+        if let Some(error) = &self.scene_error {
+            lines.push((error.clone(), WARNING_COLOR));
+        }
+
+        let width = lines.iter().map(|(text, _)| text_width(text, TEXT_SCALE)).max().unwrap_or(0);
+        let height = lines.len() as i32 * LINE_HEIGHT + 8;
+        framebuffer.fill_rect(MARGIN - 6, MARGIN - 6, width + 12, height as usize, PANEL_COLOR, 0.6);
+        for (i, (text, color)) in lines.iter().enumerate() {
+            framebuffer.draw_text(MARGIN, MARGIN + i as i32 * LINE_HEIGHT, text, *color, TEXT_SCALE, 1.0);
+        }
+        MARGIN - 6 + height
+    }
+
+    // Ficha del objetivo con los datos del catálogo
+    fn draw_body_info(&self, framebuffer: &mut Framebuffer, info: &BodyInfo, top: i32) {
+        let line_height = (GLYPH_HEIGHT + 3) as i32;
+        let panel_width: usize = 260;
+
+        let mut lines: Vec<(String, u32)> = Vec::new();
+        for line in wrap_text(&info.description, panel_width / 6) {
+            lines.push((line, TEXT_COLOR));
+        }
+        lines.push((String::new(), TEXT_COLOR));
+        lines.push((format!("Radio: {:.0} km", info.radius_km), TEXT_COLOR));
+        lines.push((format!("Masa: {:.3e} kg", info.mass_kg), TEXT_COLOR));
+        if info.orbital_period_days > 0.0 {
+            let period = format_period(info.orbital_period_days);
+            if info.is_retrograde_orbit() {
+                lines.push((format!("Órbita: {} (retrógrada)", period), TEXT_COLOR));
+            } else {
+                lines.push((format!("Órbita: {}", period), TEXT_COLOR));
+            }
+        }
+        let spin = format_day_length(info.day_length_hours);
+        if info.day_length_hours < 0.0 {
+            lines.push((format!("Día: {} (retrógrado)", spin), TEXT_COLOR));
+        } else {
+            lines.push((format!("Día: {}", spin), TEXT_COLOR));
+        }
+        lines.push((format!("Inclinación del eje: {:.2}°", info.axial_tilt_deg), TEXT_COLOR));
+        if matches!(info.kind, BodyKind::Planet | BodyKind::DwarfPlanet) {
+            lines.push((format!("Lunas: {}", info.moon_count), TEXT_COLOR));
+        }
+
+        let title_height = LINE_HEIGHT;
+        let height = title_height + lines.len() as i32 * line_height + 12;
+        framebuffer.fill_rect(MARGIN - 6, top, panel_width + 12, height as usize, PANEL_COLOR, 0.6);
+        framebuffer.draw_text(MARGIN, top + 6, &info.name, ACCENT_COLOR, TEXT_SCALE, 1.0);
+        for (i, (text, color)) in lines.iter().enumerate() {
+            framebuffer.draw_text(MARGIN, top + 6 + title_height + i as i32 * line_height, text, *color, 1, 1.0);
+        }
+    }
+
+    // Avisos centrados en la parte inferior, los más nuevos abajo
+    fn draw_notifications(&self, framebuffer: &mut Framebuffer) {
+        let bottom = framebuffer.height as i32 - MARGIN - LINE_HEIGHT;
+        for (i, notification) in self.notifications.iter().rev().enumerate() {
+            let remaining = NOTIFICATION_TIME - notification.age;
+            let alpha = (remaining / NOTIFICATION_FADE).min(1.0);
+            let width = text_width(&notification.text, TEXT_SCALE) as i32;
+            let x = (framebuffer.width as i32 - width) / 2;
+            let y = bottom - i as i32 * LINE_HEIGHT;
+            framebuffer.fill_rect(x - 6, y - 4, (width + 12) as usize, LINE_HEIGHT as usize, PANEL_COLOR, 0.5 * alpha);
+            framebuffer.draw_text(x, y, &notification.text, TEXT_COLOR, TEXT_SCALE, alpha);
+        }
+    }
+
+    // Panel de controles a la derecha
+    fn draw_help(&self, framebuffer: &mut Framebuffer) {
+        let scale = 1;
+        let line_height = (GLYPH_HEIGHT + 3) as i32;
+        let width = self.help_lines.iter().map(|line| text_width(line, scale)).max().unwrap_or(0) as i32;
+        let x = framebuffer.width as i32 - MARGIN - width;
+        let y = MARGIN;
+
+        framebuffer.fill_rect(
+            x - 8,
+            y - 8,
+            (width + 16) as usize,
+            (self.help_lines.len() as i32 * line_height + 16) as usize,
+            PANEL_COLOR,
+            0.75,
+        );
+        for (i, line) in self.help_lines.iter().enumerate() {
+            framebuffer.draw_text(x, y + i as i32 * line_height, line, TEXT_COLOR, scale, 1.0);
+        }
+    }
+}
+
+// Parte un texto en líneas de como mucho `max_chars` caracteres
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+// Periodo orbital en días o en años si es largo
+fn format_period(days: f32) -> String {
+    if days >= 730.0 {
+        format!("{:.1} años", days / 365.26)
+    } else {
+        format!("{:.1} días", days)
+    }
+}
+
+// Duración del día en horas o en días si es largo
+fn format_day_length(hours: f32) -> String {
+    let hours = hours.abs();
+    if hours >= 48.0 {
+        format!("{:.1} días", hours / 24.0)
+    } else {
+        format!("{:.1} h", hours)
+    }
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,125 @@
+use nalgebra_glm::{Mat4, Vec3};
+use crate::font::{text_width, GLYPH_HEIGHT};
+use crate::framebuffer::Framebuffer;
+use crate::shaders::project_point;
+use crate::simulation::Simulation;
+use crate::catalog::BodyKind;
+
+// Separación de las flechas respecto al borde de la pantalla
+const EDGE_MARGIN: f32 = 28.0;
+const ARROW_SIZE: f32 = 10.0;
+// Más lejos que esto las lunas no llevan etiqueta (se amontonan sobre su planeta)
+const MOON_LABEL_DISTANCE: f32 = 1500.0;
+
+const LABEL_COLOR: u32 = 0xB8C4E0;
+const TARGET_COLOR: u32 = 0xFFC857;
+
+// Distancia legible: en miles de unidades cuando es grande
+pub fn format_distance(distance: f32) -> String {
+    if distance >= 10000.0 {
+        format!("{:.1}k u", distance / 1000.0)
+    } else {
+        format!("{:.0} u", distance)
+    }
+}
+
+// Punto proyectado aunque esté fuera de pantalla o detrás de la cámara.
+// Devuelve (x, y en pixeles, si es visible en pantalla).
+fn project(world_pos: Vec3, view_projection_matrix: Mat4, width: f32, height: f32) -> (f32, f32, bool) {
+    let (screen, in_front) = project_point(world_pos, view_projection_matrix, width, height);
+    if !in_front {
+        // Empujar fuera de pantalla en la misma dirección desde el centro
+        let (dx, dy) = (screen.x / width - 0.5, screen.y / height - 0.5);
+        let len = (dx * dx + dy * dy).sqrt().max(1e-4);
+        return ((0.5 + dx / len) * width, (0.5 + dy / len) * height, false);
+    }
+    let on_screen = (0.0..=width).contains(&screen.x) && (0.0..=height).contains(&screen.y);
+    (screen.x, screen.y, on_screen)
+}
+
+// Etiquetas con nombre y distancia sobre cada cuerpo visible y flechas
+// en el borde para los que están fuera de la vista
+pub fn draw_indicators(framebuffer: &mut Framebuffer, sim: &Simulation) {
+    let (width, height) = (framebuffer.width as f32, framebuffer.height as f32);
+    let view_projection_matrix = sim.rig.view_projection_matrix();
+    let view = sim.rig.view();
+    let camera_right = view.direction().cross(&view.up).normalize();
+
+    for (i, body) in sim.bodies.iter().enumerate() {
+        let is_target = sim.selected_body == Some(i);
+        let color = if is_target { TARGET_COLOR } else { LABEL_COLOR };
+        let distance = ((body.position - sim.ship.position).magnitude() - body.radius).max(0.0);
+        let is_moon = body.info.kind == BodyKind::Moon;
+        if is_moon && !is_target && distance > MOON_LABEL_DISTANCE {
+            continue;
+        }
+
+        let (x, y, on_screen) = project(body.position, view_projection_matrix, width, height);
+        if on_screen {
+            // Radio aparente para poner la etiqueta justo encima del disco
+            let (ex, ey, _) = project(body.position + camera_right * body.radius, view_projection_matrix, width, height);
+            let screen_radius = ((ex - x).powi(2) + (ey - y).powi(2)).sqrt().min(height);
+            draw_label(framebuffer, x, y - screen_radius, &body.info.name, &format_distance(distance), color, is_target);
+        } else if !is_moon || is_target {
+            draw_edge_arrow(framebuffer, x, y, &body.info.name, &format_distance(distance), color, is_target);
+        }
+    }
+}
+
+fn draw_label(framebuffer: &mut Framebuffer, x: f32, top: f32, name: &str, distance: &str, color: u32, is_target: bool) {
+    let line_height = GLYPH_HEIGHT as i32 + 2;
+    let name_width = text_width(name, 1) as i32;
+    let distance_width = text_width(distance, 1) as i32;
+    let base_y = top as i32 - 6 - line_height * 2;
+
+    framebuffer.draw_text(x as i32 - name_width / 2, base_y, name, color, 1, 0.95);
+    framebuffer.draw_text(x as i32 - distance_width / 2, base_y + line_height, distance, color, 1, 0.7);
+
+    if is_target {
+        // Corchetes alrededor del objetivo
+        let (cx, cy) = (x as i32, top as i32);
+        let half = (name_width.max(distance_width) / 2 + 4).max(10);
+        let y0 = base_y - 3;
+        let y1 = cy.max(base_y + line_height * 2);
+        for (sx, dir) in [(cx - half, 1), (cx + half, -1)] {
+            framebuffer.draw_line_2d(sx, y0, sx, y1, color, 0.8);
+            framebuffer.draw_line_2d(sx, y0, sx + dir * 4, y0, color, 0.8);
+            framebuffer.draw_line_2d(sx, y1, sx + dir * 4, y1, color, 0.8);
+        }
+    }
+}
+
+fn draw_edge_arrow(framebuffer: &mut Framebuffer, x: f32, y: f32, name: &str, distance: &str, color: u32, is_target: bool) {
+    let (width, height) = (framebuffer.width as f32, framebuffer.height as f32);
+    let (cx, cy) = (width * 0.5, height * 0.5);
+    let (dx, dy) = (x - cx, y - cy);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1e-3 {
+        return;
+    }
+    let (ux, uy) = (dx / len, dy / len);
+
+    // Intersección del rayo desde el centro con el rectángulo interior
+    let half_w = cx - EDGE_MARGIN;
+    let half_h = cy - EDGE_MARGIN;
+    let t = (half_w / ux.abs().max(1e-6)).min(half_h / uy.abs().max(1e-6));
+    let (ax, ay) = (cx + ux * t, cy + uy * t);
+
+    let size = if is_target { ARROW_SIZE * 1.5 } else { ARROW_SIZE };
+    let alpha = if is_target { 1.0 } else { 0.6 };
+    let tip = (ax + ux * size, ay + uy * size);
+    let (px, py) = (-uy, ux);
+    let left = (ax + px * size * 0.6, ay + py * size * 0.6);
+    let right = (ax - px * size * 0.6, ay - py * size * 0.6);
+    framebuffer.fill_triangle_2d(tip, left, right, color, alpha);
+
+    // Texto hacia el interior de la pantalla
+    let line_height = GLYPH_HEIGHT as i32 + 2;
+    let text_x = ax - ux * 22.0;
+    let text_y = ay - uy * 22.0;
+    let text_w = text_width(name, 1).max(text_width(distance, 1)) as f32;
+    let left_x = (text_x - text_w * 0.5).clamp(4.0, width - text_w - 4.0) as i32;
+    let top_y = (text_y - line_height as f32).clamp(4.0, height - 2.0 * line_height as f32 - 4.0) as i32;
+    framebuffer.draw_text(left_x, top_y, name, color, 1, alpha);
+    framebuffer.draw_text(left_x, top_y + line_height, distance, color, 1, alpha * 0.8);
+}
@@ -0,0 +1,319 @@
+use minifb::{Key, KeyRepeat, Window};
+use std::fs;
+use crate::mouse::MouseFrame;
+
+// Acciones discretas (botones) que el juego entiende
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Turbo,
+    Warp,
+    TogglePause,
+    ToggleOrbits,
+    SpeedUp,
+    SpeedDown,
+    ReverseTime,
+    CycleCamera,
+    CycleObserverBody,
+    CycleTarget,
+    OrbitCamera,
+    ZoomIn,
+    ZoomOut,
+    ToggleMouseSteering,
+    ShowBindings,
+    ToggleHud,
+    CycleMinimapZoom,
+    Quit,
+}
+
+pub const ACTIONS: [Action; 18] = [
+    Action::Turbo,
+    Action::Warp,
+    Action::TogglePause,
+    Action::ToggleOrbits,
+    Action::SpeedUp,
+    Action::SpeedDown,
+    Action::ReverseTime,
+    Action::CycleCamera,
+    Action::CycleObserverBody,
+    Action::CycleTarget,
+    Action::OrbitCamera,
+    Action::ZoomIn,
+    Action::ZoomOut,
+    Action::ToggleMouseSteering,
+    Action::ShowBindings,
+    Action::ToggleHud,
+    Action::CycleMinimapZoom,
+    Action::Quit,
+];
+
+// Ejes: un grupo de teclas empuja hacia +1 y otro hacia -1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    Thrust, // Adelante / atrás
+    Strafe, // Derecha / izquierda
+    Pitch,  // Nariz arriba / abajo
+    Yaw,    // Izquierda / derecha
+    Roll,   // Alabeo
+}
+
+pub const AXES: [Axis; 5] = [Axis::Thrust, Axis::Strafe, Axis::Pitch, Axis::Yaw, Axis::Roll];
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Turbo => "Turbo",
+            Action::Warp => "Warp",
+            Action::TogglePause => "TogglePause",
+            Action::ToggleOrbits => "ToggleOrbits",
+            Action::SpeedUp => "SpeedUp",
+            Action::SpeedDown => "SpeedDown",
+            Action::ReverseTime => "ReverseTime",
+            Action::CycleCamera => "CycleCamera",
+            Action::CycleObserverBody => "CycleObserverBody",
+            Action::CycleTarget => "CycleTarget",
+            Action::OrbitCamera => "OrbitCamera",
+            Action::ZoomIn => "ZoomIn",
+            Action::ZoomOut => "ZoomOut",
+            Action::ToggleMouseSteering => "ToggleMouseSteering",
+            Action::ShowBindings => "ShowBindings",
+            Action::ToggleHud => "ToggleHud",
+            Action::CycleMinimapZoom => "CycleMinimapZoom",
+            Action::Quit => "Quit",
+        }
+    }
+
+    fn index(self) -> usize {
+        ACTIONS.iter().position(|&a| a == self).unwrap_or(0)
+    }
+}
+
+impl Axis {
+    pub fn name(self) -> &'static str {
+        match self {
+            Axis::Thrust => "Thrust",
+            Axis::Strafe => "Strafe",
+            Axis::Pitch => "Pitch",
+            Axis::Yaw => "Yaw",
+            Axis::Roll => "Roll",
+        }
+    }
+
+    fn index(self) -> usize {
+        AXES.iter().position(|&a| a == self).unwrap_or(0)
+    }
+}
+
+// Teclas que se pueden nombrar en el archivo de configuración
+const NAMED_KEYS: [Key; 85] = [
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4,
+    Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I,
+    Key::J, Key::K, Key::L, Key::M, Key::N, Key::O, Key::P, Key::Q, Key::R,
+    Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6,
+    Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12,
+    Key::Up, Key::Down, Key::Left, Key::Right,
+    Key::Space, Key::Enter, Key::Tab, Key::Escape, Key::Backspace,
+    Key::Insert, Key::Delete, Key::Home, Key::End, Key::PageUp, Key::PageDown,
+    Key::Equal, Key::Minus, Key::Comma, Key::Period, Key::Slash,
+    Key::LeftShift, Key::RightShift, Key::LeftCtrl, Key::RightCtrl,
+    Key::LeftAlt, Key::RightAlt,
+    Key::NumPad0, Key::NumPad1, Key::NumPad2, Key::NumPad3, Key::NumPad4,
+    Key::NumPad5, Key::NumPad6, Key::NumPad7, Key::NumPad8, Key::NumPad9,
+    Key::NumPadPlus,
+];
+
+fn key_from_name(name: &str) -> Option<Key> {
+    NAMED_KEYS
+        .iter()
+        .copied()
+        .find(|key| format!("{:?}", key).eq_ignore_ascii_case(name))
+}
+
+// Estado de la entrada en un frame, ya traducido a acciones y ejes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputFrame {
+    held: u32,
+    pressed: u32,
+    axes: [f32; 5],
+    pub mouse: MouseFrame,
+}
+
+impl InputFrame {
+    // Construir un frame a partir de sus partes (al leer una repetición)
+    pub fn from_raw(held: u32, pressed: u32, axes: [f32; 5], mouse: MouseFrame) -> Self {
+        InputFrame { held, pressed, axes, mouse }
+    }
+
+    // Partes del frame para guardarlo: (mantenidas, pulsadas, ejes)
+    pub fn raw(&self) -> (u32, u32, [f32; 5]) {
+        (self.held, self.pressed, self.axes)
+    }
+
+    // Acción mantenida este frame
+    pub fn held(&self, action: Action) -> bool {
+        self.held & (1 << action.index()) != 0
+    }
+
+    // Acción recién pulsada este frame (sin repetición)
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed & (1 << action.index()) != 0
+    }
+
+    pub fn axis(&self, axis: Axis) -> f32 {
+        self.axes[axis.index()]
+    }
+}
+
+pub struct AxisBinding {
+    pub positive: Vec<Key>,
+    pub negative: Vec<Key>,
+}
+
+pub struct InputBindings {
+    actions: Vec<Vec<Key>>,
+    axes: Vec<AxisBinding>,
+}
+
+impl InputBindings {
+    pub fn default_bindings() -> Self {
+        let mut bindings = InputBindings {
+            actions: vec![Vec::new(); ACTIONS.len()],
+            axes: AXES.iter().map(|_| AxisBinding { positive: Vec::new(), negative: Vec::new() }).collect(),
+        };
+
+        bindings.bind_axis(Axis::Thrust, vec![Key::W], vec![Key::S]);
+        bindings.bind_axis(Axis::Strafe, vec![Key::D], vec![Key::A]);
+        bindings.bind_axis(Axis::Pitch, vec![Key::Up], vec![Key::Down]);
+        bindings.bind_axis(Axis::Yaw, vec![Key::Left], vec![Key::Right]);
+        bindings.bind_axis(Axis::Roll, vec![Key::Q], vec![Key::E]);
+
+        bindings.bind(Action::Turbo, vec![Key::LeftShift]);
+        bindings.bind(Action::Warp, vec![Key::J]);
+        bindings.bind(Action::TogglePause, vec![Key::Space]);
+        bindings.bind(Action::ToggleOrbits, vec![Key::O]);
+        bindings.bind(Action::SpeedUp, vec![Key::Equal]);
+        bindings.bind(Action::SpeedDown, vec![Key::Minus]);
+        bindings.bind(Action::ReverseTime, vec![Key::R]);
+        bindings.bind(Action::CycleCamera, vec![Key::C]);
+        bindings.bind(Action::CycleObserverBody, vec![Key::L]);
+        bindings.bind(Action::CycleTarget, vec![Key::T]);
+        bindings.bind(Action::OrbitCamera, vec![Key::LeftCtrl, Key::RightCtrl]);
+        bindings.bind(Action::ZoomIn, vec![Key::PageUp]);
+        bindings.bind(Action::ZoomOut, vec![Key::PageDown]);
+        bindings.bind(Action::ToggleMouseSteering, vec![Key::M]);
+        bindings.bind(Action::ShowBindings, vec![Key::F1]);
+        bindings.bind(Action::ToggleHud, vec![Key::H]);
+        bindings.bind(Action::CycleMinimapZoom, vec![Key::N]);
+        bindings.bind(Action::Quit, vec![Key::Escape]);
+
+        bindings
+    }
+
+    // Carga las teclas desde un archivo. Las acciones que no aparecen
+    // conservan su tecla por defecto.
+    //
+    // Formato, una asignación por línea (# para comentarios):
+    //   TogglePause = Space P
+    //   axis Roll = Q / E
+    pub fn load(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(filename)?;
+        let mut bindings = Self::default_bindings();
+
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, keys) = line
+                .split_once('=')
+                .ok_or_else(|| format!("línea {}: falta '='", line_number + 1))?;
+            let name = name.trim();
+
+            if let Some(axis_name) = name.strip_prefix("axis ") {
+                let axis = AXES
+                    .iter()
+                    .copied()
+                    .find(|a| a.name().eq_ignore_ascii_case(axis_name.trim()))
+                    .ok_or_else(|| format!("línea {}: eje desconocido '{}'", line_number + 1, axis_name.trim()))?;
+                let (positive, negative) = keys
+                    .split_once('/')
+                    .ok_or_else(|| format!("línea {}: un eje necesita 'positivas / negativas'", line_number + 1))?;
+                let positive = parse_keys(positive, line_number)?;
+                let negative = parse_keys(negative, line_number)?;
+                bindings.bind_axis(axis, positive, negative);
+            } else {
+                let action = ACTIONS
+                    .iter()
+                    .copied()
+                    .find(|a| a.name().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("línea {}: acción desconocida '{}'", line_number + 1, name))?;
+                bindings.bind(action, parse_keys(keys, line_number)?);
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    pub fn bind(&mut self, action: Action, keys: Vec<Key>) {
+        self.actions[action.index()] = keys;
+    }
+
+    pub fn bind_axis(&mut self, axis: Axis, positive: Vec<Key>, negative: Vec<Key>) {
+        self.axes[axis.index()] = AxisBinding { positive, negative };
+    }
+
+    // Lee el teclado y lo traduce a un InputFrame
+    pub fn poll(&self, window: &Window) -> InputFrame {
+        let mut frame = InputFrame::default();
+
+        for (i, keys) in self.actions.iter().enumerate() {
+            if keys.iter().any(|&key| window.is_key_down(key)) {
+                frame.held |= 1 << i;
+            }
+            if keys.iter().any(|&key| window.is_key_pressed(key, KeyRepeat::No)) {
+                frame.pressed |= 1 << i;
+            }
+        }
+
+        for (i, binding) in self.axes.iter().enumerate() {
+            let positive = binding.positive.iter().any(|&key| window.is_key_down(key));
+            let negative = binding.negative.iter().any(|&key| window.is_key_down(key));
+            frame.axes[i] = match (positive, negative) {
+                (true, false) => 1.0,
+                (false, true) => -1.0,
+                _ => 0.0,
+            };
+        }
+
+        frame
+    }
+
+    // Descripción legible de todas las teclas asignadas
+    pub fn describe(&self) -> Vec<String> {
+        let key_list = |keys: &[Key]| {
+            if keys.is_empty() {
+                "-".to_string()
+            } else {
+                keys.iter().map(|key| format!("{:?}", key)).collect::<Vec<_>>().join(" ")
+            }
+        };
+
+        let mut lines = Vec::new();
+        for axis in AXES {
+            let binding = &self.axes[axis.index()];
+            lines.push(format!("{:<20} + {}  - {}", axis.name(), key_list(&binding.positive), key_list(&binding.negative)));
+        }
+        for action in ACTIONS {
+            lines.push(format!("{:<20} {}", action.name(), key_list(&self.actions[action.index()])));
+        }
+        lines
+    }
+}
+
+fn parse_keys(text: &str, line_number: usize) -> Result<Vec<Key>, String> {
+    text.split_whitespace()
+        .map(|name| key_from_name(name).ok_or_else(|| format!("línea {}: tecla desconocida '{}'", line_number + 1, name)))
+        .collect()
+}
@@ -0,0 +1,96 @@
+// Órbitas keplerianas (elípticas e inclinadas) para los cuerpos menores.
+// Los planetas siguen en órbitas circulares sobre el plano XZ; aquí ese
+// plano hace de eclíptica y +Y es el norte.
+//
+// La elipse se calcula en unidades astronómicas reales y cada punto se lleva
+// a la escena con la escala del sistema (SceneScale). Como las distancias de la escena están
+// comprimidas, así un perihelio queda entre los mismos planetas que en el
+// sistema real (el de Plutón, justo dentro de la órbita de Neptuno).
+
+use std::sync::Arc;
+use nalgebra_glm::Vec3;
+use crate::catalog::SceneScale;
+
+pub struct KeplerOrbit {
+    pub semi_major_axis: f32,         // Unidades astronómicas
+    pub eccentricity: f32,            // 0 circular, cerca de 1 muy alargada
+    pub inclination: f32,             // Radianes respecto al plano XZ
+    pub ascending_node: f32,          // Longitud del nodo ascendente (radianes)
+    pub periapsis_argument: f32,      // Argumento del periapsis (radianes)
+    pub mean_anomaly_at_epoch: f32,   // Posición en la órbita cuando el tiempo es 0
+    pub mean_motion: f32,             // Radianes de anomalía media por unidad de tiempo
+    pub scene_scale: Arc<SceneScale>, // La del sistema al crear la órbita
+}
+
+impl KeplerOrbit {
+    // Posición en la escena en el tiempo dado, relativa al Sol
+    pub fn position(&self, time: f32) -> Vec3 {
+        self.position_at(self.mean_anomaly_at_epoch + self.mean_motion * time)
+    }
+
+    // Distancia real al Sol en unidades astronómicas
+    pub fn distance_au(&self, time: f32) -> f32 {
+        let mean_anomaly = self.mean_anomaly_at_epoch + self.mean_motion * time;
+        let ecc_anomaly = eccentric_anomaly(mean_anomaly, self.eccentricity);
+        self.semi_major_axis * (1.0 - self.eccentricity * ecc_anomaly.cos())
+    }
+
+    // Puntos de la elipse completa, para dibujar la órbita. Se reparten por
+    // anomalía excéntrica para que no falten puntos cerca del perihelio.
+    pub fn path(&self, segments: usize) -> Vec<Vec3> {
+        (0..segments)
+            .map(|i| {
+                let ecc_anomaly = i as f32 / segments as f32 * std::f32::consts::TAU;
+                self.position_at(ecc_anomaly - self.eccentricity * ecc_anomaly.sin())
+            })
+            .collect()
+    }
+
+    fn position_at(&self, mean_anomaly: f32) -> Vec3 {
+        let e = self.eccentricity;
+        let ecc_anomaly = eccentric_anomaly(mean_anomaly, e);
+
+        // Coordenadas en el plano de la órbita, con el periapsis en +x
+        let x = self.semi_major_axis * (ecc_anomaly.cos() - e);
+        let y = self.semi_major_axis * (1.0 - e * e).sqrt() * ecc_anomaly.sin();
+
+        let (sin_w, cos_w) = self.periapsis_argument.sin_cos();
+        let (sin_n, cos_n) = self.ascending_node.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+
+        // Ejes del plano de la órbita expresados en la eclíptica (x, y, norte)
+        let p = Vec3::new(
+            cos_w * cos_n - sin_w * cos_i * sin_n,
+            cos_w * sin_n + sin_w * cos_i * cos_n,
+            sin_w * sin_i,
+        );
+        let q = Vec3::new(
+            -sin_w * cos_n - cos_w * cos_i * sin_n,
+            -sin_w * sin_n + cos_w * cos_i * cos_n,
+            cos_w * sin_i,
+        );
+        let ecliptic = p * x + q * y;
+
+        // La eclíptica es el plano XZ de la escena: el norte va en +Y
+        let direction = Vec3::new(ecliptic.x, ecliptic.z, ecliptic.y);
+        let distance_au = direction.magnitude();
+        direction * (self.scene_scale.radius_for_au(distance_au) / distance_au)
+    }
+}
+
+// Resuelve la ecuación de Kepler M = E - e·sin(E) con Newton-Raphson
+fn eccentric_anomaly(mean_anomaly: f32, eccentricity: f32) -> f32 {
+    let m = mean_anomaly.rem_euclid(std::f32::consts::TAU);
+    // Para órbitas muy alargadas conviene empezar en PI
+    let mut e_anomaly = if eccentricity > 0.8 { std::f32::consts::PI } else { m };
+    for _ in 0..12 {
+        let f = e_anomaly - eccentricity * e_anomaly.sin() - m;
+        let derivative = 1.0 - eccentricity * e_anomaly.cos();
+        let step = f / derivative;
+        e_anomaly -= step;
+        if step.abs() < 1e-6 {
+            break;
+        }
+    }
+    e_anomaly
+}
@@ -1,9 +1,26 @@
-use nalgebra_glm::{Vec3, Mat4, Vec4, look_at, perspective};
-use minifb::{Key, Window, WindowOptions, KeyRepeat};
+use nalgebra_glm::{Vec3, Mat3, Mat4, Vec4};
+use minifb::{Window, WindowOptions};
 use std::time::{Duration, Instant};
 use std::f32::consts::PI;
+use std::sync::Arc;
 
 mod framebuffer;
+mod font;
+mod hud;
+mod indicators;
+mod minimap;
+mod catalog;
+mod kepler;
+mod belts;
+mod comets;
+mod particles;
+mod generator;
+mod bake;
+mod bump;
+mod export;
+mod png;
+mod scene_config;
+mod rng;
 mod triangle;
 mod vertex;
 mod color;
@@ -12,19 +29,48 @@ mod shaders;
 mod noise;
 mod planet_shaders;
 mod obj;
+mod warp;
+mod camera;
+mod camera_rig;
+mod mouse;
+mod input;
+mod clock;
+mod simulation;
+mod replay;
 
 use framebuffer::Framebuffer;
 use vertex::Vertex;
 use triangle::triangle;
-use shaders::vertex_shader;
+use shaders::{normal_matrix, vertex_shader, world_to_screen, ScreenProjector};
 use obj::Obj;
-
-pub struct Uniforms {
+use camera_rig::CameraView;
+use mouse::MouseCapture;
+use input::{Action, InputBindings};
+use simulation::{Simulation, TICK_DT};
+use hud::Hud;
+use catalog::{BodyInfo, BodyKind};
+use belts::{Belt, BeltZone, RockMesh};
+use replay::{Recorder, Replay};
+use planet_shaders::{cloud_shell_angle, family_style, SurfaceStyle, CLOUD_SHELL_SCALE};
+use scene_config::FileWatcher;
+use bake::{BakedSurface, BakedSurfaces};
+
+pub struct Uniforms<'a> {
     pub model_matrix: Mat4,
+    // Lleva las normales del modelo al mundo (shaders::normal_matrix)
+    pub normal_matrix: Mat3,
     pub view_projection_matrix: Mat4,
     pub time: f32,
     pub current_shader: u32,
-    pub is_moon: bool,
+    // Parámetros del shader de familia; si hay, tienen prioridad sobre `current_shader`
+    pub surface: Option<&'a SurfaceStyle>,
+    // Capa estática de `surface` ya horneada (--bake)
+    pub baked: Option<&'a BakedSurface>,
+    // Posiciones en el mundo del Sol (la luz) y de la cámara
+    pub light_position: Vec3,
+    pub camera_position: Vec3,
+    // Dibujar la capa de nubes de `surface` en lugar de la superficie
+    pub cloud_shell: bool,
     pub screen_width: f32,
     pub screen_height: f32,
 }
@@ -35,35 +81,38 @@ struct SpaceshipCamera {
     rotation: Vec3, // pitch, yaw, roll
     velocity: Vec3,
     fov: f32,
-    aspect_ratio: f32,
+    base_fov: f32,
+
+    // Cámara de persecución con resorte críticamente amortiguado
+    chase_position: Vec3,
+    chase_velocity: Vec3,
+    chase_roll: f32,
+    chase_stiffness: f32,  // Rigidez del resorte (más alto = sigue más pegada)
+    chase_look_ahead: f32, // Segundos de anticipación a lo largo de la velocidad
+    chase_max_lag: f32,    // Distancia máxima que la cámara puede quedarse atrás
+    chase_distance: f32,   // Distancia detrás de la nave (rueda del ratón)
+    chase_orbit: Vec3,     // Giro extra alrededor de la nave (pitch, yaw, -) con clic derecho
 }
 
 impl SpaceshipCamera {
-    fn new(width: f32, height: f32) -> Self {
-        SpaceshipCamera {
+    fn new() -> Self {
+        let mut camera = SpaceshipCamera {
             position: Vec3::new(0.0, 200.0, 1500.0), // Comenzar lejos para ver todo el sistema
             rotation: Vec3::new(0.0, 0.0, 0.0),
             velocity: Vec3::new(0.0, 0.0, 0.0),
             fov: 60.0_f32.to_radians(),
-            aspect_ratio: width / height,
-        }
-    }
-
-    fn get_view_matrix(&self) -> Mat4 {
-        // La cámara está detrás de la nave, mirando hacia la nave
-        let camera_position = self.get_camera_position();
-        let camera_target = self.get_camera_target();
-        let up = Vec3::new(0.0, 1.0, 0.0);
-        
-        look_at(&camera_position, &camera_target, &up)
-    }
-
-    fn get_projection_matrix(&self) -> Mat4 {
-        perspective(self.fov, self.aspect_ratio, 1.0, 10000.0)
-    }
-
-    fn get_view_projection_matrix(&self) -> Mat4 {
-        self.get_projection_matrix() * self.get_view_matrix()
+            base_fov: 60.0_f32.to_radians(),
+            chase_position: Vec3::new(0.0, 0.0, 0.0),
+            chase_velocity: Vec3::new(0.0, 0.0, 0.0),
+            chase_roll: 0.0,
+            chase_stiffness: 40.0,
+            chase_look_ahead: 0.25,
+            chase_max_lag: 80.0,
+            chase_distance: 100.0,
+            chase_orbit: Vec3::new(0.0, 0.0, 0.0),
+        };
+        camera.chase_position = camera.get_chase_anchor();
+        camera
     }
 
     fn get_forward_vector(&self) -> Vec3 {
@@ -88,6 +137,36 @@ impl SpaceshipCamera {
         
         // Damping para que la nave se detenga gradualmente
         self.velocity *= 0.95;
+
+        self.update_chase(dt);
+    }
+
+    fn update_chase(&mut self, dt: f32) {
+        // Resorte críticamente amortiguado hacia el punto detrás de la nave.
+        // El amortiguamiento es relativo a la velocidad de la nave, así que a
+        // velocidad constante no hay retraso: solo se nota al acelerar o girar.
+        let anchor = self.get_chase_anchor();
+        let stiffness = self.chase_stiffness;
+        let damping = 2.0 * stiffness.sqrt();
+        let acceleration = (anchor - self.chase_position) * stiffness
+            + (self.velocity - self.chase_velocity) * damping;
+        self.chase_velocity += acceleration * dt;
+        self.chase_position += self.chase_velocity * dt;
+
+        // No dejar que la cámara se quede demasiado lejos (p.ej. al salir de warp)
+        let lag = self.chase_position - anchor;
+        if lag.magnitude() > self.chase_max_lag {
+            self.chase_position = anchor + lag.normalize() * self.chase_max_lag;
+            self.chase_velocity = self.velocity;
+        }
+
+        // El roll de la cámara sigue al de la nave con retraso y a la mitad
+        self.chase_roll += (self.rotation.z * 0.5 - self.chase_roll) * (dt * 3.0).min(1.0);
+
+        // El FOV se abre con la velocidad para dar sensación de aceleración
+        let speed = self.velocity.magnitude();
+        let target_fov = self.base_fov + 25.0_f32.to_radians() * (1.0 - (-speed / 400.0).exp());
+        self.fov += (target_fov - self.fov) * (dt * 4.0).min(1.0);
     }
 
     fn get_spaceship_transform(&self) -> Mat4 {
@@ -95,36 +174,150 @@ impl SpaceshipCamera {
         create_model_matrix(self.position, 10.0, self.rotation)
     }
     
-    fn get_camera_position(&self) -> Vec3 {
-        // La cámara está detrás de la nave para vista de tercera persona
+    // Punto ideal de la cámara: detrás y un poco arriba de la nave
+    fn get_chase_anchor(&self) -> Vec3 {
         let backward = -self.get_forward_vector(); // Vector hacia atrás
         let up = Vec3::new(0.0, 1.0, 0.0);
-        
-        // Posicionar cámara atrás y un poco arriba de la nave
-        self.position + backward * 100.0 + up * 40.0
+        let offset = backward * self.chase_distance + up * (self.chase_distance * 0.4);
+
+        // Girar el desplazamiento alrededor de la nave sin cambiar su rumbo
+        let (sin_yaw, cos_yaw) = self.chase_orbit.y.sin_cos();
+        let offset = Vec3::new(
+            offset.x * cos_yaw + offset.z * sin_yaw,
+            offset.y,
+            -offset.x * sin_yaw + offset.z * cos_yaw,
+        );
+        let axis = offset.cross(&up);
+        let offset = if axis.magnitude() > 0.001 {
+            let axis = axis.normalize();
+            let (sin_pitch, cos_pitch) = self.chase_orbit.x.sin_cos();
+            offset * cos_pitch + axis.cross(&offset) * sin_pitch
+        } else {
+            offset
+        };
+
+        self.position + offset
+    }
+
+    fn get_camera_position(&self) -> Vec3 {
+        // La cámara de tercera persona sigue al ancla con el resorte
+        self.chase_position
     }
     
     fn get_camera_target(&self) -> Vec3 {
-        // La cámara mira hacia la nave
-        self.position
+        // La cámara mira un poco por delante de la nave, según su velocidad
+        let look_ahead = self.velocity * self.chase_look_ahead;
+        let max_look_ahead = 60.0;
+        if look_ahead.magnitude() > max_look_ahead {
+            self.position + look_ahead.normalize() * max_look_ahead
+        } else {
+            self.position + look_ahead
+        }
     }
 
-    // Nueva función para obtener la dirección de vista para el skybox
-    fn get_view_direction(&self) -> Vec3 {
-        let camera_pos = self.get_camera_position();
-        let camera_target = self.get_camera_target();
-        (camera_target - camera_pos).normalize()
+    fn get_camera_up(&self) -> Vec3 {
+        // Vertical del mundo inclinada por el roll retrasado
+        let view_dir = (self.get_camera_target() - self.get_camera_position()).normalize();
+        let world_up = Vec3::new(0.0, 1.0, 0.0);
+        let right = view_dir.cross(&world_up).normalize();
+        let (sin_roll, cos_roll) = self.chase_roll.sin_cos();
+        (world_up * cos_roll - right * sin_roll).normalize()
     }
 }
 
 // Estructura para definir cada planeta
 struct Planet {
+    info: Arc<BodyInfo>,
     shader_id: u32,
     scale: f32,
     orbit_radius: f32,
     orbit_speed: f32,
-    rotation_speed: f32,
-    has_moon: bool,
+    moons: Vec<Arc<BodyInfo>>,
+}
+
+impl Planet {
+    // Parámetros de la escena a partir del catálogo
+    fn from_info(info: Arc<BodyInfo>) -> Self {
+        Planet {
+            shader_id: info.shader_id,
+            scale: info.planet_scale(),
+            orbit_radius: info.orbit_radius,
+            orbit_speed: info.orbit_speed(),
+            moons: catalog::moons_of(&info.name).collect(),
+            info,
+        }
+    }
+
+    fn orbit_angle(&self, elapsed: f32) -> f32 {
+        elapsed * self.orbit_speed
+    }
+
+    // Posición del planeta en su órbita para el tiempo dado
+    fn position(&self, elapsed: f32) -> Vec3 {
+        let orbit_angle = self.orbit_angle(elapsed);
+        Vec3::new(
+            self.orbit_radius * orbit_angle.cos(),
+            0.0,
+            self.orbit_radius * orbit_angle.sin(),
+        )
+    }
+
+    // Posición de una de sus lunas
+    fn moon_position(&self, moon: &BodyInfo, elapsed: f32) -> Vec3 {
+        let moon_orbit_radius = self.scale * moon.orbit_radius;
+        let moon_angle = elapsed * moon.orbit_speed();
+        self.position(elapsed) + Vec3::new(
+            moon_orbit_radius * moon_angle.cos(),
+            0.0,
+            moon_orbit_radius * moon_angle.sin(),
+        )
+    }
+}
+
+const SCREEN_WIDTH: usize = 1200;
+const SCREEN_HEIGHT: usize = 800;
+const CONTROLS_FILE: &str = "config/controls.cfg";
+// Por debajo de este tamaño en pantalla (pixeles de radio) una roca se dibuja como un punto
+const ROCK_POINT_PIXELS: f32 = 1.5;
+
+// Cuerpo del sistema en el frame actual (Sol, planetas y lunas)
+struct BodyState {
+    info: Arc<BodyInfo>,
+    position: Vec3,
+    radius: f32,
+}
+
+fn collect_body_states(planets: &[Planet], elapsed: f32) -> Vec<BodyState> {
+    let star = catalog::star();
+    let mut bodies = vec![BodyState { radius: star.star_scale(), info: star, position: Vec3::new(0.0, 0.0, 0.0) }];
+    for planet in planets {
+        bodies.push(BodyState { info: Arc::clone(&planet.info), position: planet.position(elapsed), radius: planet.scale });
+        for moon in &planet.moons {
+            bodies.push(BodyState {
+                info: Arc::clone(moon),
+                position: planet.moon_position(moon, elapsed),
+                radius: moon.moon_scale(&planet.info, planet.scale),
+            });
+        }
+    }
+    if let (Some(pluto), Some(orbit)) = (catalog::find("Plutón"), belts::pluto_orbit()) {
+        bodies.push(BodyState { radius: pluto.planet_scale(), info: pluto, position: orbit.position(elapsed) });
+    }
+    for comet in catalog::comets() {
+        if let Some(orbit) = comets::comet_orbit(&comet) {
+            bodies.push(BodyState { info: comet, position: orbit.position(elapsed), radius: comets::NUCLEUS_SCALE });
+        }
+    }
+    bodies
+}
+
+// Rotación de un cuerpo para create_model_matrix: primero el giro sobre su eje
+// (Y del modelo) y después la inclinación del eje hacia -X, una dirección fija
+// en el espacio igual para todos. Como los shaders trabajan en coordenadas del
+// modelo, los casquetes polares siguen al eje inclinado.
+fn body_rotation(info: &BodyInfo, elapsed: f32, orbit_angle: f32) -> Vec3 {
+    // Un giro negativo sobre Y avanza en el mismo sentido que las órbitas
+    Vec3::new(0.0, -info.spin_angle(elapsed, orbit_angle), info.axial_tilt())
 }
 
 fn create_model_matrix(translation: Vec3, scale: f32, rotation: Vec3) -> Mat4 {
@@ -172,6 +365,17 @@ fn create_model_matrix(translation: Vec3, scale: f32, rotation: Vec3) -> Mat4 {
     translation_matrix * rotation_matrix * scale_matrix
 }
 
+// Deforma la esfera en un elipsoide con los semiejes dados (en el espacio del
+// modelo, así la forma gira con el cuerpo)
+fn create_shape_matrix(axes: [f32; 3]) -> Mat4 {
+    Mat4::new(
+        axes[0], 0.0,     0.0,     0.0,
+        0.0,     axes[1], 0.0,     0.0,
+        0.0,     0.0,     axes[2], 0.0,
+        0.0,     0.0,     0.0,     1.0,
+    )
+}
+
 fn create_sphere(radius: f32, segments: u32) -> (Vec<Vertex>, Vec<u32>) {
     let mut vertices = Vec::new();
     let mut indices = Vec::new();
@@ -244,14 +448,32 @@ fn direction_to_pixel(dir: Vec3, width: f32, height: f32) -> (i32, i32) {
     (x.clamp(0, width as i32 - 1), y.clamp(0, height as i32 - 1))
 }
 
-// Renderizar skybox con estrellas
-fn render_starfield_skybox(framebuffer: &mut Framebuffer, camera: &SpaceshipCamera) {
+// Renderizar skybox con estrellas.
+// Con `stretch` > 0 (warp) cada estrella se alarga en dirección opuesta a `warp_dir`.
+fn render_starfield_skybox(framebuffer: &mut Framebuffer, view: &CameraView, warp_dir: Vec3, stretch: f32) {
     let width = framebuffer.width as f32;
     let height = framebuffer.height as f32;
-    let fov_half = camera.fov * 0.5;
+    let fov_half = view.fov * 0.5;
     
     // Generar estrellas usando hash determinístico
     let star_count = 800; // Cantidad de estrellas
+
+    // Obtener dirección de vista de la cámara
+    let view_dir = view.direction();
+    let right = view_dir.cross(&view.up).normalize();
+    let camera_up = right.cross(&view_dir).normalize();
+
+    // Proyectar una dirección del cielo a pantalla (None si está detrás de la cámara)
+    let project = |dir: Vec3| -> Option<(f32, f32)> {
+        let in_view = Vec3::new(dir.dot(&right), dir.dot(&camera_up), dir.dot(&view_dir));
+        if in_view.z > 0.1 {
+            let screen_x = (in_view.x / in_view.z / fov_half.tan() * 0.5 + 0.5) * width;
+            let screen_y = (-in_view.y / in_view.z / fov_half.tan() * 0.5 + 0.5) * height;
+            Some((screen_x, screen_y))
+        } else {
+            None
+        }
+    };
     
     for i in 0..star_count {
         // Generar posición de estrella usando hash
@@ -272,25 +494,8 @@ fn render_starfield_skybox(framebuffer: &mut Framebuffer, camera: &SpaceshipCame
             phi.sin() * theta.sin()
         );
         
-        // Obtener dirección de vista de la cámara
-        let view_dir = camera.get_view_direction();
-        let up = Vec3::new(0.0, 1.0, 0.0);
-        let right = view_dir.cross(&up).normalize();
-        let camera_up = right.cross(&view_dir).normalize();
-        
-        // Transformar estrella al espacio de pantalla
-        let star_in_view = Vec3::new(
-            star_dir.dot(&right),
-            star_dir.dot(&camera_up),
-            star_dir.dot(&view_dir)
-        );
-        
         // Solo dibujar si la estrella está enfrente de la cámara
-        if star_in_view.z > 0.1 {
-            // Proyección a pantalla
-            let screen_x = (star_in_view.x / star_in_view.z / fov_half.tan() * 0.5 + 0.5) * width;
-            let screen_y = (-star_in_view.y / star_in_view.z / fov_half.tan() * 0.5 + 0.5) * height;
-            
+        if let Some((screen_x, screen_y)) = project(star_dir) {
             let pixel_x = screen_x as i32;
             let pixel_y = screen_y as i32;
             
@@ -331,34 +536,28 @@ fn render_starfield_skybox(framebuffer: &mut Framebuffer, camera: &SpaceshipCame
                         }
                     }
                 }
+
+                // === ESTELA DE WARP ===
+                // La cola apunta hacia donde la estrella "viene", opuesta a la velocidad
+                if stretch > 0.01 {
+                    let tail_dir = (star_dir - warp_dir * stretch * 0.6).normalize();
+                    if let Some((tail_x, tail_y)) = project(tail_dir) {
+                        let steps = ((tail_x - screen_x).abs().max((tail_y - screen_y).abs()) as i32).min(400);
+                        for step in 1..=steps {
+                            let t = step as f32 / steps as f32;
+                            let x = (screen_x + (tail_x - screen_x) * t) as i32;
+                            let y = (screen_y + (tail_y - screen_y) * t) as i32;
+                            if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
+                                framebuffer.point(x as usize, y as usize, 1.0);
+                            }
+                        }
+                    }
+                }
             }
         }
     }
 }
 
-fn world_to_screen(world_pos: Vec3, view_projection_matrix: Mat4, screen_width: f32, screen_height: f32) -> Option<Vec3> {
-    let world_position = Vec4::new(world_pos.x, world_pos.y, world_pos.z, 1.0);
-    let projected = view_projection_matrix * world_position;
-    
-    let w = projected.w;
-    if w <= 0.0 {
-        return None;
-    }
-    
-    let ndc_x = projected.x / w;
-    let ndc_y = projected.y / w;
-    let ndc_z = projected.z / w;
-    
-    if ndc_x < -1.0 || ndc_x > 1.0 || ndc_y < -1.0 || ndc_y > 1.0 {
-        return None;
-    }
-    
-    let screen_x = (ndc_x + 1.0) * 0.5 * screen_width;
-    let screen_y = (1.0 - ndc_y) * 0.5 * screen_height;
-    
-    Some(Vec3::new(screen_x, screen_y, ndc_z))
-}
-
 fn draw_line(framebuffer: &mut Framebuffer, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
     let mut x0 = x0;
     let mut y0 = y0;
@@ -420,7 +619,22 @@ fn render_orbit_line(framebuffer: &mut Framebuffer, center: Vec3, radius: f32, v
     }
 }
 
-fn render_debug_spaceship_cube(framebuffer: &mut Framebuffer, camera: &SpaceshipCamera, view_projection_matrix: Mat4, screen_width: f32, screen_height: f32) {
+// Órbita cerrada a partir de sus puntos (órbitas elípticas)
+fn render_orbit_path(framebuffer: &mut Framebuffer, points: &[Vec3], view_projection_matrix: Mat4, screen_width: f32, screen_height: f32) {
+    let orbit_color = 0x333333;
+
+    for (i, point1) in points.iter().enumerate() {
+        let point2 = points[(i + 1) % points.len()];
+        let screen_point1 = world_to_screen(*point1, view_projection_matrix, screen_width, screen_height);
+        let screen_point2 = world_to_screen(point2, view_projection_matrix, screen_width, screen_height);
+
+        if let (Some(sp1), Some(sp2)) = (screen_point1, screen_point2) {
+            draw_line(framebuffer, sp1.x as i32, sp1.y as i32, sp2.x as i32, sp2.y as i32, orbit_color);
+        }
+    }
+}
+
+fn render_debug_spaceship_cube(framebuffer: &mut Framebuffer, camera: &SpaceshipCamera, view_projection_matrix: Mat4, light_position: Vec3, camera_position: Vec3, screen_width: f32, screen_height: f32) {
     // Crear un cubo simple como nave de depuración
     let cube_vertices = vec![
         // Frente
@@ -447,10 +661,15 @@ fn render_debug_spaceship_cube(framebuffer: &mut Framebuffer, camera: &Spaceship
     let cube_model_matrix = camera.get_spaceship_transform();
     let cube_uniforms = Uniforms {
         model_matrix: cube_model_matrix,
+        normal_matrix: normal_matrix(&cube_model_matrix),
         view_projection_matrix,
         time: 0.0,
         current_shader: 6, // Shader de nave
-        is_moon: false,
+        surface: None,
+        baked: None,
+        light_position,
+        camera_position,
+        cloud_shell: false,
         screen_width,
         screen_height,
     };
@@ -459,6 +678,14 @@ fn render_debug_spaceship_cube(framebuffer: &mut Framebuffer, camera: &Spaceship
 }
 
 fn render_object(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertices: &[Vertex], indices: &[u32]) {
+    // Sin parámetros propios, los ajustes de fábrica de su familia si la
+    // tiene. Se resuelven una vez por objeto: algunos copian sus listas.
+    let family = match uniforms.surface {
+        Some(_) => None,
+        None => family_style(uniforms.current_shader),
+    };
+    let uniforms = &Uniforms { surface: uniforms.surface.or(family.as_ref()), ..*uniforms };
+
     let mut transformed_vertices = Vec::with_capacity(vertices.len());
     for vertex in vertices {
         let transformed = vertex_shader(vertex, uniforms);
@@ -483,8 +710,12 @@ fn render_object(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertices: &
                     let y = fragment.position.y as usize;
                     if x < framebuffer.width && y < framebuffer.height {
                         let color = fragment.color.to_hex();
-                        framebuffer.set_current_color(color);
-                        framebuffer.point(x, y, fragment.depth);
+                        if fragment.alpha < 1.0 {
+                            framebuffer.blend_point(x, y, fragment.depth, color, fragment.alpha);
+                        } else {
+                            framebuffer.set_current_color(color);
+                            framebuffer.point(x, y, fragment.depth);
+                        }
                     }
                 }
             }
@@ -492,10 +723,225 @@ fn render_object(framebuffer: &mut Framebuffer, uniforms: &Uniforms, vertices: &
     }
 }
 
+// Capa de nubes de un cuerpo ya dibujado: la misma esfera un poco más grande
+// y girada respecto a la superficie. Se mezcla con lo de debajo sin escribir
+// en el Z-buffer.
+fn render_cloud_shell(framebuffer: &mut Framebuffer, body_uniforms: &Uniforms, vertices: &[Vertex], indices: &[u32]) {
+    // Sin parámetros propios, los de fábrica de su familia, como en la superficie
+    let family = match body_uniforms.surface {
+        Some(_) => None,
+        None => family_style(body_uniforms.current_shader),
+    };
+    let Some(surface) = body_uniforms.surface.or(family.as_ref()).filter(|style| style.has_cloud_shell()) else {
+        return;
+    };
+    let shell_rotation = Vec3::new(0.0, cloud_shell_angle(body_uniforms.time), 0.0);
+    let model_matrix = body_uniforms.model_matrix * create_model_matrix(Vec3::zeros(), CLOUD_SHELL_SCALE, shell_rotation);
+    let shell_uniforms = Uniforms {
+        model_matrix,
+        normal_matrix: normal_matrix(&model_matrix),
+        surface: Some(surface),
+        baked: None,
+        cloud_shell: true,
+        ..*body_uniforms
+    };
+    render_object(framebuffer, &shell_uniforms, vertices, indices);
+}
+
+// Dibuja muchas copias de unas pocas mallas, cada una con su propia matriz de
+// modelo. Las que en pantalla quedan más pequeñas que ROCK_POINT_PIXELS se
+// reducen a un punto con prueba de profundidad.
+fn render_object_instanced(framebuffer: &mut Framebuffer, uniforms: &Uniforms, meshes: &[RockMesh], belt: &Belt) {
+    let view_projection_matrix = uniforms.view_projection_matrix;
+    // Todas las rocas del cinturón comparten los ajustes de su familia
+    let family = family_style(belt.shader_id);
+    let to_clip = |p: Vec3| view_projection_matrix * Vec4::new(p.x, p.y, p.z, 1.0);
+
+    for instance in belt.instances(uniforms.time) {
+        let center = to_clip(instance.position);
+        if center.w <= 0.0 {
+            continue;
+        }
+        let (ndc_x, ndc_y) = (center.x / center.w, center.y / center.w);
+
+        // Radio aparente: el mayor desplazamiento en pantalla de los tres ejes
+        let mut radius_ndc: f32 = 0.0;
+        for axis in [Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)] {
+            let edge = to_clip(instance.position + axis * instance.scale);
+            if edge.w > 0.0 {
+                let (dx, dy) = (edge.x / edge.w - ndc_x, edge.y / edge.w - ndc_y);
+                radius_ndc = radius_ndc.max((dx * dx + dy * dy).sqrt());
+            }
+        }
+        let radius_pixels = radius_ndc * 0.5 * uniforms.screen_height;
+
+        if radius_pixels < ROCK_POINT_PIXELS {
+            if ndc_x.abs() > 1.0 || ndc_y.abs() > 1.0 {
+                continue;
+            }
+            let x = ((ndc_x + 1.0) * 0.5 * uniforms.screen_width) as usize;
+            let y = ((1.0 - ndc_y) * 0.5 * uniforms.screen_height) as usize;
+            let depth = (center.z / center.w + 1.0) * 0.5;
+            framebuffer.set_current_color(belt.point_color);
+            framebuffer.point(x, y, depth);
+            continue;
+        }
+
+        let mesh = &meshes[instance.mesh];
+        let model_matrix = create_model_matrix(instance.position, instance.scale, instance.rotation);
+        let instance_uniforms = Uniforms {
+            model_matrix,
+            normal_matrix: normal_matrix(&model_matrix),
+            view_projection_matrix,
+            time: uniforms.time,
+            current_shader: belt.shader_id,
+            surface: family.as_ref(),
+            baked: None,
+            light_position: uniforms.light_position,
+            camera_position: uniforms.camera_position,
+            cloud_shell: false,
+            screen_width: uniforms.screen_width,
+            screen_height: uniforms.screen_height,
+        };
+        render_object(framebuffer, &instance_uniforms, &mesh.vertices, &mesh.indices);
+    }
+}
+
+// Planetas de la escena, en el orden del catálogo
+fn create_planets() -> Vec<Planet> {
+    catalog::planets().map(Planet::from_info).collect()
+}
+
+// Opciones de línea de comandos
+struct Options {
+    record: Option<String>,   // --record <archivo>: grabar la entrada
+    replay: Option<String>,   // --replay <archivo>: reproducir una grabación
+    headless: bool,           // --headless: reproducir sin ventana
+    seed: Option<u64>,        // --seed <número>: explorar un sistema generado
+    bake: Option<usize>,      // --bake <resolución>: hornear las superficies estáticas
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options { record: None, replay: None, headless: false, seed: None, bake: None };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => options.record = Some(args.next().ok_or("--record necesita un archivo")?),
+            "--replay" => options.replay = Some(args.next().ok_or("--replay necesita un archivo")?),
+            "--headless" => options.headless = true,
+            "--seed" => {
+                let seed = args.next().ok_or("--seed necesita un número")?;
+                options.seed = Some(seed.parse().map_err(|_| format!("semilla no válida '{}'", seed))?);
+            }
+            "--bake" => {
+                let resolution = args.next().ok_or("--bake necesita una resolución")?;
+                let width = resolution
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|width| (bake::MIN_RESOLUTION..=bake::MAX_RESOLUTION).contains(width) && width % 2 == 0)
+                    .ok_or_else(|| format!("resolución no válida '{}' (un número par de {} a {})", resolution, bake::MIN_RESOLUTION, bake::MAX_RESOLUTION))?;
+                options.bake = Some(width);
+            }
+            other => return Err(format!("argumento desconocido '{}'", other)),
+        }
+    }
+    if options.headless && options.replay.is_none() {
+        return Err("--headless necesita --replay <archivo>".to_string());
+    }
+    Ok(options)
+}
+
+// Instala el sistema generado con la semilla y devuelve dónde van sus cinturones
+fn install_generated_system(seed: u64) -> Vec<BeltZone> {
+    let system = generator::generate(seed);
+    let planet_count = system.bodies.iter().filter(|info| info.kind == BodyKind::Planet).count();
+    println!("Sistema generado con la semilla {}: {} con {} planetas", seed, system.bodies[0].name, planet_count);
+    catalog::install(system.bodies);
+    system.belts
+}
+
+// Aplica los archivos de escena al Sistema Solar. Si tienen errores el
+// catálogo se queda como estaba y se devuelve el mensaje.
+fn install_scene_config() -> Result<(), String> {
+    let bodies = scene_config::load()?;
+    catalog::install(bodies);
+    Ok(())
+}
+
+// Reproduce una grabación sin ventana e imprime el estado final
+fn run_headless(replay: Replay) {
+    match replay.initial.system_seed {
+        Some(seed) => {
+            install_generated_system(seed);
+        }
+        None => {
+            if let Err(e) = install_scene_config() {
+                println!("No se pudo cargar la escena: {}", e);
+            }
+        }
+    }
+    let mut sim = Simulation::new(create_planets());
+    sim.system_seed = replay.initial.system_seed;
+    replay.initial.apply(&mut sim);
+    let ticks = replay.tick_count();
+    for input in replay {
+        sim.step(&input);
+        for message in sim.messages.drain(..) {
+            println!("[tick {}] {}", sim.tick, message);
+        }
+    }
+
+    println!("Repetición terminada: {} ticks", ticks);
+    println!("   tick final: {}", sim.tick);
+    println!("   tiempo simulado: {:.4}", sim.clock.time_f64());
+    println!("   posición: ({:.4}, {:.4}, {:.4})", sim.ship.position.x, sim.ship.position.y, sim.ship.position.z);
+    println!("   rotación: ({:.4}, {:.4}, {:.4})", sim.ship.rotation.x, sim.ship.rotation.y, sim.ship.rotation.z);
+}
+
 fn main() {
-    let window_width = 1200;
-    let window_height = 800;
-    let start_time = Instant::now();
+    // `Lab05 export ...` exporta superficies a PNG sin abrir la ventana
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("export") {
+        if let Err(e) = export::run(&args[1..]) {
+            eprintln!("Error: {}", e);
+            eprintln!("Uso: {}", export::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Uso: Lab05 [--seed <número>] [--bake <resolución>] [--record <archivo>] [--replay <archivo> [--headless]]");
+            std::process::exit(1);
+        }
+    };
+
+    let mut replay = match &options.replay {
+        Some(filename) => match Replay::load(filename) {
+            Ok(replay) => {
+                println!("Reproduciendo {} ({} ticks)", filename, replay.tick_count());
+                Some(replay)
+            }
+            Err(e) => {
+                eprintln!("No se pudo cargar la grabación ({}): {}", filename, e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    if options.headless {
+        if let Some(replay) = replay {
+            run_headless(replay);
+        }
+        return;
+    }
+
+    let window_width = SCREEN_WIDTH;
+    let window_height = SCREEN_HEIGHT;
 
     let mut framebuffer = Framebuffer::new(window_width, window_height);
     let mut window = Window::new(
@@ -504,9 +950,6 @@ fn main() {
         window_height,
         WindowOptions::default(),
     ).unwrap();
-
-    // Crear cámara/nave espacial
-    let mut spaceship_camera = SpaceshipCamera::new(window_width as f32, window_height as f32);
     
     framebuffer.set_background_color(0x000008);
 
@@ -529,59 +972,171 @@ fn main() {
         (Vec::new(), Vec::new())
     };
 
-    // Definir planetas con escalas más grandes para exploración
-    let planets = vec![
-        Planet { shader_id: 4, scale: 50.0, orbit_radius: 300.0, orbit_speed: 1.6, rotation_speed: 0.02, has_moon: false },
-        Planet { shader_id: 2, scale: 80.0, orbit_radius: 500.0, orbit_speed: 1.2, rotation_speed: 0.015, has_moon: false },
-        Planet { shader_id: 0, scale: 85.0, orbit_radius: 700.0, orbit_speed: 1.0, rotation_speed: 0.05, has_moon: true },
-        Planet { shader_id: 3, scale: 70.0, orbit_radius: 900.0, orbit_speed: 0.8, rotation_speed: 0.048, has_moon: false },
-        Planet { shader_id: 2, scale: 150.0, orbit_radius: 1200.0, orbit_speed: 0.4, rotation_speed: 0.1, has_moon: false },
-        Planet { shader_id: 5, scale: 130.0, orbit_radius: 1500.0, orbit_speed: 0.3, rotation_speed: 0.09, has_moon: false },
-        Planet { shader_id: 5, scale: 100.0, orbit_radius: 1800.0, orbit_speed: 0.2, rotation_speed: 0.07, has_moon: false },
-        Planet { shader_id: 5, scale: 95.0, orbit_radius: 2100.0, orbit_speed: 0.15, rotation_speed: 0.065, has_moon: false },
-    ];
-
     // Generar esfera para planetas
     let (planet_vertices, planet_indices) = create_sphere(1.0, 20);
 
-    let mut global_speed = 1.0f32;
-    let mut paused = false;
-    let mut show_orbits = true;
+    // Sistema a explorar: una grabación recuerda el suyo
+    let system_seed = match &replay {
+        Some(replay) => replay.initial.system_seed,
+        None => options.seed,
+    };
+
+    // Los archivos de escena solo describen el Sistema Solar
+    let mut scene_error = None;
+    if system_seed.is_none() {
+        match install_scene_config() {
+            Ok(()) => println!("Escena cargada desde {}", scene_config::FILES.join(" y ")),
+            Err(e) => {
+                println!("No se pudo cargar la escena: {}", e);
+                println!("Usando el catálogo por defecto...");
+                scene_error = Some(e);
+            }
+        }
+    }
+
+    // Cinturones de rocas (mismo resultado en cada ejecución)
+    let (mut rock_meshes, mut rock_belts) = match system_seed {
+        Some(seed) => belts::create_belts_in(&install_generated_system(seed), seed),
+        None => belts::create_belts(),
+    };
+    // Superficies horneadas: la primera vez tarda, después salen de la caché
+    let mut baked_surfaces = options.bake.map(BakedSurfaces::new);
+    if let Some(baked) = baked_surfaces.as_mut() {
+        baked.refresh(&catalog::bodies());
+    }
+
+    let mut pluto_orbit_path = belts::pluto_orbit().map(|orbit| orbit.path(256)).unwrap_or_default();
+    let mut comet_orbits: Vec<_> = catalog::comets().filter_map(|comet| comets::comet_orbit(&comet)).collect();
+    let mut comet_orbit_paths: Vec<Vec<Vec3>> = comet_orbits.iter().map(|orbit| orbit.path(256)).collect();
+
+    // Estado de la simulación (nave, cámara, warp, órbitas)
+    let mut sim = Simulation::new(create_planets());
+    sim.system_seed = system_seed;
+    if let Some(replay) = &replay {
+        replay.initial.apply(&mut sim);
+    }
+    let mut recorder = options.record.as_ref().map(|_| Recorder::new(&sim));
+    let mut mouse_capture = MouseCapture::new();
+    let mut hud = Hud::new();
+    hud.scene_error = scene_error;
+
+    // Recarga en vivo de la escena. Una grabación no guarda los cambios, así
+    // que no se vigila mientras se graba o se reproduce.
+    let mut scene_watcher = if system_seed.is_some() {
+        None
+    } else if recorder.is_some() || replay.is_some() {
+        println!("La recarga de la escena está desactivada al grabar o reproducir");
+        None
+    } else {
+        Some(FileWatcher::new(&scene_config::FILES))
+    };
+
+    // Cargar asignación de teclas
+    let bindings = match InputBindings::load(CONTROLS_FILE) {
+        Ok(bindings) => {
+            println!("Controles cargados desde {}", CONTROLS_FILE);
+            bindings
+        }
+        Err(e) => {
+            println!("No se pudieron cargar los controles ({}): {}", CONTROLS_FILE, e);
+            println!("Usando controles por defecto...");
+            InputBindings::default_bindings()
+        }
+    };
 
     println!("   Controles de la Nave Espacial:");
-    println!("   WASD: Mover nave");
-    println!("   Flechas: Rotar nave");
-    println!("   Q/E: Roll");
-    println!("   Shift: Turbo");
-    println!("   O: Mostrar/Ocultar órbitas");
-    println!("   +/-: Velocidad del sistema");
-    println!("   ESC: Salir");
+    for line in bindings.describe() {
+        println!("   {}", line);
+    }
+    println!("   Ratón: clic derecho orbita, rueda acerca, clic izquierdo selecciona");
 
+    let mut last_frame = Instant::now();
     while window.is_open() {
-        if window.is_key_down(Key::Escape) {
+        // Tiempo real del cuadro, para lo que no forma parte de la simulación
+        let now = Instant::now();
+        let frame_dt = now.duration_since(last_frame).as_secs_f32();
+        last_frame = now;
+
+        let mut input = bindings.poll(&window);
+        input.mouse = mouse_capture.poll(&window);
+        if input.held(Action::Quit) {
             break;
         }
 
-        handle_input(&window, &mut spaceship_camera, &mut global_speed, &mut paused, &mut show_orbits);
+        // Teclas de la interfaz: no forman parte de la simulación ni de la grabación
+        if input.pressed(Action::ShowBindings) {
+            hud.toggle_help(bindings.describe());
+        }
+        if input.pressed(Action::ToggleHud) {
+            hud.visible = !hud.visible;
+        }
+        if input.pressed(Action::CycleMinimapZoom) {
+            hud.minimap.cycle_zoom();
+            hud.notify(format!("Minimapa: {}", hud.minimap.zoom_name()));
+        }
 
-        let elapsed = if paused {
-            start_time.elapsed().as_secs_f32() - 0.016
-        } else {
-            start_time.elapsed().as_secs_f32() * global_speed
-        };
+        // Cambios en los archivos de escena: la nave y el reloj siguen igual
+        if let Some(watcher) = scene_watcher.as_mut()
+            && watcher.poll(frame_dt)
+        {
+            match install_scene_config() {
+                Ok(()) => {
+                    sim.planets = create_planets();
+                    (rock_meshes, rock_belts) = belts::create_belts();
+                    if let Some(baked) = baked_surfaces.as_mut() {
+                        baked.refresh(&catalog::bodies());
+                    }
+                    pluto_orbit_path = belts::pluto_orbit().map(|orbit| orbit.path(256)).unwrap_or_default();
+                    comet_orbits = catalog::comets().filter_map(|comet| comets::comet_orbit(&comet)).collect();
+                    comet_orbit_paths = comet_orbits.iter().map(|orbit| orbit.path(256)).collect();
+                    println!("Escena recargada");
+                    hud.notify("Escena recargada");
+                    hud.scene_error = None;
+                }
+                Err(e) => {
+                    println!("Error en la escena, se mantiene la anterior: {}", e);
+                    hud.notify("Error en la escena: se mantiene la anterior");
+                    hud.scene_error = Some(e);
+                }
+            }
+        }
+
+        // Durante una repetición la entrada sale del archivo
+        if let Some(replay) = replay.as_mut() {
+            match replay.next() {
+                Some(recorded) => input = recorded,
+                None => {
+                    println!("Repetición terminada en el tick {}", sim.tick);
+                    break;
+                }
+            }
+        }
+
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.push(&input);
+        }
+
+        sim.step(&input);
+        let elapsed = sim.elapsed;
+        for message in sim.messages.drain(..) {
+            hud.notify(message);
+        }
+        hud.update(TICK_DT);
 
-        spaceship_camera.update(0.016);
         framebuffer.clear();
 
         // === RENDERIZAR SKYBOX DE ESTRELLAS PRIMERO ===
-        render_starfield_skybox(&mut framebuffer, &spaceship_camera);
+        render_starfield_skybox(&mut framebuffer, sim.rig.view(), sim.warp_drive.direction(), sim.warp_drive.stretch());
 
-        let view_projection_matrix = spaceship_camera.get_view_projection_matrix();
+        let view_projection_matrix = sim.rig.view_projection_matrix();
+        // Todo se ilumina desde la estrella
+        let light_position = sim.bodies[0].position;
+        let camera_position = sim.rig.view().eye;
 
         // === RENDERIZAR ÓRBITAS ===
-        if show_orbits {
+        if sim.show_orbits {
             let orbit_center = Vec3::new(0.0, 0.0, 0.0);
-            for planet in &planets {
+            for planet in &sim.planets {
                 render_orbit_line(
                     &mut framebuffer, 
                     orbit_center, 
@@ -591,87 +1146,148 @@ fn main() {
                     window_height as f32
                 );
             }
+            render_orbit_path(&mut framebuffer, &pluto_orbit_path, view_projection_matrix, window_width as f32, window_height as f32);
+            for path in &comet_orbit_paths {
+                render_orbit_path(&mut framebuffer, path, view_projection_matrix, window_width as f32, window_height as f32);
+            }
         }
 
         // === RENDERIZAR EL SOL ===
-        let sun_scale = 200.0;
-        let sun_rotation = Vec3::new(0.0, elapsed * 0.1, 0.0);
-        let sun_model_matrix = create_model_matrix(Vec3::new(0.0, 0.0, 0.0), sun_scale, sun_rotation);
+        let sun = &sim.bodies[0];
+        let sun_rotation = body_rotation(&sun.info, elapsed, 0.0);
+        let sun_model_matrix = create_model_matrix(sun.position, sun.radius, sun_rotation);
         let sun_uniforms = Uniforms {
             model_matrix: sun_model_matrix,
+            normal_matrix: normal_matrix(&sun_model_matrix),
             view_projection_matrix,
             time: elapsed,
-            current_shader: 1,
-            is_moon: false,
+            current_shader: sun.info.shader_id,
+            surface: sun.info.surface.as_ref(),
+            baked: baked_surfaces.as_ref().and_then(|baked| baked.get(&sun.info)),
+            light_position,
+            camera_position,
+            cloud_shell: false,
             screen_width: window_width as f32,
             screen_height: window_height as f32,
         };
         render_object(&mut framebuffer, &sun_uniforms, &planet_vertices, &planet_indices);
 
         // === RENDERIZAR PLANETAS ===
-        for planet in &planets {
-            let orbit_angle = elapsed * planet.orbit_speed;
-            let planet_x = planet.orbit_radius * orbit_angle.cos();
-            let planet_z = planet.orbit_radius * orbit_angle.sin();
-            let planet_y = 0.0;
+        for planet in &sim.planets {
+            let planet_position = planet.position(elapsed);
 
-            let planet_rotation = Vec3::new(0.0, elapsed * planet.rotation_speed, 0.0);
+            let planet_rotation = body_rotation(&planet.info, elapsed, planet.orbit_angle(elapsed));
             let planet_model_matrix = create_model_matrix(
-                Vec3::new(planet_x, planet_y, planet_z),
+                planet_position,
                 planet.scale,
                 planet_rotation
             );
 
             let planet_uniforms = Uniforms {
                 model_matrix: planet_model_matrix,
+                normal_matrix: normal_matrix(&planet_model_matrix),
                 view_projection_matrix,
                 time: elapsed,
                 current_shader: planet.shader_id,
-                is_moon: false,
+                surface: planet.info.surface.as_ref(),
+                baked: baked_surfaces.as_ref().and_then(|baked| baked.get(&planet.info)),
+                light_position,
+                camera_position,
+                cloud_shell: false,
                 screen_width: window_width as f32,
                 screen_height: window_height as f32,
             };
 
             render_object(&mut framebuffer, &planet_uniforms, &planet_vertices, &planet_indices);
+            render_cloud_shell(&mut framebuffer, &planet_uniforms, &planet_vertices, &planet_indices);
 
-            // Luna para la Tierra
-            if planet.has_moon {
-                let moon_scale = planet.scale * 0.27;
-                let moon_orbit_radius = planet.scale * 2.0;
-                let moon_angle = elapsed * 2.0;
-                let moon_x = planet_x + moon_orbit_radius * moon_angle.cos();
-                let moon_z = planet_z + moon_orbit_radius * moon_angle.sin();
-                let moon_y = planet_y;
+            // Lunas del planeta
+            for moon in &planet.moons {
+                let moon_scale = moon.moon_scale(&planet.info, planet.scale);
 
                 let moon_model_matrix = create_model_matrix(
-                    Vec3::new(moon_x, moon_y, moon_z),
+                    planet.moon_position(moon, elapsed),
                     moon_scale,
-                    Vec3::new(0.0, elapsed * 0.5, 0.0)
-                );
+                    body_rotation(moon, elapsed, elapsed * moon.orbit_speed())
+                ) * create_shape_matrix(moon.shape);
 
                 let moon_uniforms = Uniforms {
                     model_matrix: moon_model_matrix,
+                    normal_matrix: normal_matrix(&moon_model_matrix),
                     view_projection_matrix,
                     time: elapsed,
-                    current_shader: 0,
-                    is_moon: true,
+                    current_shader: moon.shader_id,
+                    surface: moon.surface.as_ref(),
+                    baked: baked_surfaces.as_ref().and_then(|baked| baked.get(moon)),
+                    light_position,
+                    camera_position,
+                    cloud_shell: false,
                     screen_width: window_width as f32,
                     screen_height: window_height as f32,
                 };
 
                 render_object(&mut framebuffer, &moon_uniforms, &planet_vertices, &planet_indices);
+                render_cloud_shell(&mut framebuffer, &moon_uniforms, &planet_vertices, &planet_indices);
             }
         }
 
+        // === RENDERIZAR PLANETAS ENANOS Y NÚCLEOS DE COMETAS ===
+        let minor_bodies = sim.bodies.iter().filter(|body| matches!(body.info.kind, BodyKind::DwarfPlanet | BodyKind::Comet));
+        for body in minor_bodies {
+            let minor_model_matrix = create_model_matrix(body.position, body.radius, body_rotation(&body.info, elapsed, 0.0))
+                * create_shape_matrix(body.info.shape);
+            let minor_uniforms = Uniforms {
+                model_matrix: minor_model_matrix,
+                normal_matrix: normal_matrix(&minor_model_matrix),
+                view_projection_matrix,
+                time: elapsed,
+                current_shader: body.info.shader_id,
+                surface: body.info.surface.as_ref(),
+                baked: baked_surfaces.as_ref().and_then(|baked| baked.get(&body.info)),
+                light_position,
+                camera_position,
+                cloud_shell: false,
+                screen_width: window_width as f32,
+                screen_height: window_height as f32,
+            };
+            render_object(&mut framebuffer, &minor_uniforms, &planet_vertices, &planet_indices);
+        }
+
+        // === RENDERIZAR CINTURONES DE ROCAS ===
+        let belt_uniforms = Uniforms {
+            model_matrix: Mat4::identity(),
+            normal_matrix: Mat3::identity(),
+            view_projection_matrix,
+            time: elapsed,
+            current_shader: 0,
+            surface: None,
+            baked: None,
+            light_position,
+            camera_position,
+            cloud_shell: false,
+            screen_width: window_width as f32,
+            screen_height: window_height as f32,
+        };
+        for belt in &rock_belts {
+            render_object_instanced(&mut framebuffer, &belt_uniforms, &rock_meshes, belt);
+        }
+
         // === RENDERIZAR LA NAVE ESPACIAL ===
-        if !spaceship_vertices.is_empty() {
-            let spaceship_model_matrix = spaceship_camera.get_spaceship_transform();
+        if !sim.rig.shows_ship() {
+            // En cabina la nave no se dibuja
+        } else if !spaceship_vertices.is_empty() {
+            let spaceship_model_matrix = sim.ship.get_spaceship_transform();
             let spaceship_uniforms = Uniforms {
                 model_matrix: spaceship_model_matrix,
+                normal_matrix: normal_matrix(&spaceship_model_matrix),
                 view_projection_matrix,
                 time: elapsed,
                 current_shader: 6, // Shader especial para la nave
-                is_moon: false,
+                surface: None,
+                baked: None,
+                light_position,
+                camera_position,
+                cloud_shell: false,
                 screen_width: window_width as f32,
                 screen_height: window_height as f32,
             };
@@ -679,77 +1295,33 @@ fn main() {
             render_object(&mut framebuffer, &spaceship_uniforms, &spaceship_vertices, &spaceship_indices);
         } else {
             // Si no hay nave, renderizar un cubo simple como indicador
-            render_debug_spaceship_cube(&mut framebuffer, &spaceship_camera, view_projection_matrix, window_width as f32, window_height as f32);
+            render_debug_spaceship_cube(&mut framebuffer, &sim.ship, view_projection_matrix, light_position, camera_position, window_width as f32, window_height as f32);
+        }
+
+        // === COMA Y COLAS DE LOS COMETAS ===
+        // Brillo aditivo encima de la escena, sin tapar lo que está delante
+        let projector = ScreenProjector::new(view_projection_matrix, sim.rig.view(), window_width as f32, window_height as f32);
+        for orbit in &comet_orbits {
+            comets::draw_comet(&mut framebuffer, &projector, orbit, elapsed);
         }
 
+        // === PARTÍCULAS ===
+        sim.particles.draw(&mut framebuffer, &projector);
+
+        // === HUD ===
+        hud.draw(&mut framebuffer, &sim);
+
         window
             .update_with_buffer(&framebuffer.buffer, window_width, window_height)
             .unwrap();
 
         std::thread::sleep(Duration::from_millis(16));
     }
-}
-
-fn handle_input(window: &Window, camera: &mut SpaceshipCamera, global_speed: &mut f32, paused: &mut bool, show_orbits: &mut bool) {
-    let base_speed = 8.0; // Un poco más rápido para mejor navegación
-    let turbo_multiplier = if window.is_key_down(Key::LeftShift) { 3.0 } else { 1.0 };
-    let move_speed = base_speed * turbo_multiplier;
-    let rotation_speed = 0.03; // Un poco más responsivo
-
-    // === MOVIMIENTO DE NAVE ===
-    if window.is_key_down(Key::W) {
-        camera.velocity += camera.get_forward_vector() * move_speed;
-    }
-    if window.is_key_down(Key::S) {
-        camera.velocity -= camera.get_forward_vector() * move_speed;
-    }
-    if window.is_key_down(Key::A) {
-        camera.velocity -= camera.get_right_vector() * move_speed;
-    }
-    if window.is_key_down(Key::D) {
-        camera.velocity += camera.get_right_vector() * move_speed;
-    }
-
-    // === ROTACIÓN ===
-    if window.is_key_down(Key::Up) {
-        camera.rotation.x += rotation_speed;
-    }
-    if window.is_key_down(Key::Down) {
-        camera.rotation.x -= rotation_speed;
-    }
-    if window.is_key_down(Key::Left) {
-        camera.rotation.y += rotation_speed;
-    }
-    if window.is_key_down(Key::Right) {
-        camera.rotation.y -= rotation_speed;
-    }
-    if window.is_key_down(Key::Q) {
-        camera.rotation.z += rotation_speed;
-    }
-    if window.is_key_down(Key::E) {
-        camera.rotation.z -= rotation_speed;
-    }
-
-    // Limitar pitch
-    camera.rotation.x = camera.rotation.x.clamp(-PI / 2.5, PI / 2.5);
-
-    // === CONTROLES DEL SISTEMA ===
-    if window.is_key_pressed(Key::Space, KeyRepeat::No) {
-        *paused = !*paused;
-        println!("{}", if *paused { "Sistema PAUSADO" } else { "▶Sistema REANUDADO" });
-    }
-
-    if window.is_key_pressed(Key::O, KeyRepeat::No) {
-        *show_orbits = !*show_orbits;
-        println!("{}", if *show_orbits { "Órbitas VISIBLES" } else { "Órbitas OCULTAS" });
-    }
 
-    if window.is_key_down(Key::Equal) {
-        *global_speed += 0.1;
-        println!("⚡ Velocidad del sistema: {:.1}x", global_speed);
-    }
-    if window.is_key_down(Key::Minus) {
-        *global_speed = (*global_speed - 0.1).max(0.1);
-        println!("Velocidad del sistema: {:.1}x", global_speed);
+    if let (Some(recorder), Some(filename)) = (&recorder, &options.record) {
+        match recorder.save(filename) {
+            Ok(()) => println!("Grabación guardada en {} ({} ticks)", filename, recorder.tick_count()),
+            Err(e) => println!("No se pudo guardar la grabación ({}): {}", filename, e),
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,165 @@
+use nalgebra_glm::Vec3;
+use crate::framebuffer::Framebuffer;
+use crate::simulation::Simulation;
+use crate::catalog::BodyKind;
+
+// Tamaño del panel y separación del borde de la pantalla (pixeles)
+const MAP_SIZE: usize = 220;
+const MAP_MARGIN: i32 = 12;
+
+// Mitad del área visible en unidades del mundo para cada nivel de zoom.
+// El primero muestra todo el sistema centrado en el Sol; los demás siguen a la nave.
+const ZOOM_LEVELS: [f32; 3] = [2400.0, 1000.0, 350.0];
+
+const BACKGROUND_COLOR: u32 = 0x05070F;
+const BORDER_COLOR: u32 = 0x3A4566;
+const ORBIT_COLOR: u32 = 0x2A3350;
+const STAR_COLOR: u32 = 0xFFD75E;
+const PLANET_COLOR: u32 = 0x6FA8FF;
+const DWARF_PLANET_COLOR: u32 = 0xC8A888;
+const COMET_COLOR: u32 = 0x9FE3FF;
+const MOON_COLOR: u32 = 0xA0A0A0;
+const SHIP_COLOR: u32 = 0x5CFF9D;
+const TARGET_COLOR: u32 = 0xFFC857;
+
+// Radar con el sistema visto desde arriba (proyección ortográfica sobre XZ)
+pub struct Minimap {
+    zoom: usize,
+}
+
+impl Minimap {
+    pub fn new() -> Self {
+        Minimap { zoom: 0 }
+    }
+
+    pub fn cycle_zoom(&mut self) {
+        self.zoom = (self.zoom + 1) % ZOOM_LEVELS.len();
+    }
+
+    pub fn zoom_name(&self) -> &'static str {
+        match self.zoom {
+            0 => "sistema completo",
+            1 => "región",
+            _ => "cercano",
+        }
+    }
+
+    pub fn draw(&self, framebuffer: &mut Framebuffer, sim: &Simulation) {
+        let left = framebuffer.width as i32 - MAP_MARGIN - MAP_SIZE as i32;
+        let top = framebuffer.height as i32 - MAP_MARGIN - MAP_SIZE as i32;
+        let half_extent = ZOOM_LEVELS[self.zoom];
+        let origin = if self.zoom == 0 { Vec3::new(0.0, 0.0, 0.0) } else { sim.ship.position };
+        let map = MapTransform {
+            left,
+            top,
+            scale: MAP_SIZE as f32 * 0.5 / half_extent,
+            origin,
+        };
+
+        framebuffer.fill_rect(left, top, MAP_SIZE, MAP_SIZE, BACKGROUND_COLOR, 0.75);
+        framebuffer.set_clip_rect(left, top, MAP_SIZE, MAP_SIZE);
+
+        // === ÓRBITAS ===
+        for planet in &sim.planets {
+            let radius = planet.orbit_radius * map.scale;
+            let (cx, cy) = map.to_panel(Vec3::new(0.0, 0.0, 0.0));
+            let segments = (radius * 0.5).clamp(32.0, 256.0) as usize;
+            for s in 0..segments {
+                let angle = s as f32 / segments as f32 * std::f32::consts::TAU;
+                map.plot(framebuffer, cx + radius * angle.cos(), cy + radius * angle.sin(), ORBIT_COLOR, 0.9);
+            }
+        }
+
+        // === CUERPOS ===
+        for (i, body) in sim.bodies.iter().enumerate() {
+            let (x, y) = map.to_panel(body.position);
+            let (color, min_radius) = match body.info.kind {
+                BodyKind::Star => (STAR_COLOR, 4.0),
+                BodyKind::Planet => (PLANET_COLOR, 2.0),
+                BodyKind::DwarfPlanet => (DWARF_PLANET_COLOR, 1.5),
+                BodyKind::Comet => (COMET_COLOR, 1.0),
+                BodyKind::Moon => (MOON_COLOR, 1.0),
+            };
+            let radius = (body.radius * map.scale).max(min_radius);
+            map.disc(framebuffer, x, y, radius, color);
+
+            if sim.selected_body == Some(i) {
+                map.ring(framebuffer, x, y, radius + 4.0, TARGET_COLOR);
+            }
+        }
+
+        // === NAVE Y CONO DE RUMBO ===
+        let (sx, sy) = map.to_panel(sim.ship.position);
+        let forward = sim.ship.get_forward_vector();
+        let heading = Vec3::new(forward.x, 0.0, forward.z);
+        if heading.magnitude() > 1e-3 {
+            let heading = heading.normalize();
+            let (hx, hy) = (heading.x, heading.z);
+            let (px, py) = (-hy, hx);
+            let length = 26.0;
+            let spread = 0.45;
+            let tip = (sx + hx * 5.0, sy + hy * 5.0);
+            let left_edge = (sx + (hx + px * spread) * length, sy + (hy + py * spread) * length);
+            let right_edge = (sx + (hx - px * spread) * length, sy + (hy - py * spread) * length);
+            framebuffer.fill_triangle_2d(tip, left_edge, right_edge, SHIP_COLOR, 0.25);
+        }
+        map.disc(framebuffer, sx, sy, 3.0, SHIP_COLOR);
+
+        framebuffer.clear_clip_rect();
+
+        // === BORDE ===
+        let (right, bottom) = (left + MAP_SIZE as i32 - 1, top + MAP_SIZE as i32 - 1);
+        framebuffer.draw_line_2d(left, top, right, top, BORDER_COLOR, 1.0);
+        framebuffer.draw_line_2d(left, bottom, right, bottom, BORDER_COLOR, 1.0);
+        framebuffer.draw_line_2d(left, top, left, bottom, BORDER_COLOR, 1.0);
+        framebuffer.draw_line_2d(right, top, right, bottom, BORDER_COLOR, 1.0);
+    }
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Paso de coordenadas del mundo al panel
+struct MapTransform {
+    left: i32,
+    top: i32,
+    scale: f32,
+    origin: Vec3,
+}
+
+impl MapTransform {
+    // Igual que el mapa del sistema: X a la derecha y -Z hacia arriba
+    fn to_panel(&self, world: Vec3) -> (f32, f32) {
+        let half = MAP_SIZE as f32 * 0.5;
+        (
+            self.left as f32 + half + (world.x - self.origin.x) * self.scale,
+            self.top as f32 + half + (world.z - self.origin.z) * self.scale,
+        )
+    }
+
+    fn plot(&self, framebuffer: &mut Framebuffer, x: f32, y: f32, color: u32, alpha: f32) {
+        framebuffer.blend_pixel(x as i32, y as i32, color, alpha);
+    }
+
+    fn disc(&self, framebuffer: &mut Framebuffer, cx: f32, cy: f32, radius: f32, color: u32) {
+        let r = radius.ceil() as i32;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if (dx * dx + dy * dy) as f32 <= radius * radius {
+                    self.plot(framebuffer, cx + dx as f32, cy + dy as f32, color, 1.0);
+                }
+            }
+        }
+    }
+
+    fn ring(&self, framebuffer: &mut Framebuffer, cx: f32, cy: f32, radius: f32, color: u32) {
+        let segments = (radius * 6.0).max(24.0) as usize;
+        for s in 0..segments {
+            let angle = s as f32 / segments as f32 * std::f32::consts::TAU;
+            self.plot(framebuffer, cx + radius * angle.cos(), cy + radius * angle.sin(), color, 1.0);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use minifb::{MouseButton, MouseMode, Window};
+use nalgebra_glm::Mat4;
+use std::f32::consts::PI;
+use crate::camera_rig::{CameraMode, CameraRig};
+use crate::shaders::world_to_screen;
+use crate::{BodyState, SpaceshipCamera, SCREEN_WIDTH, SCREEN_HEIGHT};
+
+// Ajustes del ratón
+pub struct MouseSettings {
+    pub sensitivity: f32,       // Radianes de pitch/yaw por pixel movido
+    pub invert_y: bool,         // Invertir el eje vertical al dirigir la nave
+    pub orbit_sensitivity: f32, // Radianes de giro de cámara por pixel con clic derecho
+    pub zoom_step: f32,         // Fracción de distancia por paso de la rueda
+}
+
+impl Default for MouseSettings {
+    fn default() -> Self {
+        MouseSettings {
+            sensitivity: 0.003,
+            invert_y: false,
+            orbit_sensitivity: 0.008,
+            zoom_step: 0.1,
+        }
+    }
+}
+
+// Ratón en un frame: movimiento relativo, botones y rueda.
+// Es lo que se graba en una repetición.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MouseFrame {
+    pub dx: f32,
+    pub dy: f32,
+    pub right_down: bool,
+    pub scroll: f32,
+    // Posición del clic izquierdo si se pulsó este frame
+    pub click: Option<(f32, f32)>,
+}
+
+// Lectura del ratón desde la ventana; guarda lo necesario para calcular deltas
+pub struct MouseCapture {
+    last_pos: Option<(f32, f32)>,
+    left_was_down: bool,
+}
+
+impl MouseCapture {
+    pub fn new() -> Self {
+        MouseCapture {
+            last_pos: None,
+            left_was_down: false,
+        }
+    }
+
+    pub fn poll(&mut self, window: &Window) -> MouseFrame {
+        let pos = window.get_mouse_pos(MouseMode::Pass);
+        let (dx, dy) = match (pos, self.last_pos) {
+            (Some((x, y)), Some((last_x, last_y))) => (x - last_x, y - last_y),
+            _ => (0.0, 0.0),
+        };
+        self.last_pos = pos;
+
+        let left_down = window.get_mouse_down(MouseButton::Left);
+        let clicked = left_down && !self.left_was_down;
+        self.left_was_down = left_down;
+
+        MouseFrame {
+            dx,
+            dy,
+            right_down: window.get_mouse_down(MouseButton::Right),
+            scroll: window.get_scroll_wheel().map(|(_, scroll)| scroll).unwrap_or(0.0),
+            click: if clicked { pos } else { None },
+        }
+    }
+}
+
+impl Default for MouseCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct MouseInput {
+    pub settings: MouseSettings,
+    // Con el modo de dirección activo, mover el ratón gira la nave
+    pub steering: bool,
+}
+
+impl MouseInput {
+    pub fn new(settings: MouseSettings) -> Self {
+        MouseInput {
+            settings,
+            steering: false,
+        }
+    }
+
+    // Aplica el ratón de este frame. Devuelve el cuerpo clicado, si hay uno.
+    pub fn apply(
+        &self,
+        mouse: &MouseFrame,
+        ship: &mut SpaceshipCamera,
+        rig: &mut CameraRig,
+        bodies: &[BodyState],
+        view_projection_matrix: Mat4,
+        steering_allowed: bool,
+    ) -> Option<usize> {
+        let (dx, dy) = (mouse.dx, mouse.dy);
+
+        // === ARRASTRE CON CLIC DERECHO: ORBITAR LA CÁMARA ===
+        if mouse.right_down {
+            let orbit = self.settings.orbit_sensitivity;
+            if rig.mode == CameraMode::Orbit {
+                rig.orbit.rotate(dx * orbit, dy * orbit);
+            } else {
+                ship.chase_orbit.y -= dx * orbit;
+                ship.chase_orbit.x = (ship.chase_orbit.x + dy * orbit).clamp(-1.2, 1.2);
+            }
+        } else {
+            // Al soltar, la cámara vuelve poco a poco detrás de la nave
+            ship.chase_orbit *= 0.9;
+        }
+
+        // === DIRECCIÓN RELATIVA DE LA NAVE ===
+        if self.steering && steering_allowed && !mouse.right_down {
+            let invert = if self.settings.invert_y { -1.0 } else { 1.0 };
+            ship.rotation.y -= dx * self.settings.sensitivity;
+            ship.rotation.x -= dy * self.settings.sensitivity * invert;
+            ship.rotation.x = ship.rotation.x.clamp(-PI / 2.5, PI / 2.5);
+        }
+
+        // === RUEDA: DISTANCIA DE LA CÁMARA ===
+        if mouse.scroll != 0.0 {
+            let factor = 1.0 - mouse.scroll.signum() * self.settings.zoom_step;
+            if rig.mode == CameraMode::Orbit {
+                let delta = rig.orbit.distance_from_target * (factor - 1.0);
+                rig.orbit.zoom(delta);
+            } else {
+                ship.chase_distance = (ship.chase_distance * factor).clamp(40.0, 600.0);
+            }
+        }
+
+        // === CLIC IZQUIERDO: SELECCIONAR CUERPO ===
+        mouse
+            .click
+            .and_then(|(x, y)| pick_body(x, y, rig, bodies, view_projection_matrix))
+    }
+}
+
+// Busca el cuerpo bajo el cursor. Si hay varios se queda con el más cercano a la cámara.
+fn pick_body(x: f32, y: f32, rig: &CameraRig, bodies: &[BodyState], view_projection_matrix: Mat4) -> Option<usize> {
+    let (width, height) = (SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32);
+    let view = rig.view();
+    let camera_right = view.direction().cross(&view.up).normalize();
+
+    let mut best: Option<(usize, f32)> = None;
+    for (i, body) in bodies.iter().enumerate() {
+        let Some(center) = world_to_screen(body.position, view_projection_matrix, width, height) else {
+            continue;
+        };
+        // Radio en pantalla a partir de un punto en el borde del cuerpo
+        let edge = body.position + camera_right * body.radius;
+        let screen_radius = world_to_screen(edge, view_projection_matrix, width, height)
+            .map(|e| ((e.x - center.x).powi(2) + (e.y - center.y).powi(2)).sqrt())
+            .unwrap_or(0.0)
+            .max(12.0); // Margen mínimo para cuerpos lejanos
+
+        let distance = ((x - center.x).powi(2) + (y - center.y).powi(2)).sqrt();
+        if distance <= screen_radius {
+            let depth = (body.position - view.eye).magnitude();
+            if best.is_none_or(|(_, d)| depth < d) {
+                best = Some((i, depth));
+            }
+        }
+    }
+
+    best.map(|(i, _)| i)
+}
@@ -1,51 +1,369 @@
-use nalgebra_glm::Vec3;
+// Ruido coherente para los shaders: Perlin y simplex en 3D y 4D, celular
+// (Worley), sumas de octavas (fBm normal, "ridged" y "billow") y deformación
+// del dominio. Todo depende de una semilla entera y de un hash sin tablas,
+// así que dos llamadas con la misma semilla dan siempre el mismo patrón.
+//
+// Las funciones de base devuelven valores aproximadamente en [-1, 1]; las de
+// más abajo (fractal_noise, cloud_noise...) son las que usan los shaders y
+// devuelven valores en [0, 1].
 
-pub fn noise_3d(p: Vec3) -> f32 {
-    let x = p.x;
-    let y = p.y;
-    let z = p.z;
-    
-    let hash = ((x * 127.1 + y * 311.7 + z * 74.7).sin() * 43758.5453).fract();
-    (hash * 2.0 - 1.0).abs()
+use nalgebra_glm::{Vec3, Vec4};
+
+// Semilla de los patrones de los planetas
+pub const DEFAULT_SEED: u32 = 0;
+
+// Las octavas de fBm se normalizan, así que su variación es pequeña; este
+// factor la estira para que los umbrales de los shaders repartan bien las zonas
+const FRACTAL_CONTRAST: f32 = 1.8;
+
+// Cada octava dobla la frecuencia y reduce la amplitud a la mitad
+const LACUNARITY: f32 = 2.0;
+const GAIN: f32 = 0.5;
+
+// === HASH ===
+
+// Cada coordenada se multiplica por un primo grande y el resultado se
+// mezcla una vez; es barato y no repite patrones en la rejilla
+const PRIME_X: u32 = 501_125_321;
+const PRIME_Y: u32 = 1_136_930_381;
+const PRIME_Z: u32 = 1_720_413_743;
+const PRIME_W: u32 = 1_066_037_191;
+
+fn hash(x: i32, y: i32, z: i32, w: i32, seed: u32) -> u32 {
+    let h = seed.wrapping_mul(0x9E37_79B1)
+        ^ (x as u32).wrapping_mul(PRIME_X)
+        ^ (y as u32).wrapping_mul(PRIME_Y)
+        ^ (z as u32).wrapping_mul(PRIME_Z)
+        ^ (w as u32).wrapping_mul(PRIME_W);
+    let h = h.wrapping_mul(0x27D4_EB2D);
+    h ^ (h >> 15)
+}
+
+// Número en [0, 1) propio de una celda de la rejilla entera
+pub fn cell_random(cell: [i32; 3], salt: u32, seed: u32) -> f32 {
+    (hash(cell[0], cell[1], cell[2], salt as i32, seed) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// Gradientes hacia las aristas de un cubo (Perlin mejorado). En tabla y
+// no con condiciones: con hashes aleatorios los saltos no se predicen.
+const GRADIENTS_3D: [[f32; 3]; 16] = [
+    [1.0, 1.0, 0.0], [-1.0, 1.0, 0.0], [1.0, -1.0, 0.0], [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [1.0, 0.0, -1.0], [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0], [0.0, -1.0, 1.0], [0.0, 1.0, -1.0], [0.0, -1.0, -1.0],
+    [1.0, 1.0, 0.0], [0.0, -1.0, 1.0], [-1.0, 1.0, 0.0], [0.0, -1.0, -1.0],
+];
+
+fn grad_3d(h: u32, x: f32, y: f32, z: f32) -> f32 {
+    let g = GRADIENTS_3D[(h & 15) as usize];
+    g[0] * x + g[1] * y + g[2] * z
+}
+
+// Gradientes hacia las aristas de un hipercubo: una coordenada a cero y
+// las otras tres con todos los signos
+const GRADIENTS_4D: [[f32; 4]; 32] = {
+    let mut table = [[0.0; 4]; 32];
+    let mut i = 0;
+    while i < 32 {
+        let zero_axis = i / 8;
+        let mut axis = 0;
+        let mut bit = 0;
+        while axis < 4 {
+            if axis != zero_axis {
+                table[i][axis] = if (i >> bit) & 1 == 0 { 1.0 } else { -1.0 };
+                bit += 1;
+            }
+            axis += 1;
+        }
+        i += 1;
+    }
+    table
+};
+
+fn grad_4d(h: u32, x: f32, y: f32, z: f32, w: f32) -> f32 {
+    let g = GRADIENTS_4D[(h & 31) as usize];
+    g[0] * x + g[1] * y + g[2] * z + g[3] * w
+}
+
+// === PERLIN ===
+
+pub fn perlin_3d(p: Vec3, seed: u32) -> f32 {
+    let (x0, y0, z0) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (fx, fy, fz) = (p.x - x0, p.y - y0, p.z - z0);
+    let (ix, iy, iz) = (x0 as i32, y0 as i32, z0 as i32);
+    let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let h = hash(ix + dx, iy + dy, iz + dz, 0, seed);
+        grad_3d(h, fx - dx as f32, fy - dy as f32, fz - dz as f32)
+    };
+
+    lerp(
+        w,
+        lerp(v, lerp(u, corner(0, 0, 0), corner(1, 0, 0)), lerp(u, corner(0, 1, 0), corner(1, 1, 0))),
+        lerp(v, lerp(u, corner(0, 0, 1), corner(1, 0, 1)), lerp(u, corner(0, 1, 1), corner(1, 1, 1))),
+    )
+}
+
+pub fn perlin_4d(p: Vec4, seed: u32) -> f32 {
+    let base = [p.x.floor(), p.y.floor(), p.z.floor(), p.w.floor()];
+    let f = [p.x - base[0], p.y - base[1], p.z - base[2], p.w - base[3]];
+    let i = base.map(|b| b as i32);
+
+    // Las 16 esquinas del hipercubo, indexadas por sus bits (x, y, z, w)
+    let mut values = [0.0; 16];
+    for (corner, value) in values.iter_mut().enumerate() {
+        let d = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1, (corner >> 3) & 1].map(|b| b as i32);
+        let h = hash(i[0] + d[0], i[1] + d[1], i[2] + d[2], i[3] + d[3], seed);
+        *value = grad_4d(h, f[0] - d[0] as f32, f[1] - d[1] as f32, f[2] - d[2] as f32, f[3] - d[3] as f32);
+    }
+
+    // Interpolar eje por eje: cada pasada reduce las esquinas a la mitad
+    let mut len = 16;
+    for axis in f {
+        let t = fade(axis);
+        len /= 2;
+        for k in 0..len {
+            values[k] = lerp(t, values[2 * k], values[2 * k + 1]);
+        }
+    }
+    values[0]
+}
+
+// === SIMPLEX ===
+
+pub fn simplex_3d(p: Vec3, seed: u32) -> f32 {
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+
+    // Celda del retículo sesgado y posición dentro del tetraedro
+    let s = (p.x + p.y + p.z) * F3;
+    let (i, j, k) = ((p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor());
+    let t = (i + j + k) * G3;
+    let x0 = p.x - (i - t);
+    let y0 = p.y - (j - t);
+    let z0 = p.z - (k - t);
+
+    // Los ejes ordenados de mayor a menor dicen por qué vértices pasar
+    let (o1, o2) = if x0 >= y0 {
+        if y0 >= z0 {
+            ([1, 0, 0], [1, 1, 0])
+        } else if x0 >= z0 {
+            ([1, 0, 0], [1, 0, 1])
+        } else {
+            ([0, 0, 1], [1, 0, 1])
+        }
+    } else if y0 < z0 {
+        ([0, 0, 1], [0, 1, 1])
+    } else if x0 < z0 {
+        ([0, 1, 0], [0, 1, 1])
+    } else {
+        ([0, 1, 0], [1, 1, 0])
+    };
+
+    let (i, j, k) = (i as i32, j as i32, k as i32);
+    let mut total = 0.0;
+    for (n, offset) in [[0, 0, 0], o1, o2, [1, 1, 1]].iter().enumerate() {
+        let x = x0 - offset[0] as f32 + n as f32 * G3;
+        let y = y0 - offset[1] as f32 + n as f32 * G3;
+        let z = z0 - offset[2] as f32 + n as f32 * G3;
+        let falloff = 0.6 - x * x - y * y - z * z;
+        if falloff > 0.0 {
+            let h = hash(i + offset[0], j + offset[1], k + offset[2], 0, seed);
+            total += falloff.powi(4) * grad_3d(h, x, y, z);
+        }
+    }
+    32.0 * total
+}
+
+pub fn simplex_4d(p: Vec4, seed: u32) -> f32 {
+    let f4 = (5.0_f32.sqrt() - 1.0) / 4.0;
+    let g4 = (5.0 - 5.0_f32.sqrt()) / 20.0;
+
+    let s = (p.x + p.y + p.z + p.w) * f4;
+    let cell = [(p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor(), (p.w + s).floor()];
+    let t = (cell[0] + cell[1] + cell[2] + cell[3]) * g4;
+    let d = [p.x - (cell[0] - t), p.y - (cell[1] - t), p.z - (cell[2] - t), p.w - (cell[3] - t)];
+
+    // Rango de cada eje: cuántos de los otros tres son menores
+    let mut rank = [0; 4];
+    for a in 0..4 {
+        for b in a + 1..4 {
+            if d[a] > d[b] {
+                rank[a] += 1;
+            } else {
+                rank[b] += 1;
+            }
+        }
+    }
+
+    let cell = cell.map(|c| c as i32);
+    let mut total = 0.0;
+    for n in 0..5 {
+        // Vértice n del símplex: los ejes con rango >= 4 - n ya avanzaron
+        let offset = rank.map(|r| (r >= 4 - n) as i32);
+        let x: [f32; 4] = std::array::from_fn(|a| d[a] - offset[a] as f32 + n as f32 * g4);
+        let falloff = 0.6 - x.iter().map(|v| v * v).sum::<f32>();
+        if falloff > 0.0 {
+            let h = hash(cell[0] + offset[0], cell[1] + offset[1], cell[2] + offset[2], cell[3] + offset[3], seed);
+            total += falloff.powi(4) * grad_4d(h, x[0], x[1], x[2], x[3]);
+        }
+    }
+    27.0 * total
+}
+
+// === CELULAR (WORLEY) ===
+
+// Distancias al punto característico más cercano (f1) y al segundo (f2), y
+// la celda del más cercano para sacar de ella datos propios con cell_random
+pub struct Cellular {
+    pub f1: f32,
+    pub f2: f32,
+    pub cell: [i32; 3],
+}
+
+// Un punto por celda, desplazado del centro como mucho `jitter` / 2 en cada
+// eje (1 = en cualquier parte de la celda)
+pub fn worley_3d(p: Vec3, jitter: f32, seed: u32) -> Cellular {
+    let base = [p.x.floor() as i32, p.y.floor() as i32, p.z.floor() as i32];
+    let mut result = Cellular { f1: f32::MAX, f2: f32::MAX, cell: base };
+
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            for dz in -1..=1 {
+                let cell = [base[0] + dx, base[1] + dy, base[2] + dz];
+                let feature = feature_point(cell, jitter, seed);
+                let distance = (p - feature).magnitude();
+                if distance < result.f1 {
+                    result.f2 = result.f1;
+                    result.f1 = distance;
+                    result.cell = cell;
+                } else if distance < result.f2 {
+                    result.f2 = distance;
+                }
+            }
+        }
+    }
+    result
+}
+
+pub fn feature_point(cell: [i32; 3], jitter: f32, seed: u32) -> Vec3 {
+    let offset = |salt: u32| 0.5 + (cell_random(cell, salt, seed) - 0.5) * jitter;
+    Vec3::new(cell[0] as f32 + offset(0), cell[1] as f32 + offset(1), cell[2] as f32 + offset(2))
+}
+
+// === OCTAVAS ===
+
+// Movimiento browniano fraccional: octavas de Perlin normalizadas, en [-1, 1]
+pub fn fbm_3d(p: Vec3, octaves: u32, seed: u32) -> f32 {
+    sum_octaves(octaves, |octave, frequency| perlin_3d(p * frequency, seed.wrapping_add(octave)))
+}
+
+// Lo mismo con simplex 4D: la cuarta coordenada suele ser el tiempo, así el
+// patrón hierve en su sitio en vez de desplazarse
+pub fn fbm_4d(p: Vec4, octaves: u32, seed: u32) -> f32 {
+    sum_octaves(octaves, |octave, frequency| simplex_4d(p * frequency, seed.wrapping_add(octave)))
+}
+
+// Crestas afiladas (cordilleras, grietas), en [0, 1]. Cada octava pesa
+// según la anterior, así el detalle se concentra en las crestas.
+pub fn ridged_3d(p: Vec3, octaves: u32, seed: u32) -> f32 {
+    let mut weight = 1.0;
+    sum_octaves(octaves, |octave, frequency| {
+        let ridge = 1.0 - perlin_3d(p * frequency, seed.wrapping_add(octave)).abs();
+        let value = ridge * ridge * weight;
+        weight = value.clamp(0.0, 1.0);
+        value
+    })
+}
+
+// Bultos redondeados (nubes, humo), en [0, 1]. Con simplex, que no marca
+// los ejes de la rejilla como Perlin.
+pub fn billow_3d(p: Vec3, octaves: u32, seed: u32) -> f32 {
+    sum_octaves(octaves, |octave, frequency| simplex_3d(p * frequency, seed.wrapping_add(octave)).abs() * 1.5)
+        .min(1.0)
+}
+
+// Desplaza el punto con fBm antes de muestrear otro ruido: da remolinos y
+// formas estiradas en vez de manchas redondas
+pub fn domain_warp(p: Vec3, strength: f32, octaves: u32, seed: u32) -> Vec3 {
+    let offset = Vec3::new(
+        fbm_3d(p, octaves, seed),
+        fbm_3d(p, octaves, seed.wrapping_add(101)),
+        fbm_3d(p, octaves, seed.wrapping_add(202)),
+    );
+    p + offset * strength
 }
 
-pub fn fractal_noise(mut p: Vec3, octaves: i32) -> f32 {
+fn sum_octaves(octaves: u32, mut octave_value: impl FnMut(u32, f32) -> f32) -> f32 {
     let mut value = 0.0;
     let mut amplitude = 1.0;
     let mut frequency = 1.0;
-    
-    for _ in 0..octaves {
-        value += noise_3d(p * frequency) * amplitude;
-        frequency *= 2.0;
-        amplitude *= 0.5;
-        p = p * 2.0;
+    let mut total_amplitude = 0.0;
+
+    for octave in 0..octaves.max(1) {
+        value += octave_value(octave, frequency) * amplitude;
+        total_amplitude += amplitude;
+        frequency *= LACUNARITY;
+        amplitude *= GAIN;
     }
-    
-    value.clamp(0.0, 1.0)
+
+    value / total_amplitude
+}
+
+// === RUIDOS DE LOS SHADERS ===
+// Todos en [0, 1]
+
+pub fn fractal_noise(p: Vec3, octaves: i32) -> f32 {
+    seeded_fractal_noise(p, octaves, DEFAULT_SEED)
+}
+
+// Igual, con otra semilla: cada cuerpo generado tiene su propio relieve
+pub fn seeded_fractal_noise(p: Vec3, octaves: i32, seed: u32) -> f32 {
+    (0.5 + fbm_3d(p, octaves.max(1) as u32, seed) * FRACTAL_CONTRAST).clamp(0.0, 1.0)
 }
 
 pub fn continent_noise(p: Vec3) -> f32 {
-    // Más continentes grandes
+    // Más continentes grandes, con cordilleras encima
     let large_scale = fractal_noise(p * 0.6, 2) * 0.7;
-    let medium_scale = fractal_noise(p * 1.2, 3) * 0.3;
-    (large_scale + medium_scale).clamp(0.0, 1.0)
+    let mountains = ridged_3d(p * 1.2, 3, DEFAULT_SEED) * 0.3;
+    (large_scale + mountains).clamp(0.0, 1.0)
 }
 
 pub fn cloud_noise(p: Vec3, time: f32) -> f32 {
-    // Nubes más realistas
+    // Nubes algodonosas, estiradas por el viento
     let moving_pos = p + Vec3::new(time * 0.02, 0.0, time * 0.01);
-    let cloud_base = fractal_noise(moving_pos * 1.5, 4);
-    let cloud_detail = fractal_noise(moving_pos * 4.0, 2) * 0.3;
+    let warped = domain_warp(moving_pos * 1.5, 0.4, 2, DEFAULT_SEED + 7);
+    let cloud_base = billow_3d(warped, 4, DEFAULT_SEED + 7);
+    // El detalle además cambia de forma poco a poco
+    let detail_pos = moving_pos * 4.0;
+    let cloud_detail = (0.5 + perlin_4d(Vec4::new(detail_pos.x, detail_pos.y, detail_pos.z, time * 0.05), DEFAULT_SEED + 7)) * 0.3;
     (cloud_base + cloud_detail).clamp(0.0, 1.0)
 }
 
 pub fn sun_noise(p: Vec3, time: f32) -> f32 {
-    let animated_pos = p + Vec3::new(time * 0.5, time * 0.3, time * 0.2);
-    fractal_noise(animated_pos * 4.0, 5)
+    // Turbulencia que hierve con el tiempo y granulación: celdas de
+    // convección claras separadas por bordes más oscuros (calientes en el shader)
+    let scaled = p * 4.0;
+    let boiling = fbm_4d(Vec4::new(scaled.x, scaled.y, scaled.z, time * 0.5), 4, DEFAULT_SEED + 11);
+    let cells = worley_3d(scaled * 2.0, 1.0, DEFAULT_SEED + 11);
+    let granulation = (cells.f2 - cells.f1).min(1.0);
+    (0.5 + boiling * FRACTAL_CONTRAST * 0.8 + (0.5 - granulation) * 0.4).clamp(0.0, 1.0)
 }
 
-pub fn gas_bands(p: Vec3, time: f32) -> f32 {
-    let bands = (p.y * 8.0 + time * 0.2).sin() * 0.5 + 0.5;
-    let turbulence = fractal_noise(p * 2.0 + Vec3::new(time * 0.1, 0.0, 0.0), 3);
+// `frequency` fija cuántas bandas hay y `shear` cuánto se retuercen
+pub fn gas_bands(p: Vec3, time: f32, frequency: f32, shear: f32, seed: u32) -> f32 {
+    // Las bandas se retuercen en los bordes por la cizalladura del viento
+    let flow = p * 2.0 + Vec3::new(time * 0.1, 0.0, 0.0);
+    let warped = domain_warp(flow, shear, 3, seed);
+    let bands = (p.y * frequency + (warped.y - flow.y) * 3.0 + time * 0.2).sin() * 0.5 + 0.5;
+    let turbulence = seeded_fractal_noise(warped, 3, seed);
     (bands + turbulence * 0.3).clamp(0.0, 1.0)
-}
\ No newline at end of file
+}
@@ -0,0 +1,185 @@
+// Sistema de partículas en CPU para efectos que no son mallas: la estela de
+// los motores, los restos de un choque y las erupciones del Sol. Cada
+// partícula es un punto con velocidad y edad; se dibuja como un sprite
+// aditivo con prueba de profundidad. Forma parte del estado de la
+// simulación y usa su propia semilla, así una repetición da las mismas.
+
+use std::f32::consts::TAU;
+use nalgebra_glm::Vec3;
+use crate::color::Color;
+use crate::framebuffer::Framebuffer;
+use crate::rng::Rng;
+use crate::shaders::ScreenProjector;
+
+const PARTICLE_SEED: u64 = 0x9A27_1C1E;
+// Tope para que una ráfaga grande no hunda los FPS
+const MAX_PARTICLES: usize = 4000;
+
+// Cómo nacen y cómo envejecen las partículas de un efecto
+pub struct ParticleStyle {
+    pub rate: f32,            // Emisiones por segundo
+    pub lifetime: (f32, f32), // Segundos (mínimo, máximo)
+    pub speed: (f32, f32),    // Unidades por segundo (mínimo, máximo)
+    pub spread: f32,          // Semiángulo del cono de salida (radianes)
+    pub drag: f32,            // Fracción de la velocidad que se pierde por segundo
+    pub start_size: f32,      // Radio en unidades de la escena al nacer...
+    pub end_size: f32,        // ...y al morir
+    pub start_color: u32,
+    pub end_color: u32,
+    pub intensity: f32,       // Brillo al nacer; se apaga a lo largo de la vida
+}
+
+// Gases calientes de los motores: azules al salir, rojizos al enfriarse
+pub const ENGINE_EXHAUST: ParticleStyle = ParticleStyle {
+    rate: 90.0,
+    lifetime: (0.35, 0.7),
+    speed: (40.0, 70.0),
+    spread: 0.12,
+    drag: 1.5,
+    start_size: 2.0,
+    end_size: 6.0,
+    start_color: 0x9FD8FF,
+    end_color: 0xFF6A2A,
+    intensity: 0.5,
+};
+
+// Chispas y fragmentos de un choque contra un cuerpo
+pub const IMPACT_DEBRIS: ParticleStyle = ParticleStyle {
+    rate: 0.0, // Solo ráfagas
+    lifetime: (0.6, 1.6),
+    speed: (20.0, 90.0),
+    spread: 1.2,
+    drag: 0.8,
+    start_size: 1.5,
+    end_size: 0.8,
+    start_color: 0xFFF2C0,
+    end_color: 0x8A6A4A,
+    intensity: 0.9,
+};
+
+// Erupciones del Sol: cada emisión es una ráfaga desde un punto de la superficie
+pub const SUN_FLARE: ParticleStyle = ParticleStyle {
+    rate: 0.8,
+    lifetime: (1.5, 3.5),
+    speed: (30.0, 110.0),
+    spread: 0.3,
+    drag: 0.6,
+    start_size: 14.0,
+    end_size: 30.0,
+    start_color: 0xFFF0A0,
+    end_color: 0xFF3A10,
+    intensity: 0.35,
+};
+pub const SUN_FLARE_PARTICLES: usize = 40;
+
+struct Particle {
+    style: &'static ParticleStyle,
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+    lifetime: f32,
+}
+
+// Emisor continuo: acumula la fracción de emisión que sobra entre pasos
+pub struct Emitter {
+    style: &'static ParticleStyle,
+    pending: f32,
+}
+
+impl Emitter {
+    pub fn new(style: &'static ParticleStyle) -> Self {
+        Emitter { style, pending: 0.0 }
+    }
+
+    // Emisiones que tocan en este paso; `rate_scale` regula el ritmo (0 = nada)
+    pub fn due(&mut self, dt: f32, rate_scale: f32) -> usize {
+        self.pending += self.style.rate * rate_scale.max(0.0) * dt;
+        let count = self.pending.floor();
+        self.pending -= count;
+        count as usize
+    }
+
+    // Emite partículas en un cono alrededor de `direction`. `base_velocity`
+    // es la del objeto que las suelta (la nave, por ejemplo).
+    pub fn emit(&mut self, particles: &mut ParticleSystem, dt: f32, rate_scale: f32, position: Vec3, direction: Vec3, base_velocity: Vec3) {
+        let count = self.due(dt, rate_scale);
+        particles.burst(self.style, count, position, direction, base_velocity);
+    }
+}
+
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    rng: Rng,
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        ParticleSystem { particles: Vec::new(), rng: Rng::new(PARTICLE_SEED) }
+    }
+
+    // Dirección al azar, para colocar efectos (como una erupción) en cualquier punto
+    pub fn random_direction(&mut self) -> Vec3 {
+        self.rng.unit_vector()
+    }
+
+    // Suelta `count` partículas de golpe
+    pub fn burst(&mut self, style: &'static ParticleStyle, count: usize, position: Vec3, direction: Vec3, base_velocity: Vec3) {
+        let count = count.min(MAX_PARTICLES.saturating_sub(self.particles.len()));
+        for _ in 0..count {
+            let heading = random_in_cone(&mut self.rng, direction, style.spread);
+            let speed = self.rng.range(style.speed.0, style.speed.1);
+            self.particles.push(Particle {
+                style,
+                position,
+                velocity: base_velocity + heading * speed,
+                age: 0.0,
+                lifetime: self.rng.range(style.lifetime.0, style.lifetime.1),
+            });
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for particle in &mut self.particles {
+            particle.velocity *= (1.0 - particle.style.drag * dt).max(0.0);
+            particle.position += particle.velocity * dt;
+            particle.age += dt;
+        }
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+    }
+
+    // Sprites aditivos: se dibujan después de la geometría opaca
+    pub fn draw(&self, framebuffer: &mut Framebuffer, projector: &ScreenProjector) {
+        for particle in &self.particles {
+            let Some(screen) = projector.project(particle.position) else {
+                continue;
+            };
+            let style = particle.style;
+            let life = particle.age / particle.lifetime;
+            let size = style.start_size + (style.end_size - style.start_size) * life;
+            let color = Color::lerp(&Color::from_hex(style.start_color), &Color::from_hex(style.end_color), life);
+            let radius = projector.radius_pixels(particle.position, size);
+            framebuffer.add_sprite(screen.x, screen.y, screen.z, radius, color.to_hex(), style.intensity * (1.0 - life));
+        }
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Dirección uniforme dentro del cono de semiángulo `spread` alrededor de `axis`
+fn random_in_cone(rng: &mut Rng, axis: Vec3, spread: f32) -> Vec3 {
+    let axis = axis.normalize();
+    let cos_angle = rng.range(spread.cos(), 1.0);
+    let sin_angle = (1.0 - cos_angle * cos_angle).sqrt();
+    let turn = rng.range(0.0, TAU);
+
+    // Dos ejes perpendiculares al del cono
+    let helper = if axis.y.abs() < 0.9 { Vec3::new(0.0, 1.0, 0.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+    let side = axis.cross(&helper).normalize();
+    let up = axis.cross(&side);
+
+    axis * cos_angle + (side * turn.cos() + up * turn.sin()) * sin_angle
+}
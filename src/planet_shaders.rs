@@ -1,857 +1,1236 @@
-use nalgebra_glm::{Vec3, dot};
+use std::f32::consts::{PI, TAU};
+use nalgebra_glm::{Vec3, Mat3, dot};
+use crate::bump;
 use crate::color::Color;
-use crate::noise::{fractal_noise, cloud_noise, sun_noise, gas_bands};
+use crate::noise::{fractal_noise, seeded_fractal_noise, cloud_noise, sun_noise, gas_bands, ridged_3d, worley_3d, domain_warp, cell_random, DEFAULT_SEED};
+
+// Desplazamiento máximo de los centros de cráter dentro de su celda
+const CRATER_JITTER: f32 = 0.4;
+// Profundidad de los cráteres para el relieve, en radios del cuerpo por celda
+const CRATER_DEPTH: f32 = 0.06;
+
+// === FAMILIAS DE SHADERS ===
+// Cuatro shaders con los colores, las escalas y los detalles como datos:
+// estrellas, planetas rocosos, gigantes y cuerpos sin aire (MoonStyle, más
+// abajo). Los cuerpos del Sistema Solar usan sus propios ajustes y los
+// sistemas generados eligen los suyos, así un mismo shader cubre a muchos.
+
+#[derive(Clone, Debug)]
+pub enum SurfaceStyle {
+    Star(StarStyle),
+    Terrestrial(TerrestrialStyle),
+    Giant(GiantStyle),
+    Airless(MoonStyle),
+}
 
+impl SurfaceStyle {
+    // Aspecto completo del cuerpo. En los rocosos las nubes se mezclan encima
+    // en el mismo punto; el simulador las dibuja aparte con `clouds`.
+    // `normal` es la normal interpolada en el mundo, sobre la que se aplica
+    // el relieve; `to_world` es la matriz de normales del cuerpo.
+    pub fn shade(&self, world_pos: Vec3, normal: Vec3, to_world: &Mat3, light_dir: Vec3, view_dir: Vec3, time: f32) -> Color {
+        match self {
+            SurfaceStyle::Star(style) => star_shader(world_pos, normal, view_dir, time, style),
+            SurfaceStyle::Terrestrial(style) => terrestrial_shader(world_pos, normal, to_world, light_dir, view_dir, time, style),
+            SurfaceStyle::Giant(style) => giant_shader(world_pos, normal, light_dir, view_dir, time, style),
+            SurfaceStyle::Airless(style) => rocky_moon_shader(world_pos, normal, to_world, light_dir, style),
+        }
+    }
 
-pub fn earth_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
-    let sphere_pos = world_pos.normalize();
+    // Capa estática en un punto de la esfera; None si la familia se anima entera
+    pub fn surface(&self, sphere_pos: Vec3) -> Option<SurfaceSample> {
+        match self {
+            SurfaceStyle::Terrestrial(style) => Some(terrestrial_surface(sphere_pos, style)),
+            SurfaceStyle::Airless(style) => Some(rocky_moon_surface(sphere_pos, style)),
+            SurfaceStyle::Star(_) | SurfaceStyle::Giant(_) => None,
+        }
+    }
 
-    // === CAPA 1: BASE AZUL OSCURA (MAR) ===
-    let base_color = Color::new(0, 50, 150); // Azul océano intenso
+    // Iluminación sobre una capa estática ya calculada (por ejemplo, leída de
+    // una textura horneada). No incluye las nubes: van en su propia capa.
+    pub fn shade_surface(&self, sphere_pos: Vec3, sample: SurfaceSample, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32) -> Color {
+        match self {
+            SurfaceStyle::Terrestrial(style) => terrestrial_layers(sphere_pos, &sample, normal, light_dir, view_dir, time, style),
+            SurfaceStyle::Airless(style) => rocky_moon_lighting(sample.albedo, normal, light_dir, style),
+            SurfaceStyle::Star(_) | SurfaceStyle::Giant(_) => self.shade(sphere_pos, normal, &Mat3::identity(), light_dir, view_dir, time),
+        }
+    }
 
-    // === CAPA 2: CASQUETES POLARES ===
-    let latitude = sphere_pos.y.abs();
-    let ice_factor = if latitude > 0.8 {
-        ((latitude - 0.8) / 0.2).clamp(0.0, 1.0) * 0.9
-    } else {
-        0.0
-    };
+    // Si el cuerpo lleva una capa de nubes sobre la superficie
+    pub fn has_cloud_shell(&self) -> bool {
+        matches!(self, SurfaceStyle::Terrestrial(style) if style.cloud_cover > 0.0)
+    }
 
-    let ice_color = Color::new(255, 255, 255);
-    let terrain_with_ice = if ice_factor > 0.1 {
-        let r = (base_color.r() as f32 * (1.0 - ice_factor) + ice_color.r() as f32 * ice_factor).clamp(0.0, 255.0);
-        let g = (base_color.g() as f32 * (1.0 - ice_factor) + ice_color.g() as f32 * ice_factor).clamp(0.0, 255.0);
-        let b = (base_color.b() as f32 * (1.0 - ice_factor) + ice_color.b() as f32 * ice_factor).clamp(0.0, 255.0);
-        Color::new(r as u8, g as u8, b as u8)
-    } else {
-        base_color
+    // Color y opacidad de la capa de nubes en un punto de su esfera (ver
+    // `cloud_shell_angle`); None donde el cielo está despejado
+    pub fn clouds(&self, shell_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Option<(Color, f32)> {
+        match self {
+            SurfaceStyle::Terrestrial(style) => terrestrial_clouds(shell_pos, normal, light_dir, time, style),
+            _ => None,
+        }
+    }
+}
+
+// Capa de la superficie que no cambia con el tiempo: el color antes de la
+// luz, la normal en el espacio del modelo y las máscaras de los materiales.
+// Es lo que se hornea en las texturas.
+#[derive(Clone, Copy)]
+pub struct SurfaceSample {
+    pub albedo: Color,
+    pub normal: Vec3,
+    pub specular: f32, // Reflejo del Sol: 1 en el agua, 0 en tierra
+    pub lights: f32,   // Luces de ciudad que se encienden de noche (0 a 1)
+}
+
+// Parámetros de familia de los `shader_id` que tienen uno; los demás se
+// dibujan con `fixed_shader`
+pub fn family_style(shader_id: u32) -> Option<SurfaceStyle> {
+    let style = match shader_id {
+        0 => SurfaceStyle::Terrestrial(TerrestrialStyle::earth()),
+        1 => SurfaceStyle::Star(StarStyle::sun()),
+        2 => SurfaceStyle::Giant(GiantStyle::jupiter()),
+        3 => SurfaceStyle::Terrestrial(TerrestrialStyle::mars()),
+        4 => SurfaceStyle::Airless(MoonStyle::mercury()),
+        5 => SurfaceStyle::Giant(GiantStyle::neptune()),
+        8 => SurfaceStyle::Giant(GiantStyle::saturn()),
+        9 => SurfaceStyle::Giant(GiantStyle::uranus()),
+        10 => SurfaceStyle::Airless(MoonStyle::moon()),
+        13 => SurfaceStyle::Airless(MoonStyle::ganymede()),
+        14 => SurfaceStyle::Airless(MoonStyle::callisto()),
+        17 => SurfaceStyle::Airless(MoonStyle::phobos()),
+        18 => SurfaceStyle::Airless(MoonStyle::deimos()),
+        19 => SurfaceStyle::Airless(MoonStyle::pluto()),
+        20 => SurfaceStyle::Airless(MoonStyle::asteroid()),
+        21 => SurfaceStyle::Airless(MoonStyle::kuiper()),
+        22 => SurfaceStyle::Airless(MoonStyle::comet_nucleus()),
+        _ => return None,
     };
+    Some(style)
+}
 
-    // === CAPA 3: BLOQUES DE CONTINENTES CON SENOS/COSENOS ===
-    
-    // Máscara para evitar continentes en los polos
-    let pole_mask = if latitude < 0.7 { 1.0 } else { 0.0 };
-    
-    // Patrón de bloques usando senos y cosenos
-    let x = sphere_pos.x;
-    let y = sphere_pos.y;
-    let z = sphere_pos.z;
-    
-    // ===== CONTINENTE 1 - CAFÉ (ÁFRICA) =====
-    let continent_pattern1 = (x * 2.0).sin() * (z * 1.5).cos();
-    let continent_pattern2 = (y * 1.0 + z * 2.5).cos() * (x * 1.8).sin();
-    let continent_pattern3 = (z * 2.2).sin() * (y * 1.5 + x * 1.3).cos();
-    let coast_detail1 = (x * 8.0).sin() * (z * 10.0).cos() * 0.2;
-    let coast_detail2 = (x * 12.0 + z * 8.0).sin() * (y * 15.0).cos() * 0.15;
-    let fine_detail1 = ((x * 25.0).sin() * (z * 30.0).cos()) * 0.08;
-    let base_continent1 = continent_pattern1 * 0.5 + continent_pattern2 * 0.35 + continent_pattern3 * 0.15;
-    let continent_density1 = base_continent1 + coast_detail1 + coast_detail2 + fine_detail1;
-    
-    // ===== CONTINENTE 2 - VERDE (SUDAMÉRICA) =====
-    let continent_pattern4 = ((x - 1.5) * 2.1).sin() * ((z + 0.8) * 1.6).cos();
-    let continent_pattern5 = ((y * 0.9 + (z + 0.8) * 2.3).cos()) * ((x - 1.5) * 1.9).sin();
-    let continent_pattern6 = ((z + 0.8) * 2.4).sin() * ((y * 1.4 + (x - 1.5) * 1.6).cos());
-    let coast_detail4 = ((x - 1.5) * 9.0).sin() * ((z + 0.8) * 11.0).cos() * 0.18;
-    let coast_detail5 = ((x - 1.5) * 13.0).sin() * (y * 16.0).cos() * 0.14;
-    let fine_detail2 = (((x - 1.5) * 26.0).sin() * ((z + 0.8) * 31.0).cos()) * 0.07;
-    let base_continent2 = continent_pattern4 * 0.5 + continent_pattern5 * 0.35 + continent_pattern6 * 0.15;
-    let continent_density2 = base_continent2 + coast_detail4 + coast_detail5 + fine_detail2;
-    
-    // ===== CONTINENTE 3 - VERDE (AMÉRICA DEL NORTE) =====
-    let continent_pattern7 = ((x + 0.8) * 1.9).sin() * ((z - 1.2) * 1.7).cos();
-    let continent_pattern8 = ((y * 1.1 + (z - 1.2) * 2.1).cos()) * ((x + 0.8) * 2.0).sin();
-    let continent_pattern9 = ((z - 1.2) * 2.3).sin() * ((y * 1.6 + (x + 0.8) * 1.5).cos());
-    let coast_detail7 = ((x + 0.8) * 8.5).sin() * ((z - 1.2) * 10.5).cos() * 0.19;
-    let coast_detail8 = ((x + 0.8) * 12.5).sin() * (y * 15.5).cos() * 0.16;
-    let fine_detail3 = (((x + 0.8) * 24.0).sin() * ((z - 1.2) * 29.0).cos()) * 0.09;
-    let base_continent3 = continent_pattern7 * 0.5 + continent_pattern8 * 0.35 + continent_pattern9 * 0.15;
-    let continent_density3 = base_continent3 + coast_detail7 + coast_detail8 + fine_detail3;
-    
-    // ===== CONTINENTE 4 - CAFÉ (AUSTRALIA) =====
-    let continent_pattern10 = ((x + 1.2) * 2.2).sin() * ((z + 1.5) * 1.8).cos();
-    let continent_pattern11 = ((y * 0.8 + (z + 1.5) * 2.4).cos()) * ((x + 1.2) * 1.85).sin();
-    let continent_pattern12 = ((z + 1.5) * 2.6).sin() * ((y * 1.3 + (x + 1.2) * 1.7).cos());
-    let coast_detail10 = ((x + 1.2) * 9.5).sin() * ((z + 1.5) * 11.5).cos() * 0.17;
-    let coast_detail11 = ((x + 1.2) * 13.5).sin() * (y * 14.5).cos() * 0.14;
-    let fine_detail4 = (((x + 1.2) * 27.0).sin() * ((z + 1.5) * 32.0).cos()) * 0.08;
-    let base_continent4 = continent_pattern10 * 0.5 + continent_pattern11 * 0.35 + continent_pattern12 * 0.15;
-    let continent_density4 = base_continent4 + coast_detail10 + coast_detail11 + fine_detail4;
-    
-    // ===== CONTINENTE 5 - VERDE (EURASIA) =====
-    let continent_pattern13 = ((x - 0.5) * 1.95).sin() * ((z - 0.6) * 1.65).cos();
-    let continent_pattern14 = ((y * 1.05 + (z - 0.6) * 2.15).cos()) * ((x - 0.5) * 2.05).sin();
-    let continent_pattern15 = ((z - 0.6) * 2.35).sin() * ((y * 1.55 + (x - 0.5) * 1.45).cos());
-    let coast_detail13 = ((x - 0.5) * 8.8).sin() * ((z - 0.6) * 10.8).cos() * 0.185;
-    let coast_detail14 = ((x - 0.5) * 12.8).sin() * (y * 15.8).cos() * 0.155;
-    let fine_detail5 = (((x - 0.5) * 25.5).sin() * ((z - 0.6) * 30.5).cos()) * 0.075;
-    let base_continent5 = continent_pattern13 * 0.5 + continent_pattern14 * 0.35 + continent_pattern15 * 0.15;
-    let continent_density5 = base_continent5 + coast_detail13 + coast_detail14 + fine_detail5;
-    
-    // ===== CONTINENTE 6 - CAFÉ (ZONA VACÍA) =====
-    let continent_pattern16 = ((x + 0.3) * 2.15).sin() * ((z + 0.5) * 1.75).cos();
-    let continent_pattern17 = ((y * 0.95 + (z + 0.5) * 2.35).cos()) * ((x + 0.3) * 1.88).sin();
-    let continent_pattern18 = ((z + 0.5) * 2.55).sin() * ((y * 1.35 + (x + 0.3) * 1.65).cos());
-    let coast_detail16 = ((x + 0.3) * 9.2).sin() * ((z + 0.5) * 11.2).cos() * 0.175;
-    let coast_detail17 = ((x + 0.3) * 13.2).sin() * (y * 14.8).cos() * 0.145;
-    let fine_detail6 = (((x + 0.3) * 26.5).sin() * ((z + 0.5) * 31.5).cos()) * 0.08;
-    let base_continent6 = continent_pattern16 * 0.5 + continent_pattern17 * 0.35 + continent_pattern18 * 0.15;
-    let continent_density6 = base_continent6 + coast_detail16 + coast_detail17 + fine_detail6;
-    
-    // Combinar continentes verdes
-    let continent_density_green = continent_density2.max(continent_density3).max(continent_density5);
-    
-    // Combinar continentes cafés (ahora incluye el nuevo continente 6)
-    let continent_density_brown = continent_density1.max(continent_density4).max(continent_density6);
-    
-    // Calcular factor para continentes VERDES
-    let continent_factor_green = if continent_density_green > 0.10 && pole_mask > 0.5 {
-        let factor = ((continent_density_green - 0.10) / 0.90).clamp(0.0, 1.0);
-        if factor > 0.2 {
-            factor * 0.95
-        } else {
-            factor * 0.65
+// Shaders propios de los cuerpos sin familia, y de la nave. `normal` es la
+// normal interpolada en el mundo y `view_dir`, la dirección hacia la cámara.
+pub fn fixed_shader(shader_id: u32, world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32) -> Color {
+    match shader_id {
+        6 => spaceship_shader(world_pos, normal, light_dir, time), // Shader de nave
+        7 => venus_shader(world_pos, normal, light_dir, view_dir, time),
+        // Lunas
+        11 => io_shader(world_pos, normal, light_dir, time),
+        12 => europa_shader(world_pos, normal, light_dir, view_dir, time),
+        15 => titan_shader(world_pos, normal, light_dir, view_dir, time),
+        16 => triton_shader(world_pos, normal, light_dir, view_dir, time),
+        _ => Color::new(255, 0, 255),
+    }
+}
+
+// Dirección en el modelo para una latitud y longitud en grados. La longitud
+// 0 es +X, la cara que los cuerpos con acoplamiento de marea dan a su planeta.
+fn surface_direction(latitude_deg: f32, longitude_deg: f32) -> Vec3 {
+    let (latitude, longitude) = (latitude_deg.to_radians(), longitude_deg.to_radians());
+    Vec3::new(latitude.cos() * longitude.cos(), latitude.sin(), latitude.cos() * longitude.sin())
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+// --- Estrellas ---
+
+// Granulación que va del núcleo a las llamaradas, manchas y corona
+#[derive(Clone, Debug)]
+pub struct StarStyle {
+    pub core: Color,
+    pub hot: Color,
+    pub flare: Color,
+    pub spot: Color,
+    pub corona: Color,
+    pub granulation_scale: f32, // Frecuencia de la granulación
+    pub spot_scale: f32,        // Frecuencia de las manchas
+    pub spot_coverage: f32,     // Fracción de la superficie con manchas
+    pub corona_strength: f32,
+    pub pulse: f32,             // Cuánto late el brillo
+}
+
+impl StarStyle {
+    pub fn sun() -> Self {
+        StarStyle {
+            core: Color::new(255, 255, 100),
+            hot: Color::new(255, 150, 50),
+            flare: Color::new(255, 50, 0),
+            spot: Color::new(150, 80, 20),
+            corona: Color::new(255, 200, 100),
+            granulation_scale: 1.0,
+            spot_scale: 1.5,
+            spot_coverage: 0.2,
+            corona_strength: 0.6,
+            pulse: 0.1,
         }
+    }
+}
+
+pub fn star_shader(world_pos: Vec3, normal: Vec3, view_dir: Vec3, time: f32, style: &StarStyle) -> Color {
+    // === GRANULACIÓN ===
+    let turbulence = sun_noise(world_pos * style.granulation_scale, time);
+    let mut color = if turbulence < 0.7 {
+        Color::lerp(&style.core, &style.hot, (turbulence - 0.3) / 0.4)
     } else {
-        0.0
+        Color::lerp(&style.hot, &style.flare, (turbulence - 0.7) / 0.1)
     };
-    
-    // Calcular factor para continentes CAFÉS
-    let continent_factor_brown = if continent_density_brown > 0.10 && pole_mask > 0.5 {
-        let factor = ((continent_density_brown - 0.10) / 0.90).clamp(0.0, 1.0);
-        if factor > 0.2 {
-            factor * 0.95
-        } else {
-            factor * 0.65
+
+    // === MANCHAS ===
+    let spots = fractal_noise(world_pos * style.spot_scale, 3);
+    let threshold = 1.0 - style.spot_coverage;
+    if spots > threshold {
+        color = Color::lerp(&color, &style.spot, (spots - threshold) / style.spot_coverage.max(0.01) * 0.5);
+    }
+
+    // === CORONA ===
+    let fresnel = 1.0 - dot(&normal, &view_dir).abs();
+    let glow = style.corona.scale(fresnel.powf(2.0) * style.corona_strength);
+    color = color.add(&glow);
+
+    // === PULSACIÓN ===
+    color.scale(1.0 - style.pulse + (time * 3.0).sin() * style.pulse)
+}
+
+// --- Planetas rocosos ---
+
+// La capa de nubes es una esfera un poco mayor que el planeta que gira más
+// deprisa que él
+pub const CLOUD_SHELL_SCALE: f32 = 1.015;
+const CLOUD_SHELL_SPIN: f32 = 0.02; // Radianes por unidad de tiempo
+// Cuánta luz quitan las nubes más densas a la superficie de debajo
+const CLOUD_SHADOW: f32 = 0.45;
+// Exponente de Blinn-Phong del reflejo en el agua: más alto, brillo más pequeño
+const GLINT_SHININESS: f32 = 60.0;
+const GLINT_COLOR: Color = Color::new(255, 245, 220);
+
+// Relieve con o sin océanos, regiones oscuras, cañones, cráteres, casquetes,
+// ciudades y una capa de nubes (o de polvo, en Marte)
+#[derive(Clone, Debug)]
+pub struct TerrestrialStyle {
+    pub deep_ocean: Color,
+    pub shallow_ocean: Color,
+    pub lowland: Color,
+    pub highland: Color,
+    pub dark_terrain: Color, // Basalto, fondos de cañones y cráteres
+    pub ice: Color,
+    pub cloud: Color,
+    pub city: Color,         // Luces de las ciudades en el lado nocturno
+    pub feature_scale: f32,  // Frecuencia del relieve: más alta, continentes más pequeños
+    pub ocean_level: f32,    // Altura del mar entre 0 y 1 (0 = sin océanos)
+    pub dark_coverage: f32,  // Fracción de la tierra con regiones oscuras
+    pub canyons: f32,        // Intensidad de los cañones cerca del ecuador
+    pub crater_density: f32,
+    pub relief: f32,         // Relieve de cañones y cráteres en la normal (0 = liso)
+    pub ice_latitude: f32,   // Seno de la latitud donde empiezan los casquetes
+    pub cloud_cover: f32,    // 0 = cielo despejado, 1 = cubierto
+    pub city_lights: f32,    // Fracción de la tierra habitada (0 = sin ciudades)
+    pub glint: f32,          // Intensidad del reflejo del Sol en los océanos
+    pub ambient: f32,        // Luz en el lado nocturno (más con atmósfera densa)
+    pub seed: u32,
+}
+
+impl TerrestrialStyle {
+    pub fn earth() -> Self {
+        TerrestrialStyle {
+            deep_ocean: Color::new(0, 40, 130),
+            shallow_ocean: Color::new(20, 90, 175),
+            lowland: Color::new(20, 160, 40),
+            highland: Color::new(220, 150, 60),
+            dark_terrain: Color::new(60, 90, 40),
+            ice: Color::new(255, 255, 255),
+            cloud: Color::new(255, 255, 255),
+            city: Color::new(255, 190, 110),
+            feature_scale: 1.3,
+            ocean_level: 0.55,
+            dark_coverage: 0.0,
+            canyons: 0.0,
+            crater_density: 0.0,
+            relief: 0.0,
+            ice_latitude: 0.8,
+            cloud_cover: 0.4,
+            city_lights: 0.45,
+            glint: 0.7,
+            ambient: 0.12,
+            seed: 3,
         }
-    } else {
-        0.0
-    };
+    }
 
-    // Colores más saturados y vivos
-    let continent_color_green = Color::new(20, 160, 40);   // Verde más saturado
-    let continent_color_brown = Color::new(220, 150, 60);  // Café más brillante
-    
-    // Aplicar continentes verdes primero
-    let terrain_with_green = if continent_factor_green > 0.15 {
-        let r = (terrain_with_ice.r() as f32 * (1.0 - continent_factor_green) + continent_color_green.r() as f32 * continent_factor_green).clamp(0.0, 255.0);
-        let g = (terrain_with_ice.g() as f32 * (1.0 - continent_factor_green) + continent_color_green.g() as f32 * continent_factor_green).clamp(0.0, 255.0);
-        let b = (terrain_with_ice.b() as f32 * (1.0 - continent_factor_green) + continent_color_green.b() as f32 * continent_factor_green).clamp(0.0, 255.0);
-        Color::new(r as u8, g as u8, b as u8)
-    } else {
-        terrain_with_ice
-    };
-    
-    // Aplicar continentes cafés sobre el resultado anterior
-    let terrain_color = if continent_factor_brown > 0.15 {
-        let r = (terrain_with_green.r() as f32 * (1.0 - continent_factor_brown) + continent_color_brown.r() as f32 * continent_factor_brown).clamp(0.0, 255.0);
-        let g = (terrain_with_green.g() as f32 * (1.0 - continent_factor_brown) + continent_color_brown.g() as f32 * continent_factor_brown).clamp(0.0, 255.0);
-        let b = (terrain_with_green.b() as f32 * (1.0 - continent_factor_brown) + continent_color_brown.b() as f32 * continent_factor_brown).clamp(0.0, 255.0);
-        Color::new(r as u8, g as u8, b as u8)
-    } else {
-        terrain_with_green
-    };
+    // Óxido de hierro con basalto, Valles Marineris y tormentas de polvo
+    pub fn mars() -> Self {
+        TerrestrialStyle {
+            deep_ocean: Color::new(60, 40, 35),
+            shallow_ocean: Color::new(90, 60, 45),
+            lowland: Color::new(193, 68, 14),
+            highland: Color::new(220, 100, 40),
+            dark_terrain: Color::new(100, 45, 25),
+            ice: Color::new(245, 240, 235),
+            cloud: Color::new(210, 120, 70),
+            city: Color::black(),
+            feature_scale: 1.5,
+            ocean_level: 0.0,
+            dark_coverage: 0.2,
+            canyons: 0.5,
+            crater_density: 0.25,
+            relief: 1.0,
+            ice_latitude: 0.78,
+            cloud_cover: 0.15,
+            city_lights: 0.0,
+            glint: 0.0,
+            ambient: 0.35,
+            seed: 4,
+        }
+    }
+}
 
-    // === CAPA 4: NUBES ===
-    let cloud_density = cloud_noise(sphere_pos, time);
-    
-    let cloud_factor = if cloud_density > 0.6 {
-        ((cloud_density - 0.6) / 0.4).clamp(0.0, 1.0) * 0.5
+// Ángulo que la capa de nubes lleva girado respecto a la superficie
+pub fn cloud_shell_angle(time: f32) -> f32 {
+    -time * CLOUD_SHELL_SPIN
+}
+
+// Punto de la superficie en las coordenadas de la capa de nubes (deshace el
+// giro de `cloud_shell_angle` alrededor del eje Y)
+fn to_cloud_shell(sphere_pos: Vec3, time: f32) -> Vec3 {
+    let (sin, cos) = cloud_shell_angle(time).sin_cos();
+    Vec3::new(cos * sphere_pos.x - sin * sphere_pos.z, sphere_pos.y, sin * sphere_pos.x + cos * sphere_pos.z)
+}
+
+// Superficie, luz y nubes mezcladas en el mismo punto
+pub fn terrestrial_shader(world_pos: Vec3, normal: Vec3, to_world: &Mat3, light_dir: Vec3, view_dir: Vec3, time: f32, style: &TerrestrialStyle) -> Color {
+    let sphere_pos = world_pos.normalize();
+    let surface = terrestrial_surface(sphere_pos, style);
+    let ground_normal = bump::perturb(normal, sphere_pos, surface.normal, to_world);
+    let ground = terrestrial_layers(sphere_pos, &surface, ground_normal, light_dir, view_dir, time, style);
+    // Las nubes no tienen relieve
+    match terrestrial_clouds(to_cloud_shell(sphere_pos, time), normal, light_dir, time, style) {
+        Some((cloud, alpha)) => Color::lerp(&ground, &cloud, alpha),
+        None => ground,
+    }
+}
+
+// Relieve, regiones, cañones, cráteres, casquetes y ciudades
+fn terrestrial_surface(sphere_pos: Vec3, style: &TerrestrialStyle) -> SurfaceSample {
+    let seed = style.seed;
+    let mut specular = 0.0;
+    let mut lights = 0.0;
+
+    // === RELIEVE: MAR Y TIERRA ===
+    let warped = domain_warp(sphere_pos * style.feature_scale, 0.3, 2, seed);
+    let height = seeded_fractal_noise(warped, 5, seed);
+    let mut color = if height < style.ocean_level {
+        let depth = (style.ocean_level - height) / style.ocean_level;
+        specular = 1.0;
+        Color::lerp(&style.shallow_ocean, &style.deep_ocean, depth * 3.0)
     } else {
-        0.0
+        let altitude = (height - style.ocean_level) / (1.0 - style.ocean_level).max(0.01);
+        let mut land = Color::lerp(&style.lowland, &style.highland, altitude * 1.5);
+
+        // === REGIONES OSCURAS ===
+        if style.dark_coverage > 0.0 {
+            let region = seeded_fractal_noise(sphere_pos * style.feature_scale * 1.7, 3, seed.wrapping_add(2));
+            let threshold = 1.0 - style.dark_coverage;
+            if region > threshold {
+                land = Color::lerp(&land, &style.dark_terrain, (region - threshold) / style.dark_coverage * 0.6);
+            }
+        }
+
+        // === CAÑONES ===
+        // Crestas del ruido como valles, solo cerca del ecuador
+        let equator = 1.0 - sphere_pos.y.abs() / 0.4;
+        if style.canyons > 0.0 && equator > 0.0 {
+            let valley = ridged_3d(sphere_pos * 3.0, 3, seed.wrapping_add(3));
+            if valley > 0.75 {
+                land = Color::lerp(&land, &style.dark_terrain, (valley - 0.75) / 0.25 * style.canyons * equator.min(0.5) * 2.0);
+            }
+        }
+
+        // === CRÁTERES ===
+        if let Some(d) = crater_cell(sphere_pos * 8.0, style.crater_density)
+            && d < 1.0
+        {
+            land = Color::lerp(&land, &style.dark_terrain, 0.35 * (1.0 - d));
+        }
+
+        // === CIUDADES ===
+        // Regiones pobladas a gran escala, más en las tierras bajas, con
+        // núcleos urbanos sueltos dentro
+        if style.city_lights > 0.0 {
+            let population = seeded_fractal_noise(sphere_pos * 2.5, 3, seed.wrapping_add(5)) * (1.0 - altitude).max(0.0);
+            let threshold = 1.0 - style.city_lights;
+            if population > threshold {
+                let towns = worley_3d(sphere_pos * 30.0, 1.0, seed.wrapping_add(6));
+                let core = (1.0 - towns.f1 * 1.6).max(0.0);
+                lights = ((population - threshold) / style.city_lights * 3.0).min(1.0) * (0.2 + 0.8 * core);
+            }
+        }
+        land
     };
 
-    let cloud_color = Color::new(255, 255, 255);
-    let final_color = if cloud_factor > 0.15 {
-        let blend = cloud_factor;
-        let r = (terrain_color.r() as f32 * (1.0 - blend) + cloud_color.r() as f32 * blend).clamp(0.0, 255.0);
-        let g = (terrain_color.g() as f32 * (1.0 - blend) + cloud_color.g() as f32 * blend).clamp(0.0, 255.0);
-        let b = (terrain_color.b() as f32 * (1.0 - blend) + cloud_color.b() as f32 * blend).clamp(0.0, 255.0);
-        Color::new(r as u8, g as u8, b as u8)
+    // === CASQUETES POLARES ===
+    // Borde irregular para que no sea un círculo perfecto
+    let edge = (seeded_fractal_noise(sphere_pos * 4.0, 2, seed.wrapping_add(1)) - 0.5) * 0.1;
+    let latitude = sphere_pos.y.abs() + edge;
+    if latitude > style.ice_latitude {
+        let ice = ((latitude - style.ice_latitude) / 0.08 * 0.9).min(1.0);
+        color = Color::lerp(&color, &style.ice, ice);
+        specular *= 1.0 - ice;
+        lights *= 1.0 - ice;
+    }
+
+    // === RELIEVE ===
+    let normal = if style.relief > 0.0 {
+        bump::height_normal(sphere_pos, style.relief, |p| terrestrial_height(p, style))
     } else {
-        terrain_color
+        sphere_pos
     };
 
-    // === ILUMINACIÓN ===
-    let light_dir_normalized = light_dir.normalize();
-    let diffuse = dot(&normal, &light_dir_normalized).max(0.0) * 0.7;
-    let ambient = 0.5; // Más luz ambiente para que el océano se vea azul
-    let intensity = (diffuse + ambient).min(1.0);
+    SurfaceSample { albedo: color, normal, specular, lights }
+}
 
-    let r = (final_color.r() as f32 * intensity).clamp(0.0, 255.0);
-    let g = (final_color.g() as f32 * intensity).clamp(0.0, 255.0);
-    let b = (final_color.b() as f32 * intensity).clamp(0.0, 255.0);
+// Altura del terreno para el relieve: rugosidad suave, el fondo de los
+// cañones (los mismos que se pintan) y los cráteres. El mar no entra: los
+// océanos con relieve no tienen sentido.
+fn terrestrial_height(sphere_pos: Vec3, style: &TerrestrialStyle) -> f32 {
+    let seed = style.seed;
+    let mut height = (seeded_fractal_noise(sphere_pos * style.feature_scale * 4.0, 3, seed.wrapping_add(7)) - 0.5) * 0.01;
+
+    let equator = 1.0 - sphere_pos.y.abs() / 0.4;
+    if style.canyons > 0.0 && equator > 0.0 {
+        let valley = ridged_3d(sphere_pos * 3.0, 3, seed.wrapping_add(3));
+        height -= ((valley - 0.75) / 0.25).clamp(0.0, 1.0) * style.canyons * equator.min(0.5) * 2.0 * 0.03;
+    }
 
-    Color::new(r as u8, g as u8, b as u8)
+    if let Some(d) = crater_cell(sphere_pos * 8.0, style.crater_density) {
+        height += crater_height(d, 8.0);
+    }
+    height
 }
 
-pub fn sun_shader(world_pos: Vec3, normal: Vec3, _light_dir: Vec3, time: f32) -> Color {
-    // === CAPA 1: SUPERFICIE ARDIENTE ===
-    let surface_turbulence = sun_noise(world_pos, time);
-    
-    let core_color = Color::new(255, 255, 100);  // Amarillo brillante
-    let hot_color = Color::new(255, 150, 50);    // Naranja caliente
-    let flare_color = Color::new(255, 50, 0);    // Rojo llamarada
-    
-    let base_color = if surface_turbulence < 0.3 {
-        core_color
-    } else if surface_turbulence < 0.7 {
-        let t = (surface_turbulence - 0.3) / 0.4;
-        Color::new(
-            (core_color.r() as f32 * (1.0 - t) + hot_color.r() as f32 * t) as u8,
-            (core_color.g() as f32 * (1.0 - t) + hot_color.g() as f32 * t) as u8,
-            (core_color.b() as f32 * (1.0 - t) + hot_color.b() as f32 * t) as u8,
-        )
-    } else {
-        flare_color
-    };
-    
-    // === CAPA 2: MANCHAS SOLARES ===
-    let spot_noise = fractal_noise(world_pos * 1.5, 3);
-    let spot_factor = if spot_noise > 0.8 {
-        ((spot_noise - 0.8) / 0.2) * 0.5
+// Luz del Sol con la sombra de las nubes, reflejo en el agua y luces de ciudad
+fn terrestrial_layers(sphere_pos: Vec3, sample: &SurfaceSample, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32, style: &TerrestrialStyle) -> Color {
+    let n_dot_l = dot(&normal, &light_dir);
+
+    // === SOMBRA DE LAS NUBES ===
+    // La capa es tan fina que la sombra cae casi justo debajo; la rampa más
+    // ancha que la de las propias nubes difumina el borde
+    let shadow = if style.cloud_cover > 0.0 {
+        let threshold = cloud_threshold(style) - 0.1;
+        let clouds = cloud_field(to_cloud_shell(sphere_pos, time), time, style);
+        ((clouds - threshold) / 0.5).clamp(0.0, 1.0) * CLOUD_SHADOW
     } else {
         0.0
     };
-    
-    let spot_color = Color::new(150, 80, 20);
-    let surface_with_spots = if spot_factor > 0.0 {
-        Color::new(
-            (base_color.r() as f32 * (1.0 - spot_factor) + spot_color.r() as f32 * spot_factor) as u8,
-            (base_color.g() as f32 * (1.0 - spot_factor) + spot_color.g() as f32 * spot_factor) as u8,
-            (base_color.b() as f32 * (1.0 - spot_factor) + spot_color.b() as f32 * spot_factor) as u8,
-        )
-    } else {
-        base_color
-    };
-    
-    // === CAPA 3: CORONA (borde brillante) ===
-    let view_dir = Vec3::new(0.0, 0.0, 1.0);
-    let fresnel = 1.0 - dot(&normal, &view_dir).abs();
-    let corona_factor = fresnel.powf(2.0) * 0.6;
-    
-    let corona_color = Color::new(255, 200, 100);
-    let surface_with_corona = if corona_factor > 0.0 {
-        Color::new(
-            (surface_with_spots.r() as f32 + corona_color.r() as f32 * corona_factor).min(255.0) as u8,
-            (surface_with_spots.g() as f32 + corona_color.g() as f32 * corona_factor).min(255.0) as u8,
-            (surface_with_spots.b() as f32 + corona_color.b() as f32 * corona_factor).min(255.0) as u8,
-        )
-    } else {
-        surface_with_spots
-    };
-    
-    // === CAPA 4: PULSACIÓN ===
-    let pulse = (time * 3.0).sin() * 0.1 + 0.9;
-    
-    Color::new(
-        (surface_with_corona.r() as f32 * pulse) as u8,
-        (surface_with_corona.g() as f32 * pulse) as u8,
-        (surface_with_corona.b() as f32 * pulse) as u8,
-    )
+
+    // === ILUMINACIÓN ===
+    let diffuse = n_dot_l.max(0.0) * 0.8 * (1.0 - shadow);
+    let mut color = sample.albedo.scale((diffuse + style.ambient).min(1.0));
+
+    // === REFLEJO DEL SOL ===
+    // Blinn-Phong solo en el agua
+    if style.glint > 0.0 && sample.specular > 0.0 && n_dot_l > 0.0 {
+        let half = (light_dir + view_dir).normalize();
+        let highlight = dot(&normal, &half).max(0.0).powf(GLINT_SHININESS);
+        color = color.add(&GLINT_COLOR.scale(highlight * style.glint * sample.specular * (1.0 - shadow)));
+    }
+
+    // === LUCES DE CIUDAD ===
+    // Se encienden al pasar el terminador; las nubes de encima las tapan
+    if sample.lights > 0.0 {
+        let night = ((0.05 - n_dot_l) / 0.2).clamp(0.0, 1.0);
+        color = color.add(&style.city.scale(sample.lights * night));
+    }
+    color
 }
 
-pub fn gas_giant_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
-    let band_pattern = gas_bands(world_pos, time);
-    
-    let band1_color = Color::new(200, 150, 100);
-    let band2_color = Color::new(150, 100, 80);
-    let band3_color = Color::new(100, 80, 120);
-    
-    let base_color = if band_pattern < 0.33 {
-        band1_color
-    } else if band_pattern < 0.66 {
-        band2_color
-    } else {
-        band3_color
-    };
-    
-    let turbulence = fractal_noise(world_pos * 2.0 + Vec3::new(time * 0.1, 0.0, 0.0), 4);
-    let storm_factor = if turbulence > 0.7 {
-        ((turbulence - 0.7) / 0.3) * 0.4
-    } else {
-        0.0
-    };
-    
-    let storm_color = Color::new(220, 200, 180);
-    let surface_with_storms = if storm_factor > 0.0 {
-        Color::new(
-            (base_color.r() as f32 * (1.0 - storm_factor) + storm_color.r() as f32 * storm_factor) as u8,
-            (base_color.g() as f32 * (1.0 - storm_factor) + storm_color.g() as f32 * storm_factor) as u8,
-            (base_color.b() as f32 * (1.0 - storm_factor) + storm_color.b() as f32 * storm_factor) as u8,
-        )
-    } else {
-        base_color
-    };
-    
-    let spot_center = Vec3::new(0.3, 0.0, 0.0);
-    let distance_to_spot = (world_pos - spot_center).magnitude();
-    let spot_factor = if distance_to_spot < 0.3 {
-        (1.0 - distance_to_spot / 0.3) * 0.7
-    } else {
-        0.0
-    };
-    
-    let red_spot_color = Color::new(180, 80, 60);
-    let surface_with_spot = if spot_factor > 0.0 {
-        Color::new(
-            (surface_with_storms.r() as f32 * (1.0 - spot_factor) + red_spot_color.r() as f32 * spot_factor) as u8,
-            (surface_with_storms.g() as f32 * (1.0 - spot_factor) + red_spot_color.g() as f32 * spot_factor) as u8,
-            (surface_with_storms.b() as f32 * (1.0 - spot_factor) + red_spot_color.b() as f32 * spot_factor) as u8,
-        )
+// Campo de nubes en coordenadas de la capa
+fn cloud_field(shell_pos: Vec3, time: f32, style: &TerrestrialStyle) -> f32 {
+    // Cada planeta ve otra zona del campo de nubes
+    let offset = (style.seed % 1000) as f32 * 1.618;
+    cloud_noise(shell_pos + Vec3::new(offset, offset * 0.5, -offset), time)
+}
+
+// cloud_noise se amontona arriba (mediana ~0.64): el umbral baja más despacio
+// para que la cobertura se parezca a la fracción pedida
+fn cloud_threshold(style: &TerrestrialStyle) -> f32 {
+    1.0 - style.cloud_cover * 0.8
+}
+
+// Nubes iluminadas y su opacidad
+fn terrestrial_clouds(shell_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32, style: &TerrestrialStyle) -> Option<(Color, f32)> {
+    if style.cloud_cover <= 0.0 {
+        return None;
+    }
+    let threshold = cloud_threshold(style);
+    let clouds = cloud_field(shell_pos, time, style);
+    if clouds <= threshold {
+        return None;
+    }
+    let alpha = ((clouds - threshold) / 0.3 * 0.75).min(1.0);
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.9;
+    Some((style.cloud.scale((diffuse + style.ambient).min(1.0)), alpha))
+}
+
+// --- Gigantes de gas y de hielo ---
+
+// Óvalo de tormenta fijo en latitud que deriva en longitud
+#[derive(Clone, Debug)]
+pub struct Storm {
+    pub latitude_deg: f32,
+    pub longitude_deg: f32,
+    pub width_deg: f32,  // Semieje en longitud
+    pub height_deg: f32, // Semieje en latitud
+    pub color: Color,
+    pub drift: f32,      // Radianes de longitud por unidad de tiempo
+}
+
+// Polígono alrededor del polo norte, como el hexágono de Saturno
+#[derive(Clone, Debug)]
+pub struct PolarVortex {
+    pub color: Color,
+    pub rim: Color, // Borde marcado por la corriente en chorro
+    pub eye: Color, // Vórtice del centro
+    pub sides: u32,
+    pub radius: f32, // Distancia al eje, en radios del planeta
+}
+
+#[derive(Clone, Debug)]
+pub struct GiantStyle {
+    pub bands: [Color; 3],    // De las zonas claras a los cinturones oscuros
+    pub eddy: Color,          // Remolinos y óvalos sueltos
+    pub haze: Color,          // Neblina del borde y de los polos
+    pub band_count: f32,      // Frecuencia de las bandas en latitud
+    pub band_contrast: f32,   // 0 = color uniforme, 1 = bandas marcadas
+    pub turbulence: f32,      // Cuánto se retuercen las bandas
+    pub eddy_coverage: f32,
+    pub polar_haze: f32,      // Capucha polar más clara
+    pub haze_strength: f32,
+    pub storms: Vec<Storm>,
+    pub polar_vortex: Option<PolarVortex>,
+    pub ambient: f32,
+    pub seed: u32,
+}
+
+const JUPITER_STORMS: [Storm; 2] = [
+    // Gran Mancha Roja
+    Storm { latitude_deg: -22.0, longitude_deg: 0.0, width_deg: 20.0, height_deg: 10.0, color: Color::new(180, 80, 60), drift: 0.0 },
+    // Óvalo BA
+    Storm { latitude_deg: -33.0, longitude_deg: 120.0, width_deg: 7.0, height_deg: 4.0, color: Color::new(225, 215, 200), drift: 0.05 },
+];
+
+const NEPTUNE_STORMS: [Storm; 2] = [
+    // Gran Mancha Oscura y la nube brillante que la acompaña
+    Storm { latitude_deg: -20.0, longitude_deg: 40.0, width_deg: 18.0, height_deg: 9.0, color: Color::new(20, 40, 100), drift: 0.3 },
+    Storm { latitude_deg: -42.0, longitude_deg: 10.0, width_deg: 5.0, height_deg: 3.0, color: Color::new(200, 220, 255), drift: 0.5 },
+];
+
+impl GiantStyle {
+    pub fn jupiter() -> Self {
+        GiantStyle {
+            bands: [Color::new(230, 210, 175), Color::new(200, 150, 100), Color::new(150, 100, 80)],
+            eddy: Color::new(220, 200, 180),
+            haze: Color::new(200, 180, 160),
+            band_count: 8.0,
+            band_contrast: 1.0,
+            turbulence: 0.35,
+            eddy_coverage: 0.3,
+            polar_haze: 0.0,
+            haze_strength: 0.3,
+            storms: JUPITER_STORMS.to_vec(),
+            polar_vortex: None,
+            ambient: 0.3,
+            seed: 23,
+        }
+    }
+
+    // Bandas doradas apagadas por la neblina y el hexágono del polo norte
+    pub fn saturn() -> Self {
+        GiantStyle {
+            bands: [Color::new(235, 220, 185), Color::new(225, 200, 150), Color::new(190, 160, 115)],
+            eddy: Color::new(240, 228, 200),
+            haze: Color::new(240, 225, 190),
+            band_count: 4.5,
+            band_contrast: 0.6,
+            turbulence: 0.2,
+            eddy_coverage: 0.0,
+            polar_haze: 0.0,
+            haze_strength: 0.3,
+            storms: Vec::new(),
+            polar_vortex: Some(PolarVortex {
+                color: Color::new(130, 140, 150),
+                rim: Color::new(225, 200, 150),
+                eye: Color::new(80, 85, 95),
+                sides: 6,
+                radius: 0.45,
+            }),
+            ambient: 0.3,
+            seed: 29,
+        }
+    }
+
+    // Neblina cian casi uniforme con una capucha polar más clara
+    pub fn uranus() -> Self {
+        GiantStyle {
+            bands: [Color::new(175, 230, 232), Color::new(160, 220, 225), Color::new(140, 205, 215)],
+            eddy: Color::new(200, 240, 245),
+            haze: Color::new(190, 240, 245),
+            band_count: 3.2,
+            band_contrast: 0.3,
+            turbulence: 0.05,
+            eddy_coverage: 0.0,
+            polar_haze: 0.5,
+            haze_strength: 0.35,
+            storms: Vec::new(),
+            polar_vortex: None,
+            ambient: 0.35,
+            seed: 31,
+        }
+    }
+
+    // Azul profundo de metano con nubes brillantes y la Gran Mancha Oscura
+    pub fn neptune() -> Self {
+        GiantStyle {
+            bands: [Color::new(80, 120, 220), Color::new(40, 100, 200), Color::new(30, 60, 150)],
+            eddy: Color::new(200, 220, 255),
+            haze: Color::new(100, 150, 255),
+            band_count: 3.8,
+            band_contrast: 0.7,
+            turbulence: 0.3,
+            eddy_coverage: 0.2,
+            polar_haze: 0.0,
+            haze_strength: 0.4,
+            storms: NEPTUNE_STORMS.to_vec(),
+            polar_vortex: None,
+            ambient: 0.4,
+            seed: 37,
+        }
+    }
+}
+
+pub fn giant_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32, style: &GiantStyle) -> Color {
+    let sphere_pos = world_pos.normalize();
+    let latitude = sphere_pos.y;
+
+    // === BANDAS ===
+    let bands = gas_bands(sphere_pos, time, style.band_count, style.turbulence, style.seed);
+    let t = 0.5 + (bands - 0.5) * style.band_contrast;
+    let mut color = if t < 0.5 {
+        Color::lerp(&style.bands[0], &style.bands[1], t / 0.5)
     } else {
-        surface_with_storms
+        Color::lerp(&style.bands[1], &style.bands[2], (t - 0.5) / 0.5)
     };
-    
-    let view_dir = Vec3::new(0.0, 0.0, 1.0);
+
+    // === REMOLINOS ===
+    if style.eddy_coverage > 0.0 {
+        let eddies = seeded_fractal_noise(sphere_pos * 3.0 + Vec3::new(time * 0.1, 0.0, 0.0), 4, style.seed.wrapping_add(1));
+        let threshold = 1.0 - style.eddy_coverage;
+        if eddies > threshold {
+            color = Color::lerp(&color, &style.eddy, (eddies - threshold) / style.eddy_coverage * 0.6);
+        }
+    }
+
+    // === TORMENTAS ===
+    let longitude = sphere_pos.z.atan2(sphere_pos.x);
+    let latitude_angle = latitude.clamp(-1.0, 1.0).asin();
+    for storm in &style.storms {
+        let dx = wrap_angle(longitude - storm.longitude_deg.to_radians() - storm.drift * time) / storm.width_deg.to_radians();
+        let dy = (latitude_angle - storm.latitude_deg.to_radians()) / storm.height_deg.to_radians();
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance < 1.0 {
+            color = Color::lerp(&color, &storm.color, ((1.0 - distance) * 2.0).min(1.0) * 0.85);
+        }
+    }
+
+    // === POLOS ===
+    if style.polar_haze > 0.0 && latitude.abs() > 0.6 {
+        color = Color::lerp(&color, &style.haze, (latitude.abs() - 0.6) / 0.4 * style.polar_haze);
+    }
+    if let Some(vortex) = &style.polar_vortex
+        && latitude > 0.0
+    {
+        // Distancia al eje y radio del polígono en cada dirección
+        let radial = (sphere_pos.x * sphere_pos.x + sphere_pos.z * sphere_pos.z).sqrt();
+        let sector = TAU / vortex.sides.max(3) as f32;
+        let local_angle = (longitude + time * 0.02).rem_euclid(sector) - sector * 0.5;
+        let polygon_radius = vortex.radius * (sector * 0.5).cos() / local_angle.cos();
+        if radial < polygon_radius {
+            let inside = radial / polygon_radius;
+            color = Color::lerp(&color, &vortex.color, 0.7);
+            if inside > 0.88 {
+                color = Color::lerp(&color, &vortex.rim, (inside - 0.88) / 0.12 * 0.6);
+            }
+            if inside < 0.25 {
+                color = Color::lerp(&color, &vortex.eye, (1.0 - inside / 0.25) * 0.7);
+            }
+        }
+    }
+
+    // === NEBLINA DEL BORDE ===
     let fresnel = 1.0 - dot(&normal, &view_dir).abs();
-    let atmosphere_factor = fresnel.powf(4.0) * 0.3;
-    
-    let atmosphere_color = Color::new(200, 180, 160);
-    let final_color = if atmosphere_factor > 0.0 {
-        Color::new(
-            (surface_with_spot.r() as f32 * (1.0 - atmosphere_factor) + atmosphere_color.r() as f32 * atmosphere_factor) as u8,
-            (surface_with_spot.g() as f32 * (1.0 - atmosphere_factor) + atmosphere_color.g() as f32 * atmosphere_factor) as u8,
-            (surface_with_spot.b() as f32 * (1.0 - atmosphere_factor) + atmosphere_color.b() as f32 * atmosphere_factor) as u8,
-        )
-    } else {
-        surface_with_spot
-    };
-    
+    color = Color::lerp(&color, &style.haze, fresnel.powf(3.0) * style.haze_strength);
+
     let diffuse = dot(&normal, &light_dir).max(0.0) * 0.8;
-    let ambient = 0.3;
-    let intensity = (diffuse + ambient).min(1.0);
-    
-    Color::new(
-        (final_color.r() as f32 * intensity) as u8,
-        (final_color.g() as f32 * intensity) as u8,
-        (final_color.b() as f32 * intensity) as u8,
-    )
+    color.scale((diffuse + style.ambient).min(1.0))
 }
 
-pub fn mars_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
+pub fn venus_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32) -> Color {
     let sphere_pos = world_pos.normalize();
-    
-    // === CAPA 1: BASE MARCIANA (ÓXIDO DE HIERRO) ===
-    // Color base rojizo-anaranjado característico de Marte
-    let base_red = Color::new(193, 68, 14);      // Rojo óxido principal
-    let orange_tint = Color::new(220, 100, 40);  // Tonos anaranjados
-    
-    // Variación sutil de color base usando noise
-    let color_variation = fractal_noise(sphere_pos * 1.5, 3);
-    let base_color = if color_variation > 0.5 {
-        let t = ((color_variation - 0.5) * 2.0).min(0.4); // Limitamos la variación
-        Color::new(
-            (base_red.r() as f32 * (1.0 - t) + orange_tint.r() as f32 * t) as u8,
-            (base_red.g() as f32 * (1.0 - t) + orange_tint.g() as f32 * t) as u8,
-            (base_red.b() as f32 * (1.0 - t) + orange_tint.b() as f32 * t) as u8,
-        )
-    } else {
-        base_red
-    };
-    
-    // === CAPA 2: CASQUETES POLARES ===
-    let latitude = sphere_pos.y.abs();
-    let ice_factor = if latitude > 0.75 {
-        ((latitude - 0.75) / 0.25).clamp(0.0, 1.0) * 0.85
-    } else {
-        0.0
-    };
-    
-    // Hielo de CO2 y agua con tono ligeramente rosado
-    let ice_color = Color::new(245, 240, 235);
-    let terrain_with_ice = if ice_factor > 0.1 {
-        Color::new(
-            (base_color.r() as f32 * (1.0 - ice_factor) + ice_color.r() as f32 * ice_factor).clamp(0.0, 255.0) as u8,
-            (base_color.g() as f32 * (1.0 - ice_factor) + ice_color.g() as f32 * ice_factor).clamp(0.0, 255.0) as u8,
-            (base_color.b() as f32 * (1.0 - ice_factor) + ice_color.b() as f32 * ice_factor).clamp(0.0, 255.0) as u8,
-        )
-    } else {
-        base_color
-    };
-    
-    // === CAPA 3: CRÁTERES DE IMPACTO (más sutiles) ===
-    let crater_noise1 = fractal_noise(sphere_pos * 8.0, 3);
-    let crater_noise2 = fractal_noise(sphere_pos * 12.0 + Vec3::new(100.0, 100.0, 100.0), 2);
-    
-    let crater_factor = if crater_noise1 > 0.8 && crater_noise2 > 0.75 {
-        ((crater_noise1 - 0.8) / 0.2 * (crater_noise2 - 0.75) / 0.25) * 0.35
-    } else {
-        0.0
-    };
-    
-    // Color más oscuro para cráteres (basalto expuesto)
-    let crater_color = Color::new(120, 50, 30);
-    let terrain_with_craters = if crater_factor > 0.05 {
-        Color::new(
-            (terrain_with_ice.r() as f32 * (1.0 - crater_factor) + crater_color.r() as f32 * crater_factor) as u8,
-            (terrain_with_ice.g() as f32 * (1.0 - crater_factor) + crater_color.g() as f32 * crater_factor) as u8,
-            (terrain_with_ice.b() as f32 * (1.0 - crater_factor) + crater_color.b() as f32 * crater_factor) as u8,
-        )
-    } else {
-        terrain_with_ice
-    };
-    
-    // === CAPA 4: VALLES Y CAÑONES (VALLES MARINERIS) ===
-    // Gran sistema de cañones usando noise para hacerlo más natural
-    let canyon_base = fractal_noise(sphere_pos * 3.0, 4);
-    let canyon_mask = if latitude < 0.4 { // Solo cerca del ecuador
-        1.0
-    } else {
-        0.0
-    };
-    
-    let canyon_factor = if canyon_base > 0.65 && canyon_mask > 0.5 {
-        ((canyon_base - 0.65) / 0.35) * 0.4
-    } else {
-        0.0
-    };
-    
-    let canyon_color = Color::new(140, 55, 25);
-    let terrain_with_canyons = if canyon_factor > 0.1 {
-        Color::new(
-            (terrain_with_craters.r() as f32 * (1.0 - canyon_factor) + canyon_color.r() as f32 * canyon_factor) as u8,
-            (terrain_with_craters.g() as f32 * (1.0 - canyon_factor) + canyon_color.g() as f32 * canyon_factor) as u8,
-            (terrain_with_craters.b() as f32 * (1.0 - canyon_factor) + canyon_color.b() as f32 * canyon_factor) as u8,
-        )
-    } else {
-        terrain_with_craters
-    };
-    
-    // === CAPA 5: REGIONES VOLCÁNICAS OSCURAS ===
-    // Patrones de basalto volcánico usando noise
-    let volcanic_noise = fractal_noise(sphere_pos * 2.5, 4);
-    let volcanic_detail = fractal_noise(sphere_pos * 5.0 + Vec3::new(50.0, 50.0, 50.0), 2);
-    
-    let volcanic_factor = if volcanic_noise > 0.6 && volcanic_detail > 0.55 && ice_factor < 0.1 {
-        ((volcanic_noise - 0.6) / 0.4) * ((volcanic_detail - 0.55) / 0.45) * 0.5
-    } else {
-        0.0
-    };
-    
-    // Regiones oscuras de basalto
-    let volcanic_color = Color::new(100, 45, 25);
-    let terrain_with_volcanic = if volcanic_factor > 0.15 {
-        Color::new(
-            (terrain_with_canyons.r() as f32 * (1.0 - volcanic_factor) + volcanic_color.r() as f32 * volcanic_factor) as u8,
-            (terrain_with_canyons.g() as f32 * (1.0 - volcanic_factor) + volcanic_color.g() as f32 * volcanic_factor) as u8,
-            (terrain_with_canyons.b() as f32 * (1.0 - volcanic_factor) + volcanic_color.b() as f32 * volcanic_factor) as u8,
-        )
-    } else {
-        terrain_with_canyons
-    };
-    
-    // === CAPA 6: DUNAS Y CARACTERÍSTICAS DE ARENA ===
-    let dune_noise = fractal_noise(sphere_pos * 15.0, 3);
-    let dune_factor = if dune_noise > 0.7 {
-        ((dune_noise - 0.7) / 0.3) * 0.2
+
+    // === COLOR BASE: NUBES DE ÁCIDO SULFÚRICO ===
+    let cloud_light = Color::new(235, 215, 160); // Crema amarillento
+    let cloud_mid = Color::new(205, 170, 110);   // Ocre
+    let cloud_dark = Color::new(150, 115, 70);   // Sombras entre nubes
+
+    // Las nubes dan la vuelta al planeta mucho más rápido que la superficie
+    // (superrotación), así que el patrón se desplaza en longitud
+    let latitude = sphere_pos.y;
+    let longitude = sphere_pos.z.atan2(sphere_pos.x) + time * 0.15;
+
+    // === REMOLINOS DENSOS ===
+    let warp = fractal_noise(sphere_pos * 2.0 + Vec3::new(time * 0.03, 0.0, 0.0), 3);
+    let swirl = ((latitude * 6.0 + longitude.sin() * 1.5 + warp * 2.0).sin() * 0.5 + 0.5) * 0.6
+        + cloud_noise(sphere_pos * 2.5, time) * 0.4;
+
+    let mut base_color = if swirl < 0.5 {
+        Color::lerp(&cloud_mid, &cloud_light, swirl / 0.5)
     } else {
-        0.0
+        Color::lerp(&cloud_light, &cloud_mid, (swirl - 0.5) / 0.5 * 0.6)
     };
-    
-    let dune_color = Color::new(210, 95, 35);
-    let terrain_with_dunes = if dune_factor > 0.05 {
-        Color::new(
-            (terrain_with_volcanic.r() as f32 * (1.0 - dune_factor) + dune_color.r() as f32 * dune_factor) as u8,
-            (terrain_with_volcanic.g() as f32 * (1.0 - dune_factor) + dune_color.g() as f32 * dune_factor) as u8,
-            (terrain_with_volcanic.b() as f32 * (1.0 - dune_factor) + dune_color.b() as f32 * dune_factor) as u8,
-        )
+
+    // === PATRÓN EN "Y" ===
+    // Un tallo oscuro sobre el ecuador que se abre en dos brazos hacia
+    // ambos hemisferios, como se ve en ultravioleta
+    let wrapped = (longitude + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
+    let along = wrapped / std::f32::consts::PI; // -1..1 a lo largo del patrón
+    let distance_to_y = if along < 0.0 {
+        latitude.abs()
     } else {
-        terrain_with_volcanic
+        let arm = along * 0.6;
+        (latitude - arm).abs().min((latitude + arm).abs())
     };
-    
-    // === CAPA 7: TORMENTAS DE POLVO (SUTILES) ===
-    let dust_storm = fractal_noise(sphere_pos * 1.5 + Vec3::new(time * 0.03, 0.0, time * 0.02), 4);
-    let dust_factor = if dust_storm > 0.75 {
-        ((dust_storm - 0.75) / 0.25) * 0.2
+    let y_width = 0.12 + warp * 0.05;
+    let y_factor = if distance_to_y < y_width {
+        (1.0 - distance_to_y / y_width) * 0.45 * (1.0 - along.abs() * 0.5)
     } else {
         0.0
     };
-    
-    let dust_color = Color::new(210, 120, 70);
-    let final_color = if dust_factor > 0.08 {
-        Color::new(
-            (terrain_with_dunes.r() as f32 * (1.0 - dust_factor) + dust_color.r() as f32 * dust_factor) as u8,
-            (terrain_with_dunes.g() as f32 * (1.0 - dust_factor) + dust_color.g() as f32 * dust_factor) as u8,
-            (terrain_with_dunes.b() as f32 * (1.0 - dust_factor) + dust_color.b() as f32 * dust_factor) as u8,
-        )
-    } else {
-        terrain_with_dunes
-    };
-    
-    // === ILUMINACIÓN ===
-    let light_dir_normalized = light_dir.normalize();
-    let diffuse = dot(&normal, &light_dir_normalized).max(0.0) * 0.75;
-    let ambient = 0.35; // Marte tiene atmósfera tenue, menos luz ambiente
+    if y_factor > 0.0 {
+        base_color = Color::lerp(&base_color, &cloud_dark, y_factor);
+    }
+
+    // === ATMÓSFERA GRUESA ===
+    let fresnel = 1.0 - dot(&normal, &view_dir).abs();
+    let atmosphere_factor = fresnel.powf(2.0) * 0.5;
+    let atmosphere_color = Color::new(250, 235, 190);
+    let final_color = Color::lerp(&base_color, &atmosphere_color, atmosphere_factor);
+
+    // Las nubes dispersan mucha luz: el lado nocturno nunca queda negro
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.7;
+    let ambient = 0.35;
     let intensity = (diffuse + ambient).min(1.0);
-    
-    Color::new(
-        (final_color.r() as f32 * intensity).clamp(0.0, 255.0) as u8,
-        (final_color.g() as f32 * intensity).clamp(0.0, 255.0) as u8,
-        (final_color.b() as f32 * intensity).clamp(0.0, 255.0) as u8,
-    )
-}
 
+    final_color.scale(intensity)
+}
 
-pub fn mercury_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
-    let sphere_pos = world_pos.normalize();
+// === LUNAS ===
+
+// Cráteres sobre ruido celular: cada celda puede tener uno en su punto
+// característico, con el radio sacado de la celda. Los puntos se desplazan
+// poco del centro, así que el cráter (y un halo de hasta dos radios) nunca
+// pasa a otra celda y basta con mirar el punto más cercano.
+// Devuelve la distancia al centro en radios del cráter, o None si no hay.
+fn crater_cell(p: Vec3, density: f32) -> Option<f32> {
+    let cellular = worley_3d(p, CRATER_JITTER, DEFAULT_SEED);
+    if cell_random(cellular.cell, 0, DEFAULT_SEED) > density {
+        return None;
+    }
+    let radius = 0.08 + cell_random(cellular.cell, 1, DEFAULT_SEED) * 0.07;
+    Some(cellular.f1 / radius)
+}
 
-    // === Ruido base para grandes manchas ===
-    let base_noise = fractal_noise(sphere_pos * 1.5, 5); // frecuencia más baja → zonas grandes
-    let detail_noise = fractal_noise(sphere_pos * 10.0 + Vec3::new(time * 0.05, 0.0, 0.0), 3);
-    let combined_noise = (base_noise * 0.7 + detail_noise * 0.3).clamp(0.0, 1.0);
-
-    // === Paleta de colores estilo Mercurio ===
-    let dark_brown = Color::new(90, 50, 20);
-    let orange_brown = Color::new(180, 90, 40);
-    let bright_orange = Color::new(230, 140, 50);
-    let yellow_tone = Color::new(255, 190, 80);
-
-    // Mezcla progresiva entre tonos
-    let base_color = if combined_noise < 0.3 {
-        dark_brown
-    } else if combined_noise < 0.55 {
-        Color::new(
-            (dark_brown.r() as f32 * (1.0 - combined_noise) + orange_brown.r() as f32 * combined_noise) as u8,
-            (dark_brown.g() as f32 * (1.0 - combined_noise) + orange_brown.g() as f32 * combined_noise) as u8,
-            (dark_brown.b() as f32 * (1.0 - combined_noise) + orange_brown.b() as f32 * combined_noise) as u8,
-        )
-    } else if combined_noise < 0.8 {
-        Color::new(
-            (orange_brown.r() as f32 * (1.0 - combined_noise) + bright_orange.r() as f32 * combined_noise) as u8,
-            (orange_brown.g() as f32 * (1.0 - combined_noise) + bright_orange.g() as f32 * combined_noise) as u8,
-            (orange_brown.b() as f32 * (1.0 - combined_noise) + bright_orange.b() as f32 * combined_noise) as u8,
-        )
-    } else {
-        Color::new(
-            (bright_orange.r() as f32 * (1.0 - combined_noise) + yellow_tone.r() as f32 * combined_noise) as u8,
-            (bright_orange.g() as f32 * (1.0 - combined_noise) + yellow_tone.g() as f32 * combined_noise) as u8,
-            (bright_orange.b() as f32 * (1.0 - combined_noise) + yellow_tone.b() as f32 * combined_noise) as u8,
-        )
-    };
+// Altura de un cráter de `crater_cell` con la celda de tamaño 1 / `frequency`:
+// cuenco hundido con el borde levantado, y más hondo cuanto más grande
+fn crater_height(d: f32, frequency: f32) -> f32 {
+    let bowl = if d < 0.9 { ((d / 0.9).powi(2) - 1.0) * 0.6 } else { 0.0 };
+    let rim = (-((d - 0.9) / 0.15).powi(2)).exp() * 0.35;
+    // La profundidad es la mitad del radio medio de los cráteres
+    (bowl + rim) * CRATER_DEPTH / frequency
+}
 
-    // === Simulación de cráteres ===
-    let crater_noise = fractal_noise(sphere_pos * 20.0, 4);
-    let crater_factor = if crater_noise > 0.75 { (crater_noise - 0.75) * 3.0 } else { 0.0 };
-    let crater_color = Color::new(50, 30, 15);
-    let with_craters = Color::new(
-        (base_color.r() as f32 * (1.0 - crater_factor) + crater_color.r() as f32 * crater_factor) as u8,
-        (base_color.g() as f32 * (1.0 - crater_factor) + crater_color.g() as f32 * crater_factor) as u8,
-        (base_color.b() as f32 * (1.0 - crater_factor) + crater_color.b() as f32 * crater_factor) as u8,
-    );
-
-    // === Iluminación difusa + ambiente ===
-    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.85;
-    let ambient = 0.25;
-    let intensity = (diffuse + ambient).min(1.0);
+// Lunas sin aire que comparten el mismo shader: terreno oscuro y claro,
+// surcos, cráteres y relieve irregular según el estilo
+#[derive(Clone, Debug)]
+pub struct MoonStyle {
+    pub base: Color,
+    pub dark: Color,
+    pub bright: Color,
+    pub crater: Color,       // Fondo de los cráteres
+    pub dark_coverage: f32,  // Fracción de la superficie con terreno oscuro
+    pub crater_density: f32, // Probabilidad de cráter por celda
+    pub grooves: f32,        // Intensidad de los surcos paralelos
+    pub lumpiness: f32,      // Sombreado irregular de los cuerpos no esféricos
+    pub relief: f32,         // Relieve de cráteres y mares en la normal (0 = liso)
+    pub feature_scale: f32,  // Frecuencia de las regiones oscuras y claras
+    pub maria: Vec<Mare>,
+    pub ambient: f32,
+}
 
-    Color::new(
-        (with_craters.r() as f32 * intensity) as u8,
-        (with_craters.g() as f32 * intensity) as u8,
-        (with_craters.b() as f32 * intensity) as u8,
-    )
+// Mar de lava basáltica, pintado con el color oscuro del estilo
+#[derive(Clone, Debug)]
+pub struct Mare {
+    pub latitude_deg: f32,
+    pub longitude_deg: f32,
+    pub radius_deg: f32,
 }
 
-// Agrega esta función al final del archivo planet_shaders.rs
+// Los grandes mares de la cara visible de la Luna
+const LUNAR_MARIA: [Mare; 4] = [
+    Mare { latitude_deg: 8.5, longitude_deg: 31.4, radius_deg: 13.0 },   // Tranquilidad
+    Mare { latitude_deg: 32.8, longitude_deg: -15.6, radius_deg: 19.0 }, // Lluvias
+    Mare { latitude_deg: 28.0, longitude_deg: 17.5, radius_deg: 12.0 },  // Serenidad
+    Mare { latitude_deg: 18.0, longitude_deg: -57.0, radius_deg: 25.0 }, // Océano de las Tormentas
+];
+
+impl MoonStyle {
+    // Tierras altas claras con mares basálticos y cráteres de rayos brillantes
+    pub fn moon() -> Self {
+        MoonStyle {
+            base: Color::new(205, 205, 200),
+            dark: Color::new(95, 95, 95),
+            bright: Color::new(245, 245, 240),
+            crater: Color::new(240, 240, 235),
+            dark_coverage: 0.15,
+            crater_density: 0.35,
+            grooves: 0.0,
+            lumpiness: 0.0,
+            relief: 1.0,
+            feature_scale: 2.0,
+            maria: LUNAR_MARIA.to_vec(),
+            ambient: 0.45,
+        }
+    }
 
-pub fn moon_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
-    let sphere_pos = world_pos.normalize();
+    // Roca tostada y llena de cráteres oscuros
+    pub fn mercury() -> Self {
+        MoonStyle {
+            base: Color::new(180, 90, 40),
+            dark: Color::new(90, 50, 20),
+            bright: Color::new(230, 140, 50),
+            crater: Color::new(50, 30, 15),
+            dark_coverage: 0.35,
+            crater_density: 0.5,
+            grooves: 0.0,
+            lumpiness: 0.0,
+            relief: 1.0,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.25,
+        }
+    }
 
-    // === COLOR BLANCO PURO DE LA LUNA ===
-    let pure_white = Color::new(255, 255, 255);
-    let bright_white = Color::new(255, 255, 255);
-    let off_white = Color::new(250, 250, 245);
+    // Núcleo de cometa: el terreno de Mercurio casi sin color y cubierto de
+    // polvo oscuro; lo más claro son placas de hielo expuesto
+    pub fn comet_nucleus() -> Self {
+        MoonStyle {
+            base: Color::new(56, 48, 44),
+            dark: Color::new(29, 26, 23),
+            bright: Color::new(185, 200, 210),
+            crater: Color::new(17, 15, 14),
+            dark_coverage: 0.75,
+            lumpiness: 0.3,
+            ambient: 0.15,
+            ..MoonStyle::mercury()
+        }
+    }
 
-    // === MANCHAS NEGRAS CARACTERÍSTICAS (MARES LUNARES) ===
-    // Mares lunares principales - patrones grandes y reconocibles
-    let mare_noise1 = fractal_noise(sphere_pos * 2.0, 4);
-    let mare_noise2 = fractal_noise(sphere_pos * 4.0 + Vec3::new(50.0, 0.0, 50.0), 3);
-    let mare_noise3 = fractal_noise(sphere_pos * 8.0 + Vec3::new(100.0, 100.0, 0.0), 2);
-    
-    // Mare Tranquillitatis (Mar de la Tranquilidad) - grande y oscuro
-    let mare_tranq_center = Vec3::new(0.3, 0.1, 0.2);
-    let distance_to_tranq = (sphere_pos - mare_tranq_center).magnitude();
-    let mare_tranq_factor = if distance_to_tranq < 0.15 {
-        (1.0 - distance_to_tranq / 0.15) * 0.9
-    } else {
-        0.0
-    };
+    // Terreno oscuro antiguo cortado por franjas claras con surcos
+    pub fn ganymede() -> Self {
+        MoonStyle {
+            base: Color::new(150, 135, 115),
+            dark: Color::new(95, 85, 75),
+            bright: Color::new(190, 180, 165),
+            crater: Color::new(215, 210, 200),
+            dark_coverage: 0.45,
+            crater_density: 0.25,
+            grooves: 0.6,
+            lumpiness: 0.0,
+            relief: 0.5,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
 
-    // Mare Imbrium (Mar de las Lluvias) - grande y circular
-    let mare_imbrium_center = Vec3::new(-0.2, 0.05, 0.25);
-    let distance_to_imbrium = (sphere_pos - mare_imbrium_center).magnitude();
-    let mare_imbrium_factor = if distance_to_imbrium < 0.12 {
-        (1.0 - distance_to_imbrium / 0.12) * 0.85
-    } else {
-        0.0
-    };
+    // Oscura y saturada de cráteres, con hielo limpio en los impactos recientes
+    pub fn callisto() -> Self {
+        MoonStyle {
+            base: Color::new(95, 85, 70),
+            dark: Color::new(70, 62, 52),
+            bright: Color::new(130, 120, 105),
+            crater: Color::new(200, 195, 185),
+            dark_coverage: 0.6,
+            crater_density: 0.55,
+            grooves: 0.0,
+            lumpiness: 0.0,
+            relief: 0.7,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
 
-    // Mare Serenitatis (Mar de la Serenidad)
-    let mare_seren_center = Vec3::new(0.15, -0.08, 0.3);
-    let distance_to_seren = (sphere_pos - mare_seren_center).magnitude();
-    let mare_seren_factor = if distance_to_seren < 0.1 {
-        (1.0 - distance_to_seren / 0.1) * 0.8
-    } else {
-        0.0
-    };
+    pub fn phobos() -> Self {
+        MoonStyle {
+            base: Color::new(105, 95, 85),
+            dark: Color::new(80, 72, 65),
+            bright: Color::new(130, 120, 108),
+            crater: Color::new(70, 64, 58),
+            dark_coverage: 0.3,
+            crater_density: 0.45,
+            grooves: 0.3,
+            lumpiness: 0.35,
+            relief: 0.8,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
 
-    // Oceanus Procellarum (Océano de las Tormentas) - muy extenso
-    let ocean_proc_center = Vec3::new(-0.35, 0.0, 0.1);
-    let distance_to_ocean = (sphere_pos - ocean_proc_center).magnitude();
-    let ocean_proc_factor = if distance_to_ocean < 0.2 {
-        (1.0 - distance_to_ocean / 0.2) * 0.7
-    } else {
-        0.0
-    };
+    // Más lisa: el regolito rellena casi todos los cráteres
+    pub fn deimos() -> Self {
+        MoonStyle {
+            base: Color::new(140, 125, 105),
+            dark: Color::new(115, 102, 88),
+            bright: Color::new(160, 148, 130),
+            crater: Color::new(120, 108, 92),
+            dark_coverage: 0.2,
+            crater_density: 0.2,
+            grooves: 0.0,
+            lumpiness: 0.3,
+            relief: 0.4,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
 
-    // Manchas negras adicionales por noise
-    let dark_spot1 = if mare_noise1 > 0.7 {
-        ((mare_noise1 - 0.7) / 0.3) * 0.6
-    } else {
-        0.0
-    };
+    // Llanuras claras de nitrógeno y regiones rojizas de tolinas
+    pub fn pluto() -> Self {
+        MoonStyle {
+            base: Color::new(200, 170, 140),
+            dark: Color::new(120, 75, 55),
+            bright: Color::new(235, 225, 210),
+            crater: Color::new(180, 150, 125),
+            dark_coverage: 0.35,
+            crater_density: 0.2,
+            grooves: 0.0,
+            lumpiness: 0.0,
+            relief: 0.3,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
+
+    // Rocas del cinturón de asteroides
+    pub fn asteroid() -> Self {
+        MoonStyle {
+            base: Color::new(120, 110, 98),
+            dark: Color::new(85, 78, 70),
+            bright: Color::new(150, 140, 125),
+            crater: Color::new(75, 68, 60),
+            dark_coverage: 0.4,
+            crater_density: 0.5,
+            grooves: 0.0,
+            lumpiness: 0.2,
+            relief: 0.0, // Son muchas rocas pequeñas: sin relieve, más baratas
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
+
+    // Objetos del cinturón de Kuiper: hielo sucio enrojecido
+    pub fn kuiper() -> Self {
+        MoonStyle {
+            base: Color::new(170, 150, 140),
+            dark: Color::new(120, 95, 85),
+            bright: Color::new(210, 205, 200),
+            crater: Color::new(150, 130, 120),
+            dark_coverage: 0.4,
+            crater_density: 0.3,
+            grooves: 0.0,
+            lumpiness: 0.2,
+            relief: 0.0,
+            feature_scale: 1.5,
+            maria: Vec::new(),
+            ambient: 0.2,
+        }
+    }
+}
 
-    let dark_spot2 = if mare_noise2 > 0.75 {
-        ((mare_noise2 - 0.75) / 0.25) * 0.5
+pub fn rocky_moon_shader(world_pos: Vec3, normal: Vec3, to_world: &Mat3, light_dir: Vec3, style: &MoonStyle) -> Color {
+    let sphere_pos = world_pos.normalize();
+    let surface = rocky_moon_surface(sphere_pos, style);
+    rocky_moon_lighting(surface.albedo, bump::perturb(normal, sphere_pos, surface.normal, to_world), light_dir, style)
+}
+
+fn rocky_moon_surface(sphere_pos: Vec3, style: &MoonStyle) -> SurfaceSample {
+    // === TERRENO OSCURO Y CLARO ===
+    let region = fractal_noise(sphere_pos * style.feature_scale, 3);
+    let mut base_color = if region < style.dark_coverage {
+        Color::lerp(&style.dark, &style.base, region / style.dark_coverage.max(0.01))
     } else {
-        0.0
+        Color::lerp(&style.base, &style.bright, (region - style.dark_coverage) / (1.0 - style.dark_coverage).max(0.01))
     };
 
-    let dark_spot3 = if mare_noise3 > 0.8 {
-        ((mare_noise3 - 0.8) / 0.2) * 0.4
+    // === MARES ===
+    // Borde irregular y difuso para que no parezcan círculos
+    let edge = (fractal_noise(sphere_pos * 6.0, 3) - 0.5) * 0.3;
+    for mare in &style.maria {
+        let center = surface_direction(mare.latitude_deg, mare.longitude_deg);
+        let angle = dot(&sphere_pos, &center).clamp(-1.0, 1.0).acos();
+        let distance = angle / mare.radius_deg.to_radians() + edge;
+        if distance < 1.0 {
+            base_color = Color::lerp(&base_color, &style.dark, ((1.0 - distance) * 3.0).min(1.0) * 0.8);
+        }
+    }
+
+    // === SURCOS ===
+    // Franjas paralelas que cambian de dirección según la región
+    if style.grooves > 0.0 && region > style.dark_coverage {
+        let direction = Vec3::new(0.6 + region, 0.8, 0.3 - region).normalize();
+        let grooves = (dot(&sphere_pos, &direction) * 60.0).sin() * 0.5 + 0.5;
+        base_color = Color::lerp(&base_color, &style.bright, grooves * style.grooves * 0.5);
+    }
+
+    // === CRÁTERES ===
+    for frequency in [4.0, 9.0] {
+        if let Some(d) = crater_cell(sphere_pos * frequency, style.crater_density) {
+            if d < 0.8 {
+                base_color = Color::lerp(&base_color, &style.crater, 0.7 * (1.0 - d / 0.8));
+            } else if d < 1.0 {
+                // Borde levantado, más claro
+                base_color = Color::lerp(&base_color, &style.bright, 0.5);
+            }
+        }
+    }
+
+    // === RELIEVE IRREGULAR ===
+    // Los cuerpos no esféricos tienen zonas hundidas que reciben menos luz
+    let lumps = 1.0 - fractal_noise(sphere_pos * 2.5, 2) * style.lumpiness;
+
+    // === RELIEVE DE CRÁTERES Y MARES ===
+    let normal = if style.relief > 0.0 {
+        bump::height_normal(sphere_pos, style.relief, |p| rocky_moon_height(p, style))
     } else {
-        0.0
+        sphere_pos
     };
 
-    // Combinar todas las manchas negras
-    let total_dark_factor = (mare_tranq_factor + mare_imbrium_factor + mare_seren_factor + 
-                            ocean_proc_factor + dark_spot1 + dark_spot2 + dark_spot3).min(1.0);
+    SurfaceSample { albedo: base_color.scale(lumps), normal, specular: 0.0, lights: 0.0 }
+}
 
-    // === CRÁTERES BLANCOS SOBRE FONDO BLANCO (sutiles variaciones) ===
-    let crater_noise1 = fractal_noise(sphere_pos * 10.0, 3);
-    let crater_noise2 = fractal_noise(sphere_pos * 20.0 + Vec3::new(200.0, 0.0, 200.0), 2);
-    
-    let crater_factor = if crater_noise1 > 0.8 && crater_noise2 > 0.7 {
-        ((crater_noise1 - 0.8) / 0.2 * (crater_noise2 - 0.7) / 0.3) * 0.3
+// Altura para el relieve: los mares son llanuras hundidas con un escalón en
+// el borde y los cráteres, cuencos con el borde levantado
+fn rocky_moon_height(sphere_pos: Vec3, style: &MoonStyle) -> f32 {
+    let mut height = 0.0;
+
+    // Mismo borde irregular que el color
+    let edge = (fractal_noise(sphere_pos * 6.0, 3) - 0.5) * 0.3;
+    for mare in &style.maria {
+        let center = surface_direction(mare.latitude_deg, mare.longitude_deg);
+        let angle = dot(&sphere_pos, &center).clamp(-1.0, 1.0).acos();
+        let distance = angle / mare.radius_deg.to_radians() + edge;
+        height -= ((1.0 - distance) * 3.0).clamp(0.0, 1.0) * 0.015;
+    }
+
+    for frequency in [4.0, 9.0] {
+        if let Some(d) = crater_cell(sphere_pos * frequency, style.crater_density) {
+            height += crater_height(d, frequency);
+        }
+    }
+    height
+}
+
+// === ILUMINACIÓN SIN ATMÓSFERA ===
+// Terminador marcado y poca luz ambiente
+fn rocky_moon_lighting(albedo: Color, normal: Vec3, light_dir: Vec3, style: &MoonStyle) -> Color {
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.9;
+    albedo.scale((diffuse + style.ambient).min(1.0))
+}
+
+pub fn io_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
+    let sphere_pos = world_pos.normalize();
+
+    // === AZUFRE ===
+    let sulfur_yellow = Color::new(225, 205, 95);
+    let sulfur_orange = Color::new(210, 145, 65);
+    let frost_white = Color::new(235, 230, 200); // Escarcha de dióxido de azufre
+    let plume_red = Color::new(120, 45, 25);
+    let caldera_black = Color::new(30, 22, 15);
+    let lava_glow = Color::new(255, 120, 30);
+
+    let patches = fractal_noise(sphere_pos * 2.0, 3);
+    let mut base_color = if patches < 0.5 {
+        Color::lerp(&sulfur_orange, &sulfur_yellow, patches / 0.5)
     } else {
-        0.0
+        Color::lerp(&sulfur_yellow, &frost_white, (patches - 0.5) / 0.5 * 0.7)
     };
 
-    // === CONSTRUCCIÓN DEL COLOR FINAL ===
-    
-    // Base blanca pura
-    let mut base_color = pure_white;
-
-    // Aplicar variaciones sutiles de blanco para los cráteres
-    if crater_factor > 0.05 {
-        let crater_color = Color::new(245, 245, 240); // Blanco ligeramente más oscuro
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - crater_factor) + crater_color.r() as f32 * crater_factor) as u8,
-            (base_color.g() as f32 * (1.0 - crater_factor) + crater_color.g() as f32 * crater_factor) as u8,
-            (base_color.b() as f32 * (1.0 - crater_factor) + crater_color.b() as f32 * crater_factor) as u8,
-        );
-    }
-
-    // Aplicar manchas negras (mares lunares)
-    if total_dark_factor > 0.1 {
-        let mare_color = Color::new(80, 80, 80); // Gris muy oscuro, casi negro
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - total_dark_factor) + mare_color.r() as f32 * total_dark_factor) as u8,
-            (base_color.g() as f32 * (1.0 - total_dark_factor) + mare_color.g() as f32 * total_dark_factor) as u8,
-            (base_color.b() as f32 * (1.0 - total_dark_factor) + mare_color.b() as f32 * total_dark_factor) as u8,
-        );
-    }
-
-    // === VARIACIÓN DE TONOS SUTILES ===
-    let tone_variation = fractal_noise(sphere_pos * 1.5, 2);
-    let final_base_color = if tone_variation > 0.6 {
-        // Áreas ligeramente más cálidas
-        let warm_white = Color::new(255, 253, 248);
-        let t = (tone_variation - 0.6) / 0.4;
-        Color::new(
-            (base_color.r() as f32 * (1.0 - t) + warm_white.r() as f32 * t) as u8,
-            (base_color.g() as f32 * (1.0 - t) + warm_white.g() as f32 * t) as u8,
-            (base_color.b() as f32 * (1.0 - t) + warm_white.b() as f32 * t) as u8,
-        )
+    // === VOLCANES ===
+    // Caldera negra rodeada de un anillo rojizo de material de las plumas
+    let mut lava: f32 = 0.0;
+    for (frequency, density) in [(3.0, 0.35), (6.0, 0.2)] {
+        if let Some(d) = crater_cell(sphere_pos * frequency, density) {
+            if d < 0.6 {
+                base_color = Color::lerp(&base_color, &caldera_black, 0.9);
+                // Lava que late; cada volcán con su propio ritmo
+                let pulse = (time * 2.0 + frequency * d * 5.0).sin() * 0.5 + 0.5;
+                lava = lava.max((1.0 - d / 0.6) * (0.4 + pulse * 0.6));
+            } else if d < 2.0 {
+                base_color = Color::lerp(&base_color, &plume_red, (1.0 - (d - 0.6) / 1.4) * 0.6);
+            }
+        }
+    }
+
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.9;
+    let ambient = 0.2;
+    let intensity = (diffuse + ambient).min(1.0);
+
+    // La lava brilla con luz propia, también en el lado nocturno
+    Color::lerp(&base_color.scale(intensity), &lava_glow, lava)
+}
+
+pub fn europa_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, _time: f32) -> Color {
+    let sphere_pos = world_pos.normalize();
+
+    // === HIELO ===
+    let ice = Color::new(230, 222, 205);
+    let chaos_brown = Color::new(170, 125, 95); // Terreno caótico con sales
+    let lineae_red = Color::new(150, 75, 50);
+
+    let chaos = fractal_noise(sphere_pos * 1.8, 3);
+    let mut base_color = if chaos > 0.65 {
+        Color::lerp(&ice, &chaos_brown, (chaos - 0.65) / 0.35 * 0.6)
     } else {
-        base_color
+        ice
     };
 
+    // === GRIETAS (LINEAE) ===
+    // Grandes círculos que se cruzan por todo el hielo; cada uno es el
+    // conjunto de puntos casi perpendiculares a su normal
+    let planes = [
+        Vec3::new(0.9, 0.3, 0.1),
+        Vec3::new(-0.2, 0.8, 0.5),
+        Vec3::new(0.4, -0.3, 0.9),
+        Vec3::new(0.7, 0.6, -0.4),
+        Vec3::new(-0.5, 0.1, 0.8),
+        Vec3::new(0.1, 0.9, -0.6),
+    ];
+    let wobble = fractal_noise(sphere_pos * 6.0, 2) * 0.02;
+    for (i, plane) in planes.iter().enumerate() {
+        let distance = (dot(&sphere_pos, &plane.normalize()) + wobble).abs();
+        let width = 0.012 + (i % 3) as f32 * 0.006;
+        if distance < width {
+            base_color = Color::lerp(&base_color, &lineae_red, (1.0 - distance / width) * 0.8);
+        }
+    }
+
+    // Pocos cráteres: la superficie es muy joven
+    if let Some(d) = crater_cell(sphere_pos * 5.0, 0.05)
+        && d < 1.0
+    {
+        base_color = Color::lerp(&base_color, &chaos_brown, (1.0 - d) * 0.5);
+    }
+
     // === ILUMINACIÓN ===
+    // Hielo muy reflectante con un brillo especular suave
     let light_dir_normalized = light_dir.normalize();
-    let diffuse = dot(&normal, &light_dir_normalized).max(0.0) * 1.1; // Alto albedo lunar
-    let ambient = 0.6; // Buena reflectividad incluso en áreas oscuras
-    let intensity = (diffuse + ambient).min(1.2);
-
-    let illuminated_color = Color::new(
-        (final_base_color.r() as f32 * intensity).min(255.0) as u8,
-        (final_base_color.g() as f32 * intensity).min(255.0) as u8,
-        (final_base_color.b() as f32 * intensity).min(255.0) as u8,
-    );
-
-    // === EFECTO DE BRILLO ESPECULAR EN CRÁTERES ===
-    let view_dir = Vec3::new(0.0, 0.0, 1.0);
+    let diffuse = dot(&normal, &light_dir_normalized).max(0.0) * 0.9;
+    let ambient = 0.25;
+    let intensity = (diffuse + ambient).min(1.0);
+
     let half_vector = (light_dir_normalized + view_dir).normalize();
-    let specular = dot(&normal, &half_vector).max(0.0).powf(32.0) * 0.3;
+    let specular = dot(&normal, &half_vector).max(0.0).powf(24.0) * 0.25;
 
-    if specular > 0.1 {
-        // Añadir brillo especular en áreas muy iluminadas
-        Color::new(
-            (illuminated_color.r() as f32 + 20.0 * specular).min(255.0) as u8,
-            (illuminated_color.g() as f32 + 20.0 * specular).min(255.0) as u8,
-            (illuminated_color.b() as f32 + 20.0 * specular).min(255.0) as u8,
-        )
-    } else {
-        illuminated_color
-    }
+    Color::lerp(&base_color.scale(intensity), &Color::new(255, 255, 255), specular)
 }
 
-pub fn neptune_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, time: f32) -> Color {
+pub fn titan_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, time: f32) -> Color {
     let sphere_pos = world_pos.normalize();
 
-    // === COLOR BASE AZUL PROFUNDO DE NEPTUNO ===
-    let deep_blue = Color::new(30, 60, 150);    // Azul muy oscuro
-    let bright_blue = Color::new(80, 120, 220); // Azul brillante para detalles
-    let methane_blue = Color::new(40, 100, 200); // Tonos de metano
+    // === NEBLINA NARANJA ===
+    let haze_orange = Color::new(215, 145, 55);
+    let haze_dark = Color::new(165, 100, 40);
+    let polar_hood = Color::new(140, 90, 45);
+    let blue_haze = Color::new(150, 175, 210); // Capa alta separada, visible en el borde
 
-    // === PATRONES DE BANDAS ATMOSFÉRICAS ===
-    let latitude = sphere_pos.y;
-    let band_pattern = (latitude * 12.0 + time * 0.1).sin() * 0.5 + 0.5;
-    
-    // Bandas más pronunciadas cerca del ecuador
-    let equator_mask = (1.0 - latitude.abs() * 2.0).max(0.0);
-    let band_intensity = band_pattern * equator_mask * 0.7;
+    // Apenas se adivinan las dunas oscuras de la superficie: crestas largas
+    // de este a oeste, por eso el ruido se estira en longitud
+    let surface = ridged_3d(Vec3::new(sphere_pos.x * 2.0, sphere_pos.y * 9.0, sphere_pos.z * 2.0), 3, DEFAULT_SEED);
+    let mut base_color = Color::lerp(&haze_orange, &haze_dark, surface * 0.25);
 
-    // === TORMENTAS Y REMOLINOS CARACTERÍSTICOS ===
-    let storm_noise1 = fractal_noise(sphere_pos * 3.0 + Vec3::new(time * 0.05, 0.0, 0.0), 4);
-    let storm_noise2 = fractal_noise(sphere_pos * 8.0 + Vec3::new(0.0, time * 0.03, 0.0), 3);
-    
-    // Gran Mancha Oscura (simulada)
-    let dark_spot_center = Vec3::new(0.4, 0.2, 0.0);
-    let distance_to_dark_spot = (sphere_pos - dark_spot_center).magnitude();
-    let dark_spot_factor = if distance_to_dark_spot < 0.25 {
-        (1.0 - distance_to_dark_spot / 0.25) * 0.8
-    } else {
-        0.0
-    };
+    // Bandas de neblina muy suaves
+    let bands = (sphere_pos.y * 5.0 + time * 0.02).sin() * 0.5 + 0.5;
+    base_color = Color::lerp(&base_color, &haze_dark, bands * 0.1);
 
-    // Manchas blancas (nubes de metano)
-    let white_spot_factor = if storm_noise1 > 0.75 {
-        ((storm_noise1 - 0.75) / 0.25) * 0.6
-    } else {
-        0.0
-    };
-
-    // Remolinos atmosféricos
-    let swirl_factor = if storm_noise2 > 0.7 {
-        ((storm_noise2 - 0.7) / 0.3) * 0.4
-    } else {
-        0.0
-    };
-
-    // === TEXTURA DE NUBES DE METANO CRISTALIZADO ===
-    let cloud_detail1 = fractal_noise(sphere_pos * 6.0, 3);
-    let cloud_detail2 = fractal_noise(sphere_pos * 12.0 + Vec3::new(time * 0.02, 0.0, 0.0), 2);
-    let methane_clouds = (cloud_detail1 * 0.7 + cloud_detail2 * 0.3) * 0.4;
+    // Capucha polar más oscura en el norte
+    if sphere_pos.y > 0.7 {
+        base_color = Color::lerp(&base_color, &polar_hood, (sphere_pos.y - 0.7) / 0.3 * 0.6);
+    }
 
-    // === CONSTRUCCIÓN DEL COLOR FINAL ===
-    
-    // Base azul profunda
-    let mut base_color = deep_blue;
-
-    // Añadir variación de color por bandas
-    if band_intensity > 0.1 {
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - band_intensity) + bright_blue.r() as f32 * band_intensity) as u8,
-            (base_color.g() as f32 * (1.0 - band_intensity) + bright_blue.g() as f32 * band_intensity) as u8,
-            (base_color.b() as f32 * (1.0 - band_intensity) + bright_blue.b() as f32 * band_intensity) as u8,
-        );
-    }
-
-    // Añadir nubes de metano
-    if methane_clouds > 0.2 {
-        let cloud_blend = (methane_clouds - 0.2) / 0.8;
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - cloud_blend) + methane_blue.r() as f32 * cloud_blend) as u8,
-            (base_color.g() as f32 * (1.0 - cloud_blend) + methane_blue.g() as f32 * cloud_blend) as u8,
-            (base_color.b() as f32 * (1.0 - cloud_blend) + methane_blue.b() as f32 * cloud_blend) as u8,
-        );
-    }
-
-    // Añadir Gran Mancha Oscura
-    if dark_spot_factor > 0.1 {
-        let dark_spot_color = Color::new(20, 40, 100);
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - dark_spot_factor) + dark_spot_color.r() as f32 * dark_spot_factor) as u8,
-            (base_color.g() as f32 * (1.0 - dark_spot_factor) + dark_spot_color.g() as f32 * dark_spot_factor) as u8,
-            (base_color.b() as f32 * (1.0 - dark_spot_factor) + dark_spot_color.b() as f32 * dark_spot_factor) as u8,
-        );
-    }
-
-    // Añadir manchas blancas (nubes brillantes)
-    if white_spot_factor > 0.1 {
-        let white_cloud_color = Color::new(200, 220, 255);
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - white_spot_factor) + white_cloud_color.r() as f32 * white_spot_factor) as u8,
-            (base_color.g() as f32 * (1.0 - white_spot_factor) + white_cloud_color.g() as f32 * white_spot_factor) as u8,
-            (base_color.b() as f32 * (1.0 - white_spot_factor) + white_cloud_color.b() as f32 * white_spot_factor) as u8,
-        );
-    }
-
-    // Añadir remolinos
-    if swirl_factor > 0.1 {
-        let swirl_color = Color::new(60, 90, 180);
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - swirl_factor) + swirl_color.r() as f32 * swirl_factor) as u8,
-            (base_color.g() as f32 * (1.0 - swirl_factor) + swirl_color.g() as f32 * swirl_factor) as u8,
-            (base_color.b() as f32 * (1.0 - swirl_factor) + swirl_color.b() as f32 * swirl_factor) as u8,
-        );
-    }
-
-    // === ANILLOS SUTILES (simulados) ===
-    let ring_mask = (1.0 - latitude.abs() * 8.0).max(0.0).powf(2.0);
-    let ring_pattern = (sphere_pos.x * 20.0 + time * 0.1).sin().abs();
-    let ring_factor = ring_mask * ring_pattern * 0.15;
-
-    if ring_factor > 0.05 {
-        let ring_color = Color::new(70, 100, 180);
-        base_color = Color::new(
-            (base_color.r() as f32 * (1.0 - ring_factor) + ring_color.r() as f32 * ring_factor) as u8,
-            (base_color.g() as f32 * (1.0 - ring_factor) + ring_color.g() as f32 * ring_factor) as u8,
-            (base_color.b() as f32 * (1.0 - ring_factor) + ring_color.b() as f32 * ring_factor) as u8,
-        );
-    }
-
-    // === EFECTO DE ATMÓSFERA PROFUNDA ===
-    let view_dir = Vec3::new(0.0, 0.0, 1.0);
+    // === ATMÓSFERA ===
     let fresnel = 1.0 - dot(&normal, &view_dir).abs();
-    let atmosphere_glow = fresnel.powf(3.0) * 0.4;
-    
-    let atmosphere_color = Color::new(100, 150, 255);
-    let final_color = if atmosphere_glow > 0.0 {
-        Color::new(
-            (base_color.r() as f32 * (1.0 - atmosphere_glow) + atmosphere_color.r() as f32 * atmosphere_glow) as u8,
-            (base_color.g() as f32 * (1.0 - atmosphere_glow) + atmosphere_color.g() as f32 * atmosphere_glow) as u8,
-            (base_color.b() as f32 * (1.0 - atmosphere_glow) + atmosphere_color.b() as f32 * atmosphere_glow) as u8,
-        )
-    } else {
-        base_color
-    };
+    let final_color = Color::lerp(&base_color, &blue_haze, fresnel.powf(3.0) * 0.5);
 
-    
-    let light_dir_normalized = light_dir.normalize();
-    let diffuse = dot(&normal, &light_dir_normalized).max(0.0) * 0.6; // Menos luz directa
-    let ambient = 0.4; // Más luz ambiente por la dispersión atmosférica
+    // La neblina dispersa la luz hacia el lado nocturno
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.7;
+    let ambient = 0.35;
     let intensity = (diffuse + ambient).min(1.0);
 
-    Color::new(
-        (final_color.r() as f32 * intensity).clamp(0.0, 255.0) as u8,
-        (final_color.g() as f32 * intensity).clamp(0.0, 255.0) as u8,
-        (final_color.b() as f32 * intensity).clamp(0.0, 255.0) as u8,
-    )
+    final_color.scale(intensity)
 }
 
+pub fn triton_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, view_dir: Vec3, _time: f32) -> Color {
+    let sphere_pos = world_pos.normalize();
+
+    // === HIELO DE NITRÓGENO ===
+    let nitrogen_ice = Color::new(220, 205, 195);
+    let cantaloupe = Color::new(175, 160, 140);
+    let polar_pink = Color::new(235, 190, 180);
+    let plume_dark = Color::new(90, 70, 65);
 
+    let mut base_color = nitrogen_ice;
 
+    // Terreno "de melón" en el norte: hoyuelos muy apretados
+    if sphere_pos.y > -0.2 {
+        let dimples = crater_cell(sphere_pos * 14.0, 0.8).map_or(0.0, |d| (1.0 - d).max(0.0));
+        base_color = Color::lerp(&base_color, &cantaloupe, 0.35 + dimples * 0.4);
+    }
+
+    // === CASQUETE POLAR SUR ===
+    if sphere_pos.y < -0.3 {
+        let cap = ((-sphere_pos.y - 0.3) / 0.3).min(1.0);
+        base_color = Color::lerp(&base_color, &polar_pink, cap);
+
+        // Rastros oscuros de los géiseres, estirados por el viento en la
+        // misma dirección
+        let streaks = fractal_noise(Vec3::new(sphere_pos.x * 3.0, sphere_pos.y * 3.0, sphere_pos.z * 14.0), 2);
+        if streaks > 0.75 {
+            base_color = Color::lerp(&base_color, &plume_dark, (streaks - 0.75) / 0.25 * 0.7 * cap);
+        }
+    }
+
+    // === ATMÓSFERA TENUE ===
+    let fresnel = 1.0 - dot(&normal, &view_dir).abs();
+    let final_color = Color::lerp(&base_color, &Color::new(200, 210, 235), fresnel.powf(5.0) * 0.2);
+
+    let diffuse = dot(&normal, &light_dir).max(0.0) * 0.9;
+    let ambient = 0.25;
+    let intensity = (diffuse + ambient).min(1.0);
+
+    final_color.scale(intensity)
+}
 
 // Shader para la nave espacial
 pub fn spaceship_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, _time: f32) -> Color {
@@ -884,4 +1263,4 @@ pub fn spaceship_shader(world_pos: Vec3, normal: Vec3, light_dir: Vec3, _time: f
         (final_color.g() as f32 * intensity).clamp(0.0, 255.0) as u8,
         (final_color.b() as f32 * intensity).clamp(0.0, 255.0) as u8,
     )
-}
\ No newline at end of file
+}
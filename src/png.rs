@@ -0,0 +1,123 @@
+// Escritura mínima de PNG RGBA de 8 bits sin dependencias. Los datos van en
+// bloques deflate sin comprimir: los archivos son más grandes, pero
+// cualquier visor o editor los abre.
+
+use std::fs;
+use std::io;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+// Tamaño máximo de un bloque deflate sin comprimir
+const STORED_BLOCK: usize = 65_535;
+
+// `pixels` son filas de arriba abajo con 4 bytes por píxel
+pub fn write_rgba(filename: &str, width: usize, height: usize, pixels: &[u8]) -> io::Result<()> {
+    assert_eq!(pixels.len(), width * height * 4, "el tamaño de la imagen no cuadra");
+
+    // Cada fila empieza con el tipo de filtro (0 = ninguno)
+    let mut raw = Vec::with_capacity((width * 4 + 1) * height);
+    for row in pixels.chunks_exact(width * 4) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]); // 8 bits, RGBA, deflate, sin filtro adaptativo, sin entrelazado
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    fs::write(filename, png)
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// Flujo zlib con bloques deflate sin comprimir
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / STORED_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&length.to_le_bytes());
+        out.extend_from_slice(&(!length).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65_521;
+        b %= 65_521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+        // El CRC de un IEND vacío es fijo en todos los PNG
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(crc32(&[0xFF; 100_000]), 0x68C6_CEC4);
+    }
+
+    #[test]
+    fn adler32_matches_known_values() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(adler32(b"123456789"), 0x091E_01DE);
+        // Varios tramos de 5552 bytes: las sumas se reducen sin desbordar
+        assert_eq!(adler32(&[0xFF; 100_000]), 0x149A_302C);
+    }
+
+    #[test]
+    fn zlib_stream_splits_stored_blocks() {
+        let data = vec![7u8; STORED_BLOCK + 10];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + (5 + STORED_BLOCK) + (5 + 10) + 4);
+        assert_eq!(&stream[2..7], &[0, 0xFF, 0xFF, 0, 0]);
+        let second = 7 + STORED_BLOCK;
+        assert_eq!(&stream[second..second + 5], &[1, 10, 0, 0xF5, 0xFF]);
+        assert_eq!(stream[stream.len() - 4..], adler32(&data).to_be_bytes());
+        assert_eq!(zlib_stored(&[]), [0x78, 0x01, 1, 0, 0, 0xFF, 0xFF, 0, 0, 0, 1]);
+    }
+}
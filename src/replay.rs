@@ -0,0 +1,458 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use nalgebra_glm::Vec3;
+use crate::input::InputFrame;
+use crate::clock::SimClock;
+use crate::mouse::MouseFrame;
+use crate::simulation::Simulation;
+
+// Formato del archivo (little endian):
+//   cabecera: "SSREC" + versión (u8)
+//   estado inicial: tick (u64), tiempo del reloj (f64),
+//                   posición, rotación y velocidad de la nave (9 x f32),
+//                   escala de tiempo (u8), banderas (u8: pausado, órbitas, reversa, semilla),
+//                   semilla del sistema generado (u64, solo si está la bandera)
+//   entrada: bloques de (repeticiones u16, frame), frames iguales seguidos se agrupan
+const MAGIC: &[u8; 5] = b"SSREC";
+const VERSION: u8 = 3;
+
+// Banderas del ratón en cada frame
+const MOUSE_RIGHT_DOWN: u8 = 1;
+const MOUSE_CLICK: u8 = 2;
+const MOUSE_MOTION: u8 = 4;
+
+// Banderas del estado inicial
+const FLAG_PAUSED: u8 = 1;
+const FLAG_SHOW_ORBITS: u8 = 2;
+const FLAG_REVERSED: u8 = 4;
+const FLAG_SYSTEM_SEED: u8 = 8;
+
+// Estado de la simulación al empezar a grabar
+pub struct InitialState {
+    pub tick: u64,
+    pub time: f64,
+    pub position: Vec3,
+    pub rotation: Vec3,
+    pub velocity: Vec3,
+    pub preset: u8,
+    pub paused: bool,
+    pub reversed: bool,
+    pub show_orbits: bool,
+    pub system_seed: Option<u64>,
+}
+
+impl InitialState {
+    pub fn capture(sim: &Simulation) -> Self {
+        InitialState {
+            tick: sim.tick,
+            time: sim.clock.time_f64(),
+            position: sim.ship.position,
+            rotation: sim.ship.rotation,
+            velocity: sim.ship.velocity,
+            preset: sim.clock.preset() as u8,
+            paused: sim.clock.is_paused(),
+            reversed: sim.clock.is_reversed(),
+            show_orbits: sim.show_orbits,
+            system_seed: sim.system_seed,
+        }
+    }
+
+    pub fn apply(&self, sim: &mut Simulation) {
+        sim.tick = self.tick;
+        sim.ship.position = self.position;
+        sim.ship.rotation = self.rotation;
+        sim.ship.velocity = self.velocity;
+        sim.ship.chase_position = sim.ship.get_chase_anchor();
+        sim.ship.chase_velocity = self.velocity;
+        sim.clock = SimClock::from_parts(self.time, self.preset as usize, self.reversed, self.paused);
+        sim.show_orbits = self.show_orbits;
+    }
+}
+
+// Graba la entrada tick a tick
+pub struct Recorder {
+    initial: InitialState,
+    runs: Vec<(u16, InputFrame)>,
+}
+
+impl Recorder {
+    pub fn new(sim: &Simulation) -> Self {
+        Recorder {
+            initial: InitialState::capture(sim),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, frame: &InputFrame) {
+        if let Some((count, last)) = self.runs.last_mut()
+            && last == frame
+            && *count < u16::MAX
+        {
+            *count += 1;
+            return;
+        }
+        self.runs.push((1, frame.clone()));
+    }
+
+    pub fn tick_count(&self) -> usize {
+        self.runs.iter().map(|(count, _)| *count as usize).sum()
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut out = BufWriter::new(File::create(filename)?);
+
+        out.write_all(MAGIC)?;
+        out.write_all(&[VERSION])?;
+
+        let initial = &self.initial;
+        out.write_all(&initial.tick.to_le_bytes())?;
+        out.write_all(&initial.time.to_le_bytes())?;
+        for v in [initial.position, initial.rotation, initial.velocity] {
+            write_vec3(&mut out, v)?;
+        }
+        out.write_all(&[initial.preset])?;
+        let mut flags = 0;
+        for (set, flag) in [
+            (initial.paused, FLAG_PAUSED),
+            (initial.show_orbits, FLAG_SHOW_ORBITS),
+            (initial.reversed, FLAG_REVERSED),
+            (initial.system_seed.is_some(), FLAG_SYSTEM_SEED),
+        ] {
+            if set {
+                flags |= flag;
+            }
+        }
+        out.write_all(&[flags])?;
+        if let Some(seed) = initial.system_seed {
+            out.write_all(&seed.to_le_bytes())?;
+        }
+
+        for (count, frame) in &self.runs {
+            out.write_all(&count.to_le_bytes())?;
+            write_frame(&mut out, frame)?;
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+}
+
+// Reproduce una grabación: entrega los frames en el mismo orden
+pub struct Replay {
+    pub initial: InitialState,
+    runs: Vec<(u16, InputFrame)>,
+    run_index: usize,
+    repeat: u16,
+}
+
+impl Replay {
+    pub fn load(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut data = Vec::new();
+        BufReader::new(File::open(filename)?).read_to_end(&mut data)?;
+        let mut reader = ByteReader { data: &data, offset: 0 };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err("no es un archivo de grabación".into());
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(format!("versión de grabación no soportada: {}", version).into());
+        }
+
+        let tick = u64::from_le_bytes(reader.take(8)?.try_into()?);
+        let time = f64::from_le_bytes(reader.take(8)?.try_into()?);
+        let position = reader.vec3()?;
+        let rotation = reader.vec3()?;
+        let velocity = reader.vec3()?;
+        let preset = reader.u8()?;
+        let flags = reader.u8()?;
+        let system_seed = if flags & FLAG_SYSTEM_SEED != 0 {
+            Some(u64::from_le_bytes(reader.take(8)?.try_into()?))
+        } else {
+            None
+        };
+        let initial = InitialState {
+            tick,
+            time,
+            position,
+            rotation,
+            velocity,
+            preset,
+            paused: flags & FLAG_PAUSED != 0,
+            reversed: flags & FLAG_REVERSED != 0,
+            show_orbits: flags & FLAG_SHOW_ORBITS != 0,
+            system_seed,
+        };
+
+        let mut runs = Vec::new();
+        while !reader.is_empty() {
+            let count = u16::from_le_bytes(reader.take(2)?.try_into()?);
+            runs.push((count, read_frame(&mut reader)?));
+        }
+
+        Ok(Replay { initial, runs, run_index: 0, repeat: 0 })
+    }
+
+    pub fn tick_count(&self) -> usize {
+        self.runs.iter().map(|(count, _)| *count as usize).sum()
+    }
+}
+
+impl Iterator for Replay {
+    type Item = InputFrame;
+
+    fn next(&mut self) -> Option<InputFrame> {
+        let (count, frame) = self.runs.get(self.run_index)?;
+        let frame = frame.clone();
+        self.repeat += 1;
+        if self.repeat >= *count {
+            self.run_index += 1;
+            self.repeat = 0;
+        }
+        Some(frame)
+    }
+}
+
+fn write_vec3(out: &mut impl Write, v: Vec3) -> std::io::Result<()> {
+    out.write_all(&v.x.to_le_bytes())?;
+    out.write_all(&v.y.to_le_bytes())?;
+    out.write_all(&v.z.to_le_bytes())
+}
+
+fn write_frame(out: &mut impl Write, frame: &InputFrame) -> std::io::Result<()> {
+    let (held, pressed, axes) = frame.raw();
+    out.write_all(&held.to_le_bytes())?;
+    out.write_all(&pressed.to_le_bytes())?;
+    for axis in axes {
+        out.write_all(&[(axis.round() as i8) as u8])?;
+    }
+
+    let mouse = &frame.mouse;
+    let moved = mouse.dx != 0.0 || mouse.dy != 0.0 || mouse.scroll != 0.0;
+    let mut flags = 0;
+    if mouse.right_down {
+        flags |= MOUSE_RIGHT_DOWN;
+    }
+    if mouse.click.is_some() {
+        flags |= MOUSE_CLICK;
+    }
+    if moved {
+        flags |= MOUSE_MOTION;
+    }
+    out.write_all(&[flags])?;
+
+    if moved {
+        out.write_all(&mouse.dx.to_le_bytes())?;
+        out.write_all(&mouse.dy.to_le_bytes())?;
+        out.write_all(&mouse.scroll.to_le_bytes())?;
+    }
+    if let Some((x, y)) = mouse.click {
+        out.write_all(&x.to_le_bytes())?;
+        out.write_all(&y.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_frame(reader: &mut ByteReader) -> Result<InputFrame, Box<dyn std::error::Error>> {
+    let held = u32::from_le_bytes(reader.take(4)?.try_into()?);
+    let pressed = u32::from_le_bytes(reader.take(4)?.try_into()?);
+    let mut axes = [0.0; 5];
+    for axis in axes.iter_mut() {
+        *axis = reader.u8()? as i8 as f32;
+    }
+
+    let flags = reader.u8()?;
+    let mut mouse = MouseFrame {
+        right_down: flags & MOUSE_RIGHT_DOWN != 0,
+        ..MouseFrame::default()
+    };
+    if flags & MOUSE_MOTION != 0 {
+        mouse.dx = reader.f32()?;
+        mouse.dy = reader.f32()?;
+        mouse.scroll = reader.f32()?;
+    }
+    if flags & MOUSE_CLICK != 0 {
+        mouse.click = Some((reader.f32()?, reader.f32()?));
+    }
+
+    Ok(InputFrame::from_raw(held, pressed, axes, mouse))
+}
+
+// Lector simple sobre el contenido del archivo
+struct ByteReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+        let end = self.offset + count;
+        if end > self.data.len() {
+            return Err("grabación truncada".into());
+        }
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Box<dyn std::error::Error>> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn f32(&mut self) -> Result<f32, Box<dyn std::error::Error>> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn vec3(&mut self) -> Result<Vec3, Box<dyn std::error::Error>> {
+        Ok(Vec3::new(self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Action, ACTIONS};
+
+    // Archivo temporal propio de cada prueba (las pruebas corren en paralelo)
+    fn temp_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("ssrec-{}-{}.rec", std::process::id(), name));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn action_bit(action: Action) -> u32 {
+        1 << ACTIONS.iter().position(|&a| a == action).unwrap()
+    }
+
+    // Vuelo corto: acelerar y girar, pausar, soltar y rebobinar
+    fn flight_inputs() -> Vec<InputFrame> {
+        let mut frames = Vec::new();
+        let thrust = InputFrame::from_raw(0, 0, [1.0, 0.0, 0.0, 1.0, 0.0], MouseFrame::default());
+        frames.extend(std::iter::repeat_n(thrust.clone(), 40));
+        frames.push(InputFrame::from_raw(0, action_bit(Action::TogglePause), [0.0; 5], MouseFrame::default()));
+        frames.extend(std::iter::repeat_n(InputFrame::default(), 10));
+        frames.push(InputFrame::from_raw(0, action_bit(Action::TogglePause) | action_bit(Action::ReverseTime), [0.0; 5], MouseFrame::default()));
+        let mouse = MouseFrame { dx: 3.0, dy: -2.0, right_down: true, ..MouseFrame::default() };
+        frames.push(InputFrame::from_raw(action_bit(Action::Turbo), 0, [1.0, -1.0, 0.0, 0.0, 1.0], mouse));
+        frames.extend(std::iter::repeat_n(thrust, 30));
+        frames
+    }
+
+    fn record(sim: &Simulation, frames: &[InputFrame], name: &str) -> String {
+        let mut recorder = Recorder::new(sim);
+        for frame in frames {
+            recorder.push(frame);
+        }
+        let path = temp_path(name);
+        recorder.save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn round_trip_keeps_initial_state_and_frames() {
+        let mut sim = Simulation::new(Vec::new());
+        sim.tick = 42;
+        sim.ship.position = Vec3::new(1.0, -2.0, 3.5);
+        sim.clock = SimClock::from_parts(12.5, 2, true, false);
+        sim.show_orbits = false;
+        let frames = flight_inputs();
+        let path = record(&sim, &frames, "round-trip");
+
+        let replay = Replay::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let initial = &replay.initial;
+        assert_eq!(initial.tick, 42);
+        assert_eq!(initial.time, 12.5);
+        assert_eq!(initial.position, Vec3::new(1.0, -2.0, 3.5));
+        assert_eq!(initial.preset, 2);
+        assert!(initial.reversed && !initial.paused && !initial.show_orbits);
+        assert_eq!(initial.system_seed, None);
+        assert_eq!(replay.tick_count(), frames.len());
+        assert_eq!(replay.collect::<Vec<_>>(), frames);
+    }
+
+    #[test]
+    fn long_runs_split_past_u16_max() {
+        let sim = Simulation::new(Vec::new());
+        let idle = InputFrame::default();
+        let ticks = u16::MAX as usize * 2 + 5;
+        let mut recorder = Recorder::new(&sim);
+        for _ in 0..ticks {
+            recorder.push(&idle);
+        }
+        assert_eq!(recorder.runs.len(), 3);
+        assert_eq!(recorder.tick_count(), ticks);
+
+        let path = temp_path("long-runs");
+        recorder.save(&path).unwrap();
+        let replay = Replay::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replay.tick_count(), ticks);
+        assert!(replay.into_iter().all(|frame| frame == idle));
+    }
+
+    #[test]
+    fn system_seed_is_optional() {
+        let mut sim = Simulation::new(Vec::new());
+        let solar = record(&sim, &[InputFrame::default()], "no-seed");
+        sim.system_seed = Some(0xDEAD_BEEF_1234);
+        let generated = record(&sim, &[InputFrame::default()], "seed");
+
+        let solar_replay = Replay::load(&solar).unwrap();
+        let generated_replay = Replay::load(&generated).unwrap();
+        std::fs::remove_file(&solar).unwrap();
+        std::fs::remove_file(&generated).unwrap();
+        assert_eq!(solar_replay.initial.system_seed, None);
+        assert_eq!(generated_replay.initial.system_seed, Some(0xDEAD_BEEF_1234));
+        assert_eq!(generated_replay.tick_count(), 1);
+    }
+
+    #[test]
+    fn rejects_old_versions_and_truncated_files() {
+        let sim = Simulation::new(Vec::new());
+        let path = record(&sim, &flight_inputs(), "rejects");
+        let data = std::fs::read(&path).unwrap();
+
+        let mut old = data.clone();
+        old[MAGIC.len()] = 2;
+        std::fs::write(&path, &old).unwrap();
+        assert!(Replay::load(&path).is_err());
+
+        for len in [3, MAGIC.len() + 1, 20, data.len() - 1] {
+            std::fs::write(&path, &data[..len]).unwrap();
+            assert!(Replay::load(&path).is_err(), "se aceptó un archivo de {} bytes", len);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn headless_replay_is_deterministic() {
+        let start = Simulation::new(crate::create_planets());
+        let path = record(&start, &flight_inputs(), "determinism");
+
+        let run = || {
+            let replay = Replay::load(&path).unwrap();
+            let mut sim = Simulation::new(crate::create_planets());
+            replay.initial.apply(&mut sim);
+            for input in replay {
+                sim.step(&input);
+            }
+            sim
+        };
+        let first = run();
+        let second = run();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(first.tick, flight_inputs().len() as u64);
+        assert_eq!(first.tick, second.tick);
+        assert_eq!(first.clock.time_f64(), second.clock.time_f64());
+        assert_eq!(first.clock.is_reversed(), second.clock.is_reversed());
+        assert_eq!(first.ship.position, second.ship.position);
+        assert_eq!(first.ship.rotation, second.ship.rotation);
+        assert_eq!(first.ship.velocity, second.ship.velocity);
+        assert_eq!(first.selected_body, second.selected_body);
+    }
+}
@@ -0,0 +1,45 @@
+// Generador pseudoaleatorio con semilla (SplitMix64). Siempre da la misma
+// secuencia para la misma semilla, así el contenido procedural no cambia
+// entre ejecuciones ni entre una grabación y su repetición.
+
+use nalgebra_glm::Vec3;
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Número en [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    // Índice en [0, len)
+    pub fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len.max(1) as u64) as usize
+    }
+
+    // Dirección uniforme sobre la esfera unitaria
+    pub fn unit_vector(&mut self) -> Vec3 {
+        let z = self.range(-1.0, 1.0);
+        let angle = self.range(0.0, std::f32::consts::TAU);
+        let r = (1.0 - z * z).sqrt();
+        Vec3::new(r * angle.cos(), r * angle.sin(), z)
+    }
+}
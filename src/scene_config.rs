@@ -0,0 +1,537 @@
+// Datos de la escena en archivos de texto: los parámetros de los shaders de
+// cada cuerpo (config/surfaces.cfg) y sus datos físicos (config/bodies.cfg).
+// Parten siempre del Sistema Solar de fábrica, así borrar una línea devuelve
+// el valor original. Los archivos se vigilan mientras la app corre y se
+// vuelven a aplicar al guardarlos; si tienen errores, no se aplica nada.
+//
+// Formato, una sección por cuerpo (# para comentarios):
+//   [Tierra]
+//   preset = earth
+//   ocean_level = 0.6
+//   deep_ocean = 0x00288C
+//
+// Las tormentas y los mares se repiten una línea por elemento:
+//   storm = <latitud> <longitud> <ancho> <alto> <color> <deriva>
+//   mare = <latitud> <longitud> <radio>
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::ErrorKind;
+use std::time::SystemTime;
+use crate::catalog::{BodyInfo, BodyKind, CATALOG};
+use crate::color::Color;
+use crate::planet_shaders::{GiantStyle, Mare, MoonStyle, PolarVortex, StarStyle, Storm, SurfaceStyle, TerrestrialStyle};
+
+pub const SURFACES_FILE: &str = "config/surfaces.cfg";
+pub const BODIES_FILE: &str = "config/bodies.cfg";
+pub const FILES: [&str; 2] = [SURFACES_FILE, BODIES_FILE];
+
+// Cada cuánto se mira si los archivos cambiaron (segundos)
+const POLL_INTERVAL: f32 = 0.5;
+
+// El Sistema Solar con los archivos aplicados. Un archivo que no existe no
+// cambia nada; el primer error se devuelve con el archivo y la línea.
+pub fn load() -> Result<Vec<BodyInfo>, String> {
+    let mut bodies = CATALOG.to_vec();
+    if let Some(contents) = read_optional(BODIES_FILE)? {
+        apply_file(&mut bodies, &contents, apply_body_section).map_err(|e| format!("{}: {}", BODIES_FILE, e))?;
+    }
+    if let Some(contents) = read_optional(SURFACES_FILE)? {
+        apply_file(&mut bodies, &contents, apply_surface_section).map_err(|e| format!("{}: {}", SURFACES_FILE, e))?;
+    }
+    Ok(bodies)
+}
+
+fn read_optional(filename: &str) -> Result<Option<String>, String> {
+    match fs::read_to_string(filename) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", filename, e)),
+    }
+}
+
+// Avisa cuando cambia la fecha de modificación de alguno de los archivos
+pub struct FileWatcher {
+    files: Vec<(&'static str, Option<SystemTime>)>,
+    countdown: f32,
+}
+
+impl FileWatcher {
+    pub fn new(filenames: &[&'static str]) -> Self {
+        FileWatcher {
+            files: filenames.iter().map(|&filename| (filename, modified(filename))).collect(),
+            countdown: POLL_INTERVAL,
+        }
+    }
+
+    // true si algún archivo cambió (o apareció, o se borró) desde la última vez
+    pub fn poll(&mut self, dt: f32) -> bool {
+        self.countdown -= dt;
+        if self.countdown > 0.0 {
+            return false;
+        }
+        self.countdown = POLL_INTERVAL;
+
+        let mut changed = false;
+        for (filename, last_modified) in &mut self.files {
+            let current = modified(filename);
+            if current != *last_modified {
+                *last_modified = current;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+fn modified(filename: &str) -> Option<SystemTime> {
+    fs::metadata(filename).and_then(|metadata| metadata.modified()).ok()
+}
+
+// === LECTURA ===
+
+struct Entry {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+impl Entry {
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        format!("línea {}: {}", self.line, message)
+    }
+
+    fn unknown(&self) -> String {
+        self.error(format_args!("parámetro desconocido '{}'", self.key))
+    }
+
+    fn number(&self) -> Result<f32, String> {
+        parse_number(&self.value).ok_or_else(|| self.error(format_args!("'{}' no es un número", self.value)))
+    }
+
+    fn positive(&self) -> Result<f32, String> {
+        let value = self.number()?;
+        if value > 0.0 {
+            Ok(value)
+        } else {
+            Err(self.error(format_args!("{} debe ser mayor que 0", self.key)))
+        }
+    }
+
+    fn color(&self) -> Result<Color, String> {
+        parse_color(&self.value).ok_or_else(|| self.error(format_args!("'{}' no es un color 0xRRGGBB", self.value)))
+    }
+
+    // Valores separados por espacios, exactamente `count`
+    fn fields(&self, count: usize, format: &str) -> Result<Vec<&str>, String> {
+        let fields: Vec<&str> = self.value.split_whitespace().collect();
+        if fields.len() == count {
+            Ok(fields)
+        } else {
+            Err(self.error(format_args!("{} necesita '{}'", self.key, format)))
+        }
+    }
+}
+
+struct Section {
+    name: String,
+    line: usize,
+    entries: Vec<Entry>,
+}
+
+fn parse_sections(contents: &str) -> Result<Vec<Section>, String> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        let line_number = line_number + 1;
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| format!("línea {}: falta ']'", line_number))?;
+            sections.push(Section { name: name.trim().to_string(), line: line_number, entries: Vec::new() });
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("línea {}: falta '='", line_number))?;
+        let section = sections
+            .last_mut()
+            .ok_or_else(|| format!("línea {}: falta la sección [Cuerpo] antes", line_number))?;
+        section.entries.push(Entry { key: key.trim().to_string(), value: value.trim().to_string(), line: line_number });
+    }
+    Ok(sections)
+}
+
+fn apply_file(
+    bodies: &mut [BodyInfo],
+    contents: &str,
+    apply_section: fn(&mut BodyInfo, &Section) -> Result<(), String>,
+) -> Result<(), String> {
+    for section in parse_sections(contents)? {
+        let body = bodies
+            .iter_mut()
+            .find(|body| body.name == section.name)
+            .ok_or_else(|| format!("línea {}: no hay ningún cuerpo llamado '{}'", section.line, section.name))?;
+        apply_section(body, &section)?;
+    }
+    Ok(())
+}
+
+fn parse_number(text: &str) -> Option<f32> {
+    text.parse::<f32>().ok().filter(|value| value.is_finite())
+}
+
+fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"))?;
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(Color::from_hex)
+}
+
+// === DATOS FÍSICOS ===
+
+fn apply_body_section(body: &mut BodyInfo, section: &Section) -> Result<(), String> {
+    for entry in &section.entries {
+        match entry.key.as_str() {
+            "radius_km" => body.radius_km = entry.positive()?,
+            "mass_kg" => {
+                body.mass_kg = entry.value.parse::<f64>().ok().filter(|mass| *mass > 0.0).ok_or_else(|| entry.error("la masa debe ser un número mayor que 0"))?;
+            }
+            "orbital_period_days" if body.kind == BodyKind::Star => return Err(entry.error("la estrella no tiene órbita")),
+            "orbital_period_days" => body.orbital_period_days = entry.positive()?,
+            "day_length_hours" => {
+                let hours = entry.number()?;
+                if hours == 0.0 {
+                    return Err(entry.error("el día no puede durar 0 horas"));
+                }
+                body.day_length_hours = hours;
+            }
+            "axial_tilt_deg" => body.axial_tilt_deg = entry.number()?,
+            "orbit_inclination_deg" => body.orbit_inclination_deg = entry.number()?,
+            "tidally_locked" => {
+                body.tidally_locked = match entry.value.as_str() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(entry.error("tidally_locked es 'true' o 'false'")),
+                };
+            }
+            "orbit_radius" => body.orbit_radius = entry.positive()?,
+            "shape" => {
+                let fields = entry.fields(3, "x y z")?;
+                for (axis, field) in body.shape.iter_mut().zip(fields) {
+                    *axis = parse_number(field)
+                        .filter(|value| *value > 0.0 && *value <= 1.0)
+                        .ok_or_else(|| entry.error("los semiejes van entre 0 y 1"))?;
+                }
+            }
+            "description" => body.description = Cow::Owned(entry.value.clone()),
+            _ => return Err(entry.unknown()),
+        }
+    }
+    Ok(())
+}
+
+// === SUPERFICIES ===
+
+// Ajustes de fábrica de cada familia, por nombre
+pub fn preset(name: &str) -> Option<SurfaceStyle> {
+    let style = match name {
+        "sun" => SurfaceStyle::Star(StarStyle::sun()),
+        "earth" => SurfaceStyle::Terrestrial(TerrestrialStyle::earth()),
+        "mars" => SurfaceStyle::Terrestrial(TerrestrialStyle::mars()),
+        "jupiter" => SurfaceStyle::Giant(GiantStyle::jupiter()),
+        "saturn" => SurfaceStyle::Giant(GiantStyle::saturn()),
+        "uranus" => SurfaceStyle::Giant(GiantStyle::uranus()),
+        "neptune" => SurfaceStyle::Giant(GiantStyle::neptune()),
+        "moon" => SurfaceStyle::Airless(MoonStyle::moon()),
+        "mercury" => SurfaceStyle::Airless(MoonStyle::mercury()),
+        "comet" => SurfaceStyle::Airless(MoonStyle::comet_nucleus()),
+        "ganymede" => SurfaceStyle::Airless(MoonStyle::ganymede()),
+        "callisto" => SurfaceStyle::Airless(MoonStyle::callisto()),
+        "phobos" => SurfaceStyle::Airless(MoonStyle::phobos()),
+        "deimos" => SurfaceStyle::Airless(MoonStyle::deimos()),
+        "pluto" => SurfaceStyle::Airless(MoonStyle::pluto()),
+        "asteroid" => SurfaceStyle::Airless(MoonStyle::asteroid()),
+        "kuiper" => SurfaceStyle::Airless(MoonStyle::kuiper()),
+        _ => return None,
+    };
+    Some(style)
+}
+
+fn apply_surface_section(body: &mut BodyInfo, section: &Section) -> Result<(), String> {
+    let mut style = body.surface.clone();
+    // La primera tormenta o el primer mar de la sección sustituyen a los del preset
+    let mut storms: Option<Vec<Storm>> = None;
+    let mut maria: Option<Vec<Mare>> = None;
+
+    for entry in &section.entries {
+        match (entry.key.as_str(), style.as_mut()) {
+            ("preset", _) => {
+                style = Some(preset(&entry.value).ok_or_else(|| entry.error(format_args!("preset desconocido '{}'", entry.value)))?);
+                storms = None;
+                maria = None;
+            }
+            (_, None) => return Err(entry.error(format_args!("falta 'preset' antes de '{}'", entry.key))),
+            ("storm", Some(SurfaceStyle::Giant(_))) => {
+                let list = storms.get_or_insert_with(Vec::new);
+                if entry.value != "none" {
+                    list.push(parse_storm(entry)?);
+                }
+            }
+            ("mare", Some(SurfaceStyle::Airless(_))) => {
+                let list = maria.get_or_insert_with(Vec::new);
+                if entry.value != "none" {
+                    list.push(parse_mare(entry)?);
+                }
+            }
+            (_, Some(SurfaceStyle::Star(star))) => set_star(star, entry)?,
+            (_, Some(SurfaceStyle::Terrestrial(terrestrial))) => set_terrestrial(terrestrial, entry)?,
+            (_, Some(SurfaceStyle::Giant(giant))) => set_giant(giant, entry)?,
+            (_, Some(SurfaceStyle::Airless(airless))) => set_airless(airless, entry)?,
+        }
+    }
+
+    match &mut style {
+        Some(SurfaceStyle::Giant(giant)) => {
+            if let Some(storms) = storms {
+                giant.storms = storms;
+            }
+        }
+        Some(SurfaceStyle::Airless(airless)) => {
+            if let Some(maria) = maria {
+                airless.maria = maria;
+            }
+        }
+        _ => {}
+    }
+    body.surface = style;
+    Ok(())
+}
+
+fn set_star(style: &mut StarStyle, entry: &Entry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "core" => style.core = entry.color()?,
+        "hot" => style.hot = entry.color()?,
+        "flare" => style.flare = entry.color()?,
+        "spot" => style.spot = entry.color()?,
+        "corona" => style.corona = entry.color()?,
+        "granulation_scale" => style.granulation_scale = entry.positive()?,
+        "spot_scale" => style.spot_scale = entry.positive()?,
+        "spot_coverage" => style.spot_coverage = entry.number()?,
+        "corona_strength" => style.corona_strength = entry.number()?,
+        "pulse" => style.pulse = entry.number()?,
+        _ => return Err(entry.unknown()),
+    }
+    Ok(())
+}
+
+fn set_terrestrial(style: &mut TerrestrialStyle, entry: &Entry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "deep_ocean" => style.deep_ocean = entry.color()?,
+        "shallow_ocean" => style.shallow_ocean = entry.color()?,
+        "lowland" => style.lowland = entry.color()?,
+        "highland" => style.highland = entry.color()?,
+        "dark_terrain" => style.dark_terrain = entry.color()?,
+        "ice" => style.ice = entry.color()?,
+        "cloud" => style.cloud = entry.color()?,
+        "city" => style.city = entry.color()?,
+        "feature_scale" => style.feature_scale = entry.positive()?,
+        "ocean_level" => style.ocean_level = entry.number()?,
+        "dark_coverage" => style.dark_coverage = entry.number()?,
+        "canyons" => style.canyons = entry.number()?,
+        "relief" => style.relief = entry.number()?,
+        "crater_density" => style.crater_density = entry.number()?,
+        "ice_latitude" => style.ice_latitude = entry.number()?,
+        "cloud_cover" => style.cloud_cover = entry.number()?,
+        "city_lights" => style.city_lights = entry.number()?,
+        "glint" => style.glint = entry.number()?,
+        "ambient" => style.ambient = entry.number()?,
+        "seed" => style.seed = parse_seed(entry)?,
+        _ => return Err(entry.unknown()),
+    }
+    Ok(())
+}
+
+fn set_giant(style: &mut GiantStyle, entry: &Entry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "bands" => {
+            let fields = entry.fields(3, "clara media oscura")?;
+            for (band, field) in style.bands.iter_mut().zip(fields) {
+                *band = parse_color(field).ok_or_else(|| entry.error(format_args!("'{}' no es un color 0xRRGGBB", field)))?;
+            }
+        }
+        "eddy" => style.eddy = entry.color()?,
+        "haze" => style.haze = entry.color()?,
+        "band_count" => style.band_count = entry.number()?,
+        "band_contrast" => style.band_contrast = entry.number()?,
+        "turbulence" => style.turbulence = entry.number()?,
+        "eddy_coverage" => style.eddy_coverage = entry.number()?,
+        "polar_haze" => style.polar_haze = entry.number()?,
+        "haze_strength" => style.haze_strength = entry.number()?,
+        "polar_vortex" => style.polar_vortex = parse_polar_vortex(entry)?,
+        "ambient" => style.ambient = entry.number()?,
+        "seed" => style.seed = parse_seed(entry)?,
+        _ => return Err(entry.unknown()),
+    }
+    Ok(())
+}
+
+fn set_airless(style: &mut MoonStyle, entry: &Entry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "base" => style.base = entry.color()?,
+        "dark" => style.dark = entry.color()?,
+        "bright" => style.bright = entry.color()?,
+        "crater" => style.crater = entry.color()?,
+        "dark_coverage" => style.dark_coverage = entry.number()?,
+        "crater_density" => style.crater_density = entry.number()?,
+        "grooves" => style.grooves = entry.number()?,
+        "lumpiness" => style.lumpiness = entry.number()?,
+        "relief" => style.relief = entry.number()?,
+        "feature_scale" => style.feature_scale = entry.positive()?,
+        "ambient" => style.ambient = entry.number()?,
+        _ => return Err(entry.unknown()),
+    }
+    Ok(())
+}
+
+fn parse_seed(entry: &Entry) -> Result<u32, String> {
+    entry.value.parse().map_err(|_| entry.error(format_args!("'{}' no es una semilla válida", entry.value)))
+}
+
+fn parse_storm(entry: &Entry) -> Result<Storm, String> {
+    let fields = entry.fields(6, "latitud longitud ancho alto color deriva")?;
+    let number = |field: &str| parse_number(field).ok_or_else(|| entry.error(format_args!("'{}' no es un número", field)));
+    Ok(Storm {
+        latitude_deg: number(fields[0])?,
+        longitude_deg: number(fields[1])?,
+        width_deg: number(fields[2])?,
+        height_deg: number(fields[3])?,
+        color: parse_color(fields[4]).ok_or_else(|| entry.error(format_args!("'{}' no es un color 0xRRGGBB", fields[4])))?,
+        drift: number(fields[5])?,
+    })
+}
+
+fn parse_mare(entry: &Entry) -> Result<Mare, String> {
+    let fields = entry.fields(3, "latitud longitud radio")?;
+    let number = |field: &str| parse_number(field).ok_or_else(|| entry.error(format_args!("'{}' no es un número", field)));
+    Ok(Mare {
+        latitude_deg: number(fields[0])?,
+        longitude_deg: number(fields[1])?,
+        radius_deg: number(fields[2])?,
+    })
+}
+
+fn parse_polar_vortex(entry: &Entry) -> Result<Option<PolarVortex>, String> {
+    if entry.value == "none" {
+        return Ok(None);
+    }
+    let fields = entry.fields(5, "color borde ojo lados radio")?;
+    let color = |field: &str| parse_color(field).ok_or_else(|| entry.error(format_args!("'{}' no es un color 0xRRGGBB", field)));
+    Ok(Some(PolarVortex {
+        color: color(fields[0])?,
+        rim: color(fields[1])?,
+        eye: color(fields[2])?,
+        sides: fields[3].parse().ok().filter(|sides| *sides >= 3).ok_or_else(|| entry.error("el vórtice necesita al menos 3 lados"))?,
+        radius: parse_number(fields[4]).ok_or_else(|| entry.error(format_args!("'{}' no es un número", fields[4])))?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_bodies(contents: &str) -> Result<Vec<BodyInfo>, String> {
+        let mut bodies = CATALOG.to_vec();
+        apply_file(&mut bodies, contents, apply_body_section)?;
+        Ok(bodies)
+    }
+
+    fn apply_surfaces(contents: &str) -> Result<Vec<BodyInfo>, String> {
+        let mut bodies = CATALOG.to_vec();
+        apply_file(&mut bodies, contents, apply_surface_section)?;
+        Ok(bodies)
+    }
+
+    // Primer error al aplicar el archivo; BodyInfo no implementa Debug
+    fn body_error(contents: &str) -> String {
+        apply_bodies(contents).err().expect("el archivo debería tener un error")
+    }
+
+    fn surface_error(contents: &str) -> String {
+        apply_surfaces(contents).err().expect("el archivo debería tener un error")
+    }
+
+    fn find<'a>(bodies: &'a [BodyInfo], name: &str) -> &'a BodyInfo {
+        bodies.iter().find(|body| body.name == name).unwrap()
+    }
+
+    #[test]
+    fn applies_body_values() {
+        let bodies = apply_bodies("# comentario\n[Marte]\nday_length_hours = -30.5\ntidally_locked = true  # al final\n").unwrap_or_else(|e| panic!("{}", e));
+        let mars = find(&bodies, "Marte");
+        assert_eq!(mars.day_length_hours, -30.5);
+        assert!(mars.tidally_locked);
+        assert_eq!(find(&bodies, "Tierra").day_length_hours, find(&CATALOG, "Tierra").day_length_hours);
+    }
+
+    #[test]
+    fn rejects_a_zero_day() {
+        let error = body_error("[Tierra]\n\nday_length_hours = 0\n");
+        assert_eq!(error, "línea 3: el día no puede durar 0 horas");
+    }
+
+    #[test]
+    fn rejects_an_orbit_on_the_star() {
+        let error = body_error("[Sol]\norbital_period_days = 30\n");
+        assert_eq!(error, "línea 2: la estrella no tiene órbita");
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        for (contents, expected) in [
+            ("[Tierra]\norbital_period_days = -1\n", "línea 2: orbital_period_days debe ser mayor que 0"),
+            ("[Tierra]\nradius_km = mucho\n", "línea 2: 'mucho' no es un número"),
+            ("[Tierra]\nradius_km = inf\n", "línea 2: 'inf' no es un número"),
+            ("[Tierra]\ntidally_locked = si\n", "línea 2: tidally_locked es 'true' o 'false'"),
+            ("[Tierra]\nshape = 1 1\n", "línea 2: shape necesita 'x y z'"),
+            ("[Tierra]\nshape = 1 1.5 1\n", "línea 2: los semiejes van entre 0 y 1"),
+            ("[Tierra]\ncolor = 3\n", "línea 2: parámetro desconocido 'color'"),
+        ] {
+            assert_eq!(body_error(contents), expected, "{:?}", contents);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        for (contents, expected) in [
+            ("radius_km = 3\n", "línea 1: falta la sección [Cuerpo] antes"),
+            ("[Tierra\n", "línea 1: falta ']'"),
+            ("[Tierra]\nradius_km 3\n", "línea 2: falta '='"),
+            ("\n[Vulcano]\n", "línea 2: no hay ningún cuerpo llamado 'Vulcano'"),
+        ] {
+            assert_eq!(body_error(contents), expected, "{:?}", contents);
+        }
+    }
+
+    #[test]
+    fn surface_sections_need_a_known_preset() {
+        assert_eq!(surface_error("[Tierra]\npreset = plasma\n"), "línea 2: preset desconocido 'plasma'");
+        assert_eq!(surface_error("[Venus]\nbase = 0x112233\n"), "línea 2: falta 'preset' antes de 'base'");
+        assert_eq!(surface_error("[Tierra]\npreset = earth\ndeep_ocean = 0x12345\n"), "línea 3: '0x12345' no es un color 0xRRGGBB");
+    }
+
+    #[test]
+    fn storm_lines_replace_the_preset_list() {
+        let bodies = apply_surfaces("[Júpiter]\npreset = jupiter\nstorm = none\n").unwrap_or_else(|e| panic!("{}", e));
+        let Some(SurfaceStyle::Giant(jupiter)) = &find(&bodies, "Júpiter").surface else {
+            panic!("Júpiter debe ser un gigante");
+        };
+        assert!(jupiter.storms.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use nalgebra_glm::{Vec3, Vec4, Mat3};
+use nalgebra_glm::{Vec3, Vec4, Mat3, Mat4, mat4_to_mat3};
 use crate::vertex::Vertex;
+use crate::camera_rig::CameraView;
 use crate::Uniforms;
 
 pub fn vertex_shader(vertex: &Vertex, uniforms: &Uniforms) -> Vertex {
@@ -29,13 +30,7 @@ pub fn vertex_shader(vertex: &Vertex, uniforms: &Uniforms) -> Vertex {
     let transformed_position = Vec3::new(screen_x, screen_y, screen_z);
 
     // Transformar las normales correctamente
-    let model_mat3 = Mat3::new(
-        uniforms.model_matrix[0], uniforms.model_matrix[1], uniforms.model_matrix[2],
-        uniforms.model_matrix[4], uniforms.model_matrix[5], uniforms.model_matrix[6],
-        uniforms.model_matrix[8], uniforms.model_matrix[9], uniforms.model_matrix[10]
-    );
-    let normal_matrix = model_mat3.transpose().try_inverse().unwrap_or(Mat3::identity());
-    let transformed_normal = (normal_matrix * vertex.normal).normalize();
+    let transformed_normal = (uniforms.normal_matrix * vertex.normal).normalize();
 
     Vertex {
         position: vertex.position,
@@ -45,4 +40,73 @@ pub fn vertex_shader(vertex: &Vertex, uniforms: &Uniforms) -> Vertex {
         transformed_position,
         transformed_normal,
     }
-}
\ No newline at end of file
+}
+
+// Lleva normales del espacio del modelo al mundo (inversa transpuesta de la
+// parte 3x3 de la matriz del modelo, por las escalas no uniformes)
+pub fn normal_matrix(model_matrix: &Mat4) -> Mat3 {
+    mat4_to_mat3(model_matrix).try_inverse().map_or(Mat3::identity(), |inverse| inverse.transpose())
+}
+
+// Punto del mundo en pantalla: (x, y en pixeles, z en NDC) y si queda delante
+// de la cámara. Detrás de ella se divide por |w| para que el punto no salga
+// invertido y siga indicando hacia qué lado está.
+pub fn project_point(world_pos: Vec3, view_projection_matrix: Mat4, width: f32, height: f32) -> (Vec3, bool) {
+    let clip = view_projection_matrix * Vec4::new(world_pos.x, world_pos.y, world_pos.z, 1.0);
+    let in_front = clip.w > 0.0;
+    let w = clip.w.abs().max(1e-4);
+    let screen = Vec3::new(
+        (clip.x / w + 1.0) * 0.5 * width,
+        (1.0 - clip.y / w) * 0.5 * height,
+        clip.z / w,
+    );
+    (screen, in_front)
+}
+
+// Igual, solo si el punto cae dentro de la pantalla
+pub fn world_to_screen(world_pos: Vec3, view_projection_matrix: Mat4, width: f32, height: f32) -> Option<Vec3> {
+    let (screen, in_front) = project_point(world_pos, view_projection_matrix, width, height);
+    let on_screen = in_front && (0.0..=width).contains(&screen.x) && (0.0..=height).contains(&screen.y);
+    on_screen.then_some(screen)
+}
+
+// Proyección de puntos sueltos (brillos, colas, partículas) con la misma
+// profundidad que usa el rasterizador, para que compartan el Z-buffer
+pub struct ScreenProjector {
+    view_projection_matrix: Mat4,
+    camera_right: Vec3,
+    width: f32,
+    height: f32,
+}
+
+impl ScreenProjector {
+    pub fn new(view_projection_matrix: Mat4, view: &CameraView, width: f32, height: f32) -> Self {
+        ScreenProjector {
+            view_projection_matrix,
+            camera_right: view.direction().cross(&view.up).normalize(),
+            width,
+            height,
+        }
+    }
+
+    // (x, y, profundidad) en pantalla; None si queda detrás de la cámara
+    pub fn project(&self, point: Vec3) -> Option<Vec3> {
+        let clip = self.view_projection_matrix * Vec4::new(point.x, point.y, point.z, 1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        Some(Vec3::new(
+            (clip.x / clip.w + 1.0) * 0.5 * self.width,
+            (1.0 - clip.y / clip.w) * 0.5 * self.height,
+            (clip.z / clip.w + 1.0) * 0.5,
+        ))
+    }
+
+    // Radio en pixeles de una esfera de radio `radius` centrada en `point`
+    pub fn radius_pixels(&self, point: Vec3, radius: f32) -> f32 {
+        match (self.project(point), self.project(point + self.camera_right * radius)) {
+            (Some(center), Some(edge)) => ((edge.x - center.x).powi(2) + (edge.y - center.y).powi(2)).sqrt(),
+            _ => 0.0,
+        }
+    }
+}
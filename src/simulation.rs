@@ -0,0 +1,263 @@
+use std::f32::consts::PI;
+use nalgebra_glm::Vec3;
+use crate::camera_rig::CameraRig;
+use crate::clock::SimClock;
+use crate::input::{Action, Axis, InputFrame};
+use crate::mouse::{MouseInput, MouseSettings};
+use crate::particles::{Emitter, ParticleSystem, ENGINE_EXHAUST, IMPACT_DEBRIS, SUN_FLARE, SUN_FLARE_PARTICLES};
+use crate::warp::{WarpDrive, WarpEvent};
+use crate::{collect_body_states, BodyState, Planet, SpaceshipCamera, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+// Paso fijo de la simulación (segundos por tick)
+pub const TICK_DT: f32 = 0.016;
+
+// Distancia del centro de la nave a las toberas
+const ENGINE_OFFSET: f32 = 8.0;
+// Radio de la nave para los choques
+const SHIP_RADIUS: f32 = 6.0;
+// Velocidad de impacto a partir de la cual hay restos y aviso
+const IMPACT_MIN_SPEED: f32 = 30.0;
+
+// Todo el estado que avanza con la entrada. No lee el reloj: el tiempo
+// sale del número de ticks, así una repetición da exactamente el mismo vuelo.
+pub struct Simulation {
+    pub ship: SpaceshipCamera,
+    pub rig: CameraRig,
+    pub warp_drive: WarpDrive,
+    pub mouse: MouseInput,
+    pub planets: Vec<Planet>,
+    pub bodies: Vec<BodyState>,
+    pub selected_body: Option<usize>,
+    pub clock: SimClock,
+    pub show_orbits: bool,
+    // Semilla del sistema generado; None es el Sistema Solar
+    pub system_seed: Option<u64>,
+    pub particles: ParticleSystem,
+    engine_exhaust: Emitter,
+    sun_flares: Emitter,
+    pub tick: u64,
+    // Tiempo de simulación usado en el último tick
+    pub elapsed: f32,
+    // Avisos generados en el tick (los muestra el HUD)
+    pub messages: Vec<String>,
+}
+
+impl Simulation {
+    pub fn new(planets: Vec<Planet>) -> Self {
+        let ship = SpaceshipCamera::new();
+        let rig = CameraRig::new(SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32, &ship);
+        let bodies = collect_body_states(&planets, 0.0);
+
+        Simulation {
+            ship,
+            rig,
+            warp_drive: WarpDrive::new(),
+            mouse: MouseInput::new(MouseSettings::default()),
+            planets,
+            bodies,
+            selected_body: None,
+            clock: SimClock::new(),
+            show_orbits: true,
+            system_seed: None,
+            particles: ParticleSystem::new(),
+            engine_exhaust: Emitter::new(&ENGINE_EXHAUST),
+            sun_flares: Emitter::new(&SUN_FLARE),
+            tick: 0,
+            elapsed: 0.0,
+            messages: Vec::new(),
+        }
+    }
+
+    // Avanza un tick con la entrada dada
+    pub fn step(&mut self, input: &InputFrame) {
+        self.elapsed = self.clock.time();
+        self.bodies = collect_body_states(&self.planets, self.elapsed);
+
+        if input.pressed(Action::ToggleMouseSteering) {
+            self.mouse.steering = !self.mouse.steering;
+            let message = if self.mouse.steering { "Dirección con ratón ACTIVADA" } else { "Dirección con ratón DESACTIVADA" };
+            self.messages.push(message.to_string());
+        }
+
+        let throttle = handle_input(input, &mut self.ship, &mut self.clock, &mut self.show_orbits, self.warp_drive.is_engaged(), &mut self.messages);
+
+        // Estela de los motores, más densa cuanto más empuje
+        let forward = self.ship.get_forward_vector();
+        let nozzle = self.ship.position - forward * ENGINE_OFFSET;
+        self.engine_exhaust.emit(&mut self.particles, TICK_DT, throttle, nozzle, -forward, self.ship.velocity);
+
+        handle_warp_input(input, &self.ship, &mut self.warp_drive, &self.bodies, self.selected_body, &mut self.messages);
+
+        // === RATÓN ===
+        // La selección usa la proyección del frame anterior, que es la que se ve en pantalla
+        let previous_view_projection = self.rig.view_projection_matrix();
+        let picked = self.mouse.apply(
+            &input.mouse,
+            &mut self.ship,
+            &mut self.rig,
+            &self.bodies,
+            previous_view_projection,
+            !self.warp_drive.is_engaged(),
+        );
+        // Con la tecla de objetivo se recorren Sol, planetas y lunas en orden
+        let cycled = if input.pressed(Action::CycleTarget) && !self.bodies.is_empty() {
+            Some(self.selected_body.map_or(0, |i| (i + 1) % self.bodies.len()))
+        } else {
+            None
+        };
+        if let Some(i) = picked.or(cycled) {
+            self.selected_body = Some(i);
+            self.rig.lock_on(i, &self.bodies);
+            self.messages.push(format!("Objetivo: {}", self.bodies[i].info.name));
+        }
+
+        // === MOTOR WARP ===
+        let warp_event = self.warp_drive.update(
+            TICK_DT,
+            self.ship.position,
+            &mut self.ship.velocity,
+            &mut self.ship.rotation,
+            &self.bodies,
+        );
+        match warp_event {
+            WarpEvent::Engaged => self.messages.push("Warp ACTIVADO".to_string()),
+            WarpEvent::Arrived(i) => self.messages.push(format!("Llegada a {}", self.bodies[i].info.name)),
+            WarpEvent::DroppedOut(i) => self.messages.push(format!("Salida de warp: pozo gravitatorio de {}", self.bodies[i].info.name)),
+            WarpEvent::None => {}
+        }
+
+        let previous_position = self.ship.position;
+        self.ship.update(TICK_DT);
+        emit_impact_debris(previous_position, &self.ship, &self.bodies, &mut self.particles, &mut self.messages);
+
+        // === PARTÍCULAS ===
+        // Las erupciones salen de un punto al azar de la superficie del Sol
+        let sun_radius = self.bodies[0].radius;
+        for _ in 0..self.sun_flares.due(TICK_DT, 1.0) {
+            let direction = self.particles.random_direction();
+            self.particles.burst(&SUN_FLARE, SUN_FLARE_PARTICLES, direction * sun_radius, direction, Vec3::zeros());
+        }
+        self.particles.update(TICK_DT);
+
+        // === CÁMARA ===
+        handle_camera_input(input, &mut self.rig, &self.bodies, &mut self.messages);
+        self.rig.update(TICK_DT, &self.ship, &self.bodies);
+
+        // El sistema avanza según la escala de tiempo; la nave siempre en tiempo real
+        self.clock.advance(TICK_DT);
+        self.tick += 1;
+    }
+}
+
+// Devuelve el empuje hacia delante del tick (con turbo puede pasar de 1)
+fn handle_input(input: &InputFrame, camera: &mut SpaceshipCamera, clock: &mut SimClock, show_orbits: &mut bool, warp_engaged: bool, messages: &mut Vec<String>) -> f32 {
+    let base_speed = 8.0; // Un poco más rápido para mejor navegación
+    let turbo_multiplier = if input.held(Action::Turbo) { 3.0 } else { 1.0 };
+    let move_speed = base_speed * turbo_multiplier;
+    let rotation_speed = 0.03; // Un poco más responsivo
+
+    // Con el warp en uso la nave no acepta empuje manual
+    let move_speed = if warp_engaged { 0.0 } else { move_speed };
+
+    // === MOVIMIENTO DE NAVE ===
+    camera.velocity += camera.get_forward_vector() * move_speed * input.axis(Axis::Thrust);
+    camera.velocity += camera.get_right_vector() * move_speed * input.axis(Axis::Strafe);
+
+    // === ROTACIÓN ===
+    // Con OrbitCamera (Ctrl) los ejes controlan la cámara observador, no la nave
+    let rotation_speed = if input.held(Action::OrbitCamera) { 0.0 } else { rotation_speed };
+    camera.rotation.x += rotation_speed * input.axis(Axis::Pitch);
+    camera.rotation.y += rotation_speed * input.axis(Axis::Yaw);
+    camera.rotation.z += rotation_speed * input.axis(Axis::Roll);
+
+    // Limitar pitch
+    camera.rotation.x = camera.rotation.x.clamp(-PI / 2.5, PI / 2.5);
+
+    // === CONTROLES DEL SISTEMA ===
+    if input.pressed(Action::TogglePause) {
+        clock.toggle_pause();
+        messages.push(if clock.is_paused() { "Sistema PAUSADO" } else { "Sistema REANUDADO" }.to_string());
+    }
+
+    if input.pressed(Action::ReverseTime) {
+        clock.toggle_reverse();
+        messages.push(if clock.is_reversed() { "Tiempo en REVERSA" } else { "Tiempo hacia ADELANTE" }.to_string());
+    }
+
+    if input.pressed(Action::ToggleOrbits) {
+        *show_orbits = !*show_orbits;
+        messages.push(if *show_orbits { "Órbitas VISIBLES" } else { "Órbitas OCULTAS" }.to_string());
+    }
+
+    if input.pressed(Action::SpeedUp) {
+        clock.faster();
+        messages.push(format!("Velocidad del sistema: {}", clock.describe()));
+    }
+    if input.pressed(Action::SpeedDown) {
+        clock.slower();
+        messages.push(format!("Velocidad del sistema: {}", clock.describe()));
+    }
+
+    input.axis(Axis::Thrust).max(0.0) * move_speed / base_speed
+}
+
+// Restos al chocar: solo en el tick en que la nave cruza la superficie de un
+// cuerpo y con un golpe fuerte. No cambia el vuelo de la nave.
+fn emit_impact_debris(previous_position: Vec3, camera: &SpaceshipCamera, bodies: &[BodyState], particles: &mut ParticleSystem, messages: &mut Vec<String>) {
+    for body in bodies {
+        let min_distance = body.radius + SHIP_RADIUS;
+        let offset = camera.position - body.position;
+        let distance = offset.magnitude();
+        let was_outside = (previous_position - body.position).magnitude() >= min_distance;
+        if !was_outside || distance >= min_distance || distance == 0.0 {
+            continue;
+        }
+
+        let normal = offset / distance;
+        let impact_speed = -camera.velocity.dot(&normal);
+        if impact_speed > IMPACT_MIN_SPEED {
+            let count = (impact_speed * 0.5) as usize;
+            particles.burst(&IMPACT_DEBRIS, count, body.position + normal * body.radius, normal, Vec3::zeros());
+            messages.push(format!("Impacto contra {}", body.info.name));
+        }
+    }
+}
+
+fn handle_warp_input(input: &InputFrame, camera: &SpaceshipCamera, warp_drive: &mut WarpDrive, bodies: &[BodyState], selected_body: Option<usize>, messages: &mut Vec<String>) {
+    if input.pressed(Action::Warp) {
+        let was_engaged = warp_drive.is_engaged();
+        warp_drive.toggle(camera.position, camera.get_forward_vector(), bodies, selected_body);
+        if was_engaged {
+            messages.push("Warp CANCELADO".to_string());
+        } else if let Some(target) = warp_drive.target {
+            messages.push(format!("Cargando warp... destino: {}", bodies[target].info.name));
+        } else {
+            messages.push("Cargando warp... sin destino, rumbo al frente".to_string());
+        }
+    }
+}
+
+fn handle_camera_input(input: &InputFrame, rig: &mut CameraRig, bodies: &[BodyState], messages: &mut Vec<String>) {
+    if input.pressed(Action::CycleCamera) {
+        rig.cycle_mode();
+        messages.push(format!("Cámara: {}", rig.mode.name()));
+    }
+
+    if input.pressed(Action::CycleObserverBody) && !bodies.is_empty() {
+        let next = (rig.locked_body + 1) % bodies.len();
+        rig.lock_on(next, bodies);
+        messages.push(format!("Observador fijado en {}", bodies[next].info.name));
+    }
+
+    // === CONTROLES DEL OBSERVADOR ===
+    let orbit_speed = 0.02;
+    if input.held(Action::OrbitCamera) {
+        rig.orbit.rotate(-input.axis(Axis::Yaw) * orbit_speed, input.axis(Axis::Pitch) * orbit_speed);
+    }
+    if input.held(Action::ZoomIn) {
+        rig.orbit.zoom(-20.0);
+    }
+    if input.held(Action::ZoomOut) {
+        rig.orbit.zoom(20.0);
+    }
+}
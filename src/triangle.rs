@@ -1,8 +1,8 @@
-use nalgebra_glm::Vec3;
+use nalgebra_glm::{Vec3, Vec4};
 use crate::fragment::Fragment;
 use crate::vertex::Vertex;
-use crate::color::Color;
-use crate::planet_shaders::{earth_shader, sun_shader, gas_giant_shader, mars_shader, mercury_shader, moon_shader, neptune_shader, spaceship_shader};
+use crate::planet_shaders::fixed_shader;
+use crate::bump;
 use crate::Uniforms;
 
 pub fn triangle(v1: &Vertex, v2: &Vertex, v3: &Vertex, uniforms: &Uniforms) -> Vec<Fragment> {
@@ -24,7 +24,9 @@ pub fn triangle(v1: &Vertex, v2: &Vertex, v3: &Vertex, uniforms: &Uniforms) -> V
         return fragments;
     }
 
-    let light_dir = Vec3::new(0.0, 0.0, -1.0).normalize();
+    // El relieve de la capa estática está en el espacio del modelo
+    let to_world = uniforms.normal_matrix;
+    let style = uniforms.surface;
     let triangle_area = edge_function(&a, &b, &c);
 
     if triangle_area.abs() < 0.1 {
@@ -42,27 +44,41 @@ pub fn triangle(v1: &Vertex, v2: &Vertex, v3: &Vertex, uniforms: &Uniforms) -> V
             let (w1, w2, w3) = barycentric_coordinates(&point, &a, &b, &c, triangle_area);
 
             if w1 >= 0.0 && w2 >= 0.0 && w3 >= 0.0 {
-                // Interpolar la posición del mundo y la normal
+                // Interpolar la posición (en el modelo) y la normal
                 let world_pos = v1.position * w1 + v2.position * w2 + v3.position * w3;
                 let interpolated_normal = (v1.transformed_normal * w1 + v2.transformed_normal * w2 + v3.transformed_normal * w3).normalize();
+                let depth = a.z * w1 + b.z * w2 + c.z * w3;
+
+                // La luz viene del Sol y la vista de la cámara
+                let world_point = (uniforms.model_matrix * Vec4::new(world_pos.x, world_pos.y, world_pos.z, 1.0)).xyz();
+                let light_dir = (uniforms.light_position - world_point).normalize();
+                let view_dir = (uniforms.camera_position - world_point).normalize();
+
+                if uniforms.cloud_shell {
+                    // Capa de nubes: solo donde hay nubes, mezclada con lo de debajo
+                    let clouds = style.and_then(|surface| surface.clouds(world_pos.normalize(), interpolated_normal, light_dir, uniforms.time));
+                    if let Some((color, alpha)) = clouds {
+                        fragments.push(Fragment::translucent(x as f32, y as f32, color, depth, alpha));
+                    }
+                    continue;
+                }
 
                 // Calcular el color del planeta
-                let planet_color = if uniforms.is_moon {
-                    moon_shader(world_pos, interpolated_normal, light_dir, uniforms.time)
+                let sphere_pos = world_pos.normalize();
+                let sample = style.and_then(|surface| match uniforms.baked {
+                    Some(baked) => Some(baked.sample(world_pos)),
+                    None => surface.surface(sphere_pos),
+                });
+                let planet_color = if let (Some(surface), Some(sample)) = (style, sample) {
+                    // Capa estática (horneada o calculada aquí); luz en cada cuadro
+                    let normal = bump::perturb(interpolated_normal, sphere_pos, sample.normal, &to_world);
+                    surface.shade_surface(sphere_pos, sample, normal, light_dir, view_dir, uniforms.time)
+                } else if let Some(style) = style {
+                    style.shade(world_pos, interpolated_normal, &to_world, light_dir, view_dir, uniforms.time)
                 } else {
-                    match uniforms.current_shader {
-                        0 => earth_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        1 => sun_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        2 => gas_giant_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        3 => mars_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        4 => mercury_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        5 => neptune_shader(world_pos, interpolated_normal, light_dir, uniforms.time),
-                        6 => spaceship_shader(world_pos, interpolated_normal, light_dir, uniforms.time), // Shader de nave
-                        _ => Color::new(255, 0, 255), 
-                    }
+                    fixed_shader(uniforms.current_shader, world_pos, interpolated_normal, light_dir, view_dir, uniforms.time)
                 };
-                
-                let depth = a.z * w1 + b.z * w2 + c.z * w3;
+
                 fragments.push(Fragment::new(x as f32, y as f32, planet_color, depth));
             }
         }
@@ -0,0 +1,177 @@
+use nalgebra_glm::Vec3;
+use crate::BodyState;
+
+// Tiempo de carga del motor antes de saltar (segundos)
+const CHARGE_TIME: f32 = 1.5;
+// Pseudo-velocidad durante el warp (unidades por segundo)
+const WARP_SPEED: f32 = 3000.0;
+// Velocidad con la que la nave sale del warp
+const EXIT_SPEED: f32 = 60.0;
+// Distancia de salida alrededor del destino, en radios del cuerpo
+const ARRIVAL_RADII: f32 = 3.0;
+// Radio del pozo gravitatorio de cualquier cuerpo, en radios del cuerpo
+const GRAVITY_WELL_RADII: f32 = 2.0;
+// Velocidad con la que sube/baja el efecto visual
+const STRETCH_RATE: f32 = 2.5;
+// Alineación mínima (coseno) para elegir un cuerpo como destino
+const TARGET_ALIGNMENT: f32 = 0.9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WarpState {
+    Idle,
+    Charging { progress: f32 },
+    Active,
+}
+
+pub struct WarpDrive {
+    pub state: WarpState,
+    pub target: Option<usize>,
+    // Dirección de viaje fijada al entrar en warp (o hacia el destino)
+    direction: Vec3,
+    // 0.0 = estrellas normales, 1.0 = estiramiento máximo
+    stretch: f32,
+}
+
+// Resultado de un paso del motor warp para la nave
+pub enum WarpEvent {
+    None,
+    Engaged,
+    Arrived(usize),
+    DroppedOut(usize),
+}
+
+impl WarpDrive {
+    pub fn new() -> Self {
+        WarpDrive {
+            state: WarpState::Idle,
+            target: None,
+            direction: Vec3::new(0.0, 0.0, 1.0),
+            stretch: 0.0,
+        }
+    }
+
+    pub fn is_engaged(&self) -> bool {
+        self.state != WarpState::Idle
+    }
+
+    pub fn stretch(&self) -> f32 {
+        self.stretch
+    }
+
+    // Empieza a cargar el motor o lo cancela si ya estaba en uso.
+    // El destino es el cuerpo seleccionado o, si no hay, el más alineado con la nariz de la nave.
+    pub fn toggle(&mut self, position: Vec3, forward: Vec3, bodies: &[BodyState], selected: Option<usize>) {
+        if self.is_engaged() {
+            self.state = WarpState::Idle;
+            return;
+        }
+
+        if let Some(i) = selected.filter(|&i| i < bodies.len()) {
+            self.target = Some(i);
+            self.direction = (bodies[i].position - position).normalize();
+            self.state = WarpState::Charging { progress: 0.0 };
+            return;
+        }
+
+        let mut best: Option<(usize, f32)> = None;
+        for (i, body) in bodies.iter().enumerate() {
+            let to_body = body.position - position;
+            let distance = to_body.magnitude();
+            if distance < body.radius * ARRIVAL_RADII {
+                continue;
+            }
+            let alignment = to_body.dot(&forward) / distance;
+            if alignment > TARGET_ALIGNMENT && best.is_none_or(|(_, a)| alignment > a) {
+                best = Some((i, alignment));
+            }
+        }
+
+        self.target = best.map(|(i, _)| i);
+        self.direction = forward.normalize();
+        self.state = WarpState::Charging { progress: 0.0 };
+    }
+
+    // Avanza el motor y ajusta la velocidad y orientación de la nave.
+    // `rotation` es (pitch, yaw, roll) igual que en la nave.
+    pub fn update(&mut self, dt: f32, position: Vec3, velocity: &mut Vec3, rotation: &mut Vec3, bodies: &[BodyState]) -> WarpEvent {
+        // Durante la carga y el viaje se reapunta hacia el destino, que se mueve en su órbita
+        if let Some(target) = self.target.and_then(|i| bodies.get(i)) {
+            let to_target = target.position - position;
+            if to_target.magnitude() > 0.001 {
+                self.direction = to_target.normalize();
+            }
+        }
+
+        let mut event = WarpEvent::None;
+        let stretch_goal = match self.state {
+            WarpState::Idle => 0.0,
+            WarpState::Charging { progress } => {
+                // Alinear la nave suavemente con la dirección de salto
+                let pitch = (-self.direction.y).asin();
+                let yaw = self.direction.x.atan2(self.direction.z);
+                rotation.x += (pitch - rotation.x) * (dt * 4.0).min(1.0);
+                rotation.y += wrap_angle(yaw - rotation.y) * (dt * 4.0).min(1.0);
+
+                let progress = progress + dt / CHARGE_TIME;
+                if progress >= 1.0 {
+                    self.state = WarpState::Active;
+                    event = WarpEvent::Engaged;
+                } else {
+                    self.state = WarpState::Charging { progress };
+                }
+                progress.min(1.0) * 0.15
+            }
+            WarpState::Active => {
+                rotation.x = (-self.direction.y).asin();
+                rotation.y = self.direction.x.atan2(self.direction.z);
+                *velocity = self.direction * WARP_SPEED;
+
+                // Salida automática: llegada al destino o pozo gravitatorio por delante
+                let lookahead = WARP_SPEED * dt;
+                for (i, body) in bodies.iter().enumerate() {
+                    let to_body = body.position - position;
+                    let distance = to_body.magnitude();
+                    let approaching = to_body.dot(&self.direction) > 0.0;
+                    let limit = if Some(i) == self.target {
+                        body.radius * ARRIVAL_RADII
+                    } else {
+                        body.radius * GRAVITY_WELL_RADII
+                    };
+
+                    if approaching && distance - lookahead < limit {
+                        *velocity = self.direction * EXIT_SPEED;
+                        self.state = WarpState::Idle;
+                        event = if Some(i) == self.target {
+                            WarpEvent::Arrived(i)
+                        } else {
+                            WarpEvent::DroppedOut(i)
+                        };
+                        break;
+                    }
+                }
+                1.0
+            }
+        };
+
+        let step = STRETCH_RATE * dt;
+        self.stretch += (stretch_goal - self.stretch).clamp(-step, step);
+
+        event
+    }
+
+    // Dirección hacia la que se estiran las estrellas
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+}
+
+impl Default for WarpDrive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    let two_pi = std::f32::consts::PI * 2.0;
+    (angle + std::f32::consts::PI).rem_euclid(two_pi) - std::f32::consts::PI
+}